@@ -0,0 +1,251 @@
+//! Synthesis, keygen, prove and verify times of the ec ops.
+//!
+//! ```sh
+//! cargo bench --bench ecc
+//! ```
+
+use ark_std::rand::rngs::StdRng;
+use ark_std::test_rng;
+use criterion::criterion_group;
+use criterion::criterion_main;
+use criterion::BenchmarkId;
+use criterion::Criterion;
+use halo2_native_ecc::cost::rows_for_point_mul_with_bits;
+use halo2_native_ecc::cost::Op;
+use halo2_native_ecc::cost::RowEstimate;
+use halo2_native_ecc::prelude::*;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::plonk::create_proof;
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::verify_proof;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::ProvingKey;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::poly::kzg::multiopen::ProverSHPLONK;
+use halo2_proofs::poly::kzg::multiopen::VerifierSHPLONK;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::transcript::Blake2bRead;
+use halo2_proofs::transcript::Blake2bWrite;
+use halo2_proofs::transcript::Challenge255;
+use halo2_proofs::transcript::TranscriptReadBuffer;
+use halo2_proofs::transcript::TranscriptWriterBuffer;
+use halo2curves::bn256::Bn256;
+use halo2curves::bn256::G1Affine as Bn256G1Affine;
+use halo2curves::ff::Field;
+use halo2curves::group::Curve;
+use halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+#[derive(Clone, Copy, Debug)]
+enum Bench {
+    Add,
+    Double,
+    Mul,
+    /// A mul of the generator; there is no fixed-base mul yet.
+    FixedBaseMul,
+    /// A naive MSM of n terms: n muls and n - 1 adds.
+    Msm(usize),
+}
+
+impl Bench {
+    fn name(&self) -> String {
+        match self {
+            Self::Add => "add".to_string(),
+            Self::Double => "double".to_string(),
+            Self::Mul => "mul".to_string(),
+            Self::FixedBaseMul => "fixed base mul".to_string(),
+            Self::Msm(n) => format!("msm {}", n),
+        }
+    }
+
+    /// An upper bound of the rows, each op being assigned in its own region.
+    fn rows(&self) -> RowEstimate {
+        let point = RowEstimate::new().op(Op::LoadPoint).op(Op::Pad);
+        let scalar = RowEstimate::new().op(Op::DecomposeScalar).op(Op::Pad);
+        let double = RowEstimate::new()
+            .op(Op::LoadPoint)
+            .op(Op::PointDouble)
+            .op(Op::Pad);
+        let add = RowEstimate::new()
+            .repeat(Op::LoadPoint, 2)
+            .op(Op::LoadField)
+            .op(Op::ConditionalPointAdd)
+            .op(Op::Pad);
+        let mul = RowEstimate::new()
+            .extra_rows(rows_for_point_mul_with_bits(256))
+            .op(Op::Pad);
+        let terms = |n: usize| {
+            (point.total_rows() + scalar.total_rows() + mul.total_rows()) * n
+                + add.total_rows() * n.saturating_sub(1)
+        };
+
+        let rows = match self {
+            Self::Add => point.total_rows() + double.total_rows() + add.total_rows(),
+            Self::Double => point.total_rows() + double.total_rows(),
+            Self::Mul | Self::FixedBaseMul => terms(1),
+            Self::Msm(n) => terms(*n),
+        };
+        RowEstimate::new().extra_rows(rows)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+struct BenchCircuit {
+    bench: Bench,
+    p: Value<G1Affine>,
+    s: Value<Fr>,
+}
+
+impl BenchCircuit {
+    fn new(bench: Bench, rng: &mut StdRng) -> Self {
+        let p = match bench {
+            Bench::FixedBaseMul => G1::generator().to_affine(),
+            _ => G1::random(&mut *rng).to_affine(),
+        };
+        Self {
+            bench,
+            p: Value::known(p),
+            s: Value::known(Fr::random(rng)),
+        }
+    }
+}
+
+impl Circuit<Fq> for BenchCircuit {
+    type Config = GrumpkinConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            bench: self.bench,
+            p: Value::unknown(),
+            s: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        GrumpkinChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = GrumpkinChip::construct(config);
+
+        let p = chip.witness_point(&mut layouter, self.p)?;
+        let terms = match self.bench {
+            Bench::Add => {
+                let p2 = chip.double(&mut layouter, &p)?;
+                EccInstructions::add(&chip, &mut layouter, &p, &p2)?;
+                return Ok(());
+            }
+            Bench::Double => {
+                chip.double(&mut layouter, &p)?;
+                return Ok(());
+            }
+            Bench::Mul | Bench::FixedBaseMul => 1,
+            Bench::Msm(n) => n,
+        };
+
+        let mut acc: Option<AssignedGrumpkinPoint> = None;
+        for i in 0..terms {
+            // distinct scalars, so that the terms differ
+            let s = chip.witness_scalar(&mut layouter, self.s.map(|s| s + Fr::from(i as u64)))?;
+            let term = EccInstructions::mul(&chip, &mut layouter, &s, &p)?;
+            acc = Some(match acc {
+                Some(acc) => EccInstructions::add(&chip, &mut layouter, &acc, &term)?,
+                None => term,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn prove(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<Bn256G1Affine>,
+    circuit: BenchCircuit,
+    rng: &mut StdRng,
+) -> Vec<u8> {
+    let mut transcript = Blake2bWrite::<_, Bn256G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&[]],
+        rng,
+        &mut transcript,
+    )
+    .unwrap();
+    transcript.finalize()
+}
+
+fn bench_ops(c: &mut Criterion) {
+    let mut rng = test_rng();
+    let benches = [
+        Bench::Add,
+        Bench::Double,
+        Bench::Mul,
+        Bench::FixedBaseMul,
+        Bench::Msm(2),
+        Bench::Msm(4),
+        Bench::Msm(8),
+    ];
+
+    let mut group = c.benchmark_group("ecc");
+    group.sample_size(10);
+    for bench in benches {
+        let name = bench.name();
+        let rows = bench.rows();
+        let k = rows.min_k();
+        println!("{}: at most {} rows, k = {}", name, rows.total_rows(), k);
+
+        let circuit = BenchCircuit::new(bench, &mut rng);
+        let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+        let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+        let pk = keygen_pk(&params, vk.clone(), &circuit.without_witnesses()).unwrap();
+        let proof = prove(&params, &pk, circuit, &mut rng);
+
+        group.bench_function(BenchmarkId::new("synthesize", &name), |b| {
+            b.iter(|| MockProver::run(k, &circuit, vec![]).unwrap())
+        });
+        group.bench_function(BenchmarkId::new("keygen", &name), |b| {
+            b.iter(|| {
+                let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+                keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap()
+            })
+        });
+        group.bench_function(BenchmarkId::new("prove", &name), |b| {
+            b.iter(|| prove(&params, &pk, circuit, &mut rng))
+        });
+        group.bench_function(BenchmarkId::new("verify", &name), |b| {
+            b.iter(|| {
+                let mut transcript =
+                    Blake2bRead::<_, Bn256G1Affine, Challenge255<_>>::init(&proof[..]);
+                verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+                    &params,
+                    &vk,
+                    SingleStrategy::new(&params),
+                    &[&[]],
+                    &mut transcript,
+                )
+                .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_ops);
+criterion_main!(benches);
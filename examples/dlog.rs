@@ -0,0 +1,165 @@
+//! Proves the knowledge of a discrete log on Grumpkin with the KZG backend.
+//!
+//! The circuit runs over the scalar field of BN254, which is the base field of
+//! Grumpkin; the points g and q = s * g are public, the scalar s is private.
+//!
+//! ```sh
+//! cargo run --release --example dlog
+//! ```
+
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::path::Path;
+use std::time::Instant;
+
+use ark_std::rand::rngs::OsRng;
+use halo2_native_ecc::cost;
+use halo2_native_ecc::keys;
+use halo2_native_ecc::prelude::*;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::create_proof;
+use halo2_proofs::plonk::verify_proof;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::poly::kzg::multiopen::ProverSHPLONK;
+use halo2_proofs::poly::kzg::multiopen::VerifierSHPLONK;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::transcript::Blake2bRead;
+use halo2_proofs::transcript::Blake2bWrite;
+use halo2_proofs::transcript::Challenge255;
+use halo2_proofs::transcript::TranscriptReadBuffer;
+use halo2_proofs::transcript::TranscriptWriterBuffer;
+use halo2curves::bn256::Bn256;
+use halo2curves::bn256::G1Affine as Bn256G1Affine;
+use halo2curves::ff::Field;
+use halo2curves::group::Curve;
+use halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+use halo2curves::CurveAffine;
+
+/// Proves q = s * g for the public points g and q.
+#[derive(Default, Debug, Clone, Copy)]
+struct DlogCircuit {
+    g: Value<G1Affine>,
+    s: Value<Fr>,
+}
+
+impl Circuit<Fq> for DlogCircuit {
+    type Config = GrumpkinConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        GrumpkinChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = GrumpkinChip::construct(config);
+
+        let g = chip.witness_point(&mut layouter, self.g)?;
+        let s = chip.witness_scalar(&mut layouter, self.s)?;
+        let q = EccInstructions::mul(&chip, &mut layouter, &s, &g)?;
+
+        // public inputs: (x, y) of g, then (x, y) of q
+        chip.expose_point_public(&mut layouter, &g, 0)?;
+        chip.expose_point_public(&mut layouter, &q, 2)?;
+
+        Ok(())
+    }
+}
+
+/// Reads the params cached at `path`, or generates them and caches them there.
+fn load_or_setup_params(path: &Path, k: u32) -> io::Result<ParamsKZG<Bn256>> {
+    if let Ok(file) = File::open(path) {
+        return ParamsKZG::read(&mut BufReader::new(file));
+    }
+    let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+    params.write(&mut BufWriter::new(File::create(path)?))?;
+    Ok(params)
+}
+
+fn main() -> Result<(), Error> {
+    // the witness regions of g and s, and the scalar mul, with their padding
+    let rows = cost::RowEstimate::new()
+        .op(cost::Op::LoadPoint)
+        .op(cost::Op::DecomposeScalar)
+        .extra_rows(cost::rows_for_point_mul_with_bits(256))
+        .repeat(cost::Op::Pad, 3);
+    let k = rows.min_k();
+    println!("{} rows, k = {}", rows.total_rows(), k);
+
+    let g = G1::generator().to_affine();
+    let s = Fr::random(OsRng);
+    let q = (g * s).to_affine();
+    let circuit = DlogCircuit {
+        g: Value::known(g),
+        s: Value::known(s),
+    };
+    let g = g.coordinates().unwrap();
+    let q = q.coordinates().unwrap();
+    let instance = vec![*g.x(), *g.y(), *q.x(), *q.y()];
+
+    // the params and the proving key are cached across runs; the key is
+    // only valid for the params it was generated with
+    let start = Instant::now();
+    let params = load_or_setup_params(&env::temp_dir().join(format!("dlog-k{}.params", k)), k)
+        .map_err(Error::Transcript)?;
+    let pk = keys::load_or_keygen_pk(
+        &env::temp_dir().join(format!("dlog-k{}.pk", k)),
+        &params,
+        &circuit,
+        keys::circuit_layout_hash::<DlogCircuit>(),
+    )
+    .map_err(Error::Transcript)?;
+    println!("keygen: {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let mut transcript = Blake2bWrite::<_, Bn256G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[&instance]],
+        OsRng,
+        &mut transcript,
+    )?;
+    let proof = transcript.finalize();
+    println!(
+        "prove: {:?}, proof of {} bytes",
+        start.elapsed(),
+        proof.len()
+    );
+
+    let start = Instant::now();
+    let mut transcript = Blake2bRead::<_, Bn256G1Affine, Challenge255<_>>::init(&proof[..]);
+    verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+        &params,
+        pk.get_vk(),
+        SingleStrategy::new(&params),
+        &[&[&instance]],
+        &mut transcript,
+    )?;
+    println!("verify: {:?}", start.elapsed());
+
+    Ok(())
+}
@@ -0,0 +1,195 @@
+//! Verifies a proof of the chip with the EVM verifier of snark-verifier.
+//!
+//! The circuit proves q = s * g for the public points g and q, as in the
+//! `dlog` example. Its public points are laid out by `point_instances`, which
+//! is the instance layout snark-verifier compiles the verifier for, and are
+//! passed to the verifier contract as calldata.
+//!
+//! ```sh
+//! cargo run --release --example evm_verifier --features snark-verifier
+//! ```
+
+use std::rc::Rc;
+
+use halo2_native_ecc::cost;
+use halo2_native_ecc::prelude::*;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::plonk::create_proof;
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::ProvingKey;
+use halo2_proofs::plonk::VerifyingKey;
+use halo2_proofs::poly::commitment::Params;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::poly::kzg::multiopen::ProverGWC;
+use halo2_proofs::transcript::TranscriptWriterBuffer;
+use halo2curves::bn256;
+use halo2curves::bn256::Bn256;
+use halo2curves::ff::Field;
+use halo2curves::group::Curve;
+use halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+use rand_core::OsRng;
+use snark_verifier::loader::evm;
+use snark_verifier::loader::evm::encode_calldata;
+use snark_verifier::loader::evm::Address;
+use snark_verifier::loader::evm::EvmLoader;
+use snark_verifier::loader::evm::ExecutorBuilder;
+use snark_verifier::pcs::kzg::Gwc19;
+use snark_verifier::pcs::kzg::KzgAs;
+use snark_verifier::system::halo2::compile;
+use snark_verifier::system::halo2::transcript::evm::EvmTranscript;
+use snark_verifier::system::halo2::Config;
+use snark_verifier::verifier::plonk::PlonkVerifier;
+use snark_verifier::verifier::SnarkVerifier;
+
+type Verifier = PlonkVerifier<KzgAs<Bn256, Gwc19>>;
+
+/// Proves q = s * g for the public points g and q.
+#[derive(Default, Debug, Clone, Copy)]
+struct DlogCircuit {
+    g: Value<G1Affine>,
+    s: Value<Fr>,
+}
+
+impl Circuit<Fq> for DlogCircuit {
+    type Config = GrumpkinConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        GrumpkinChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = GrumpkinChip::construct(config);
+
+        let g = chip.witness_point(&mut layouter, self.g)?;
+        let s = chip.witness_scalar(&mut layouter, self.s)?;
+        let q = EccInstructions::mul(&chip, &mut layouter, &s, &g)?;
+
+        // the layout of `point_instances`: (x, y) of g, then (x, y) of q
+        chip.expose_point_public(&mut layouter, &g, 0)?;
+        chip.expose_point_public(&mut layouter, &q, 2)?;
+
+        Ok(())
+    }
+}
+
+/// The deployment code of the verifier contract of the circuit.
+fn gen_evm_verifier(
+    params: &ParamsKZG<Bn256>,
+    vk: &VerifyingKey<bn256::G1Affine>,
+    num_instance: Vec<usize>,
+) -> Vec<u8> {
+    let protocol = compile(
+        params,
+        vk,
+        Config::kzg().with_num_instance(num_instance.clone()),
+    );
+    let vk = (params.get_g()[0], params.g2(), params.s_g2()).into();
+
+    let loader = EvmLoader::new::<bn256::Fq, bn256::Fr>();
+    let protocol = protocol.loaded(&loader);
+    let mut transcript = EvmTranscript::<_, Rc<EvmLoader>, _, _>::new(&loader);
+
+    let instances = transcript.load_instances(num_instance);
+    let proof = Verifier::read_proof(&vk, &protocol, &instances, &mut transcript).unwrap();
+    Verifier::verify(&vk, &protocol, &instances, &proof).unwrap();
+
+    evm::compile_yul(&loader.yul_code())
+}
+
+fn gen_proof(
+    params: &ParamsKZG<Bn256>,
+    pk: &ProvingKey<bn256::G1Affine>,
+    circuit: DlogCircuit,
+    instance: &[Fq],
+) -> Vec<u8> {
+    let mut transcript = TranscriptWriterBuffer::<_, bn256::G1Affine, _>::init(Vec::new());
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverGWC<_>, _, _, EvmTranscript<_, _, _, _>, _>(
+        params,
+        pk,
+        &[circuit],
+        &[&[instance]],
+        OsRng,
+        &mut transcript,
+    )
+    .unwrap();
+    transcript.finalize()
+}
+
+/// Deploys the verifier and calls it with the instance and the proof.
+fn evm_verify(deployment_code: Vec<u8>, instance: Vec<Fq>, proof: Vec<u8>) -> bool {
+    let calldata = encode_calldata(&[instance], &proof);
+    let mut evm = ExecutorBuilder::default()
+        .with_gas_limit(u64::MAX.into())
+        .build();
+
+    let caller = Address::from_low_u64_be(0xfe);
+    let verifier = evm
+        .deploy(caller, deployment_code.into(), 0.into())
+        .address
+        .unwrap();
+    let result = evm.call_raw(caller, verifier, calldata.into(), 0.into());
+    println!("verification gas: {}", result.gas_used);
+    !result.reverted
+}
+
+fn main() {
+    // the witness regions of g and s, and the scalar mul, with their padding
+    let rows = cost::RowEstimate::new()
+        .op(cost::Op::LoadPoint)
+        .op(cost::Op::DecomposeScalar)
+        .extra_rows(cost::rows_for_point_mul_with_bits(256))
+        .repeat(cost::Op::Pad, 3);
+    let k = rows.min_k();
+
+    let g = G1::generator().to_affine();
+    let s = Fr::random(OsRng);
+    let q = (g * s).to_affine();
+    let circuit = DlogCircuit {
+        g: Value::known(g),
+        s: Value::known(s),
+    };
+    let instance = GrumpkinChip::point_instances(&[g, q]).unwrap();
+    MockProver::run(k, &circuit, vec![instance.clone()])
+        .unwrap()
+        .assert_satisfied();
+
+    let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+    let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+
+    let deployment_code = gen_evm_verifier(&params, pk.get_vk(), vec![instance.len()]);
+    let proof = gen_proof(&params, &pk, circuit, &instance);
+    assert!(evm_verify(
+        deployment_code.clone(),
+        instance.clone(),
+        proof.clone()
+    ));
+
+    // error case: q is not s * g
+    let mut wrong = instance;
+    wrong[3] += Fq::ONE;
+    assert!(!evm_verify(deployment_code, wrong, proof));
+    println!("verified on the evm");
+}
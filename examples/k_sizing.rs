@@ -0,0 +1,132 @@
+//! Sizes a circuit of the chip from a description of its workload, before
+//! writing any circuit code: the estimated rows, the smallest k of the SRS,
+//! and the columns used by the chip.
+//!
+//! ```sh
+//! cargo run --example k_sizing -- adds=100 muls=2 msm=8 regions=4
+//! ```
+//!
+//! The workload keys are:
+//! - `points`: points loaded with the on curve check
+//! - `adds`: point adds, each with the copies of its two operands
+//! - `doubles`: point doubles, each with the copy of its operand
+//! - `muls`: variable base scalar muls
+//! - `fixed_muls`: scalar muls of the generator
+//! - `msm`: the number of terms of a naive MSM
+//! - `regions`: the number of regions, each padded once (default 1)
+//! - `public`: 1 if the chip has an instance column (default 0)
+
+use std::env;
+use std::process;
+
+use halo2_native_ecc::cost;
+use halo2_native_ecc::cost::Op;
+use halo2_native_ecc::cost::RowEstimate;
+use halo2_native_ecc::prelude::*;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2curves::grumpkin::Fq;
+
+const USAGE: &str = "usage: k_sizing [points=N] [adds=N] [doubles=N] [muls=N] \
+                     [fixed_muls=N] [msm=N] [regions=N] [public=0|1]";
+
+#[derive(Debug)]
+struct Workload {
+    points: usize,
+    adds: usize,
+    doubles: usize,
+    muls: usize,
+    fixed_muls: usize,
+    msm: usize,
+    regions: usize,
+    public: bool,
+}
+
+impl Default for Workload {
+    fn default() -> Self {
+        Self {
+            points: 0,
+            adds: 0,
+            doubles: 0,
+            muls: 0,
+            fixed_muls: 0,
+            msm: 0,
+            regions: 1,
+            public: false,
+        }
+    }
+}
+
+impl Workload {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut res = Self::default();
+        for arg in args {
+            let (key, value) = arg
+                .split_once('=')
+                .ok_or_else(|| format!("expected key=value, got {}", arg))?;
+            let n: usize = value
+                .parse()
+                .map_err(|_| format!("expected a number for {}, got {}", key, value))?;
+            match key {
+                "points" => res.points = n,
+                "adds" => res.adds = n,
+                "doubles" => res.doubles = n,
+                "muls" => res.muls = n,
+                "fixed_muls" => res.fixed_muls = n,
+                "msm" => res.msm = n,
+                "regions" => res.regions = n,
+                "public" => res.public = n != 0,
+                _ => return Err(format!("unknown key {}", key)),
+            }
+        }
+        Ok(res)
+    }
+
+    fn estimate(&self) -> RowEstimate {
+        RowEstimate::new()
+            .repeat(Op::LoadPoint, self.points)
+            .repeat(Op::LoadPointUnchecked, 2 * self.adds)
+            .repeat(Op::ConditionalPointAdd, self.adds)
+            .repeat(Op::LoadPointUnchecked, self.doubles)
+            .repeat(Op::PointDouble, self.doubles)
+            .repeat(Op::PointMul, self.muls)
+            .repeat(Op::GeneratorMul, self.fixed_muls)
+            .extra_rows(cost::rows_for_msm(self.msm, 256))
+            .repeat(Op::Pad, self.regions)
+    }
+}
+
+fn main() {
+    let workload = Workload::parse(env::args().skip(1)).unwrap_or_else(|e| {
+        eprintln!("{}\n{}", e, USAGE);
+        process::exit(1);
+    });
+
+    let mut meta = ConstraintSystem::<Fq>::default();
+    let config = if workload.public {
+        let instance = meta.instance_column();
+        GrumpkinChip::configure_with_instance(&mut meta, instance)
+    } else {
+        GrumpkinChip::configure(&mut meta)
+    };
+    let stats = config.stats();
+
+    let estimate = workload.estimate();
+    let k = estimate.min_k();
+    let usable = (1usize << k) - cost::RESERVED_ROWS;
+    println!("{:?}", workload);
+    println!(
+        "rows: {} (+{} reserved)",
+        estimate.total_rows(),
+        cost::RESERVED_ROWS
+    );
+    println!(
+        "k: {} ({:.1}% of the usable rows)",
+        k,
+        100.0 * estimate.total_rows() as f64 / usable as f64
+    );
+    println!(
+        "columns: {} advice, {} fixed, {} instance, {} selectors",
+        stats.advice_columns, stats.fixed_columns, stats.instance_columns, stats.selectors
+    );
+    println!("max degree: {}", stats.max_degree);
+}
@@ -0,0 +1,89 @@
+//! Renders the layout of a circuit of the chip to `layout.png`, one labelled
+//! region per op, to see how `point_mul`, `generator_mul` and an MSM use the
+//! rows when tuning k.
+//!
+//! ```sh
+//! cargo run --example layout --features dev-graph
+//! ```
+
+use std::path::Path;
+
+use halo2_native_ecc::cost;
+use halo2_native_ecc::cost::Op;
+use halo2_native_ecc::cost::RowEstimate;
+use halo2_native_ecc::prelude::*;
+use halo2_native_ecc::testing::render_layout;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+
+/// The terms of the MSM.
+const MSM_TERMS: usize = 4;
+
+#[derive(Default, Debug, Clone)]
+struct LayoutCircuit {
+    p: Value<G1Affine>,
+    s: Value<Fr>,
+    terms: Vec<(Value<G1Affine>, Value<Fr>)>,
+}
+
+impl Circuit<Fq> for LayoutCircuit {
+    type Config = GrumpkinConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            terms: vec![(Value::unknown(), Value::unknown()); self.terms.len()],
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        GrumpkinChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = GrumpkinChip::construct(config);
+
+        chip.assign_scoped(
+            &mut layouter,
+            || "point_mul",
+            |ecc| ecc.point_mul(self.p, self.s),
+        )?;
+        chip.assign_scoped(
+            &mut layouter,
+            || "generator_mul",
+            |ecc| ecc.generator_mul(self.s),
+        )?;
+        // one region per chunk of 2 terms
+        chip.msm_chunked(&mut layouter, &self.terms, 2)?;
+
+        Ok(())
+    }
+}
+
+fn main() {
+    let k = RowEstimate::new()
+        .op(Op::PointMul)
+        .op(Op::GeneratorMul)
+        .extra_rows(cost::rows_for_msm(MSM_TERMS, 256))
+        .repeat(Op::Pad, 2 + MSM_TERMS / 2)
+        .min_k();
+
+    let circuit = LayoutCircuit {
+        terms: vec![(Value::unknown(), Value::unknown()); MSM_TERMS],
+        ..LayoutCircuit::default()
+    };
+    render_layout(k, &circuit, "native ec chip", Path::new("layout.png")).unwrap();
+    println!("rendered layout.png, k = {}", k);
+}
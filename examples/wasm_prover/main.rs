@@ -0,0 +1,122 @@
+//! Proves a Grumpkin point doubling in the browser.
+//!
+//! Build with
+//!
+//! ```sh
+//! cargo build --release --target wasm32-unknown-unknown --example wasm_prover --features wasm
+//! wasm-bindgen --target web --out-dir examples/wasm_prover/pkg \
+//!     target/wasm32-unknown-unknown/release/examples/wasm_prover.wasm
+//! ```
+//!
+//! and serve `examples/wasm_prover/index.html`.
+
+use halo2_native_ecc::prelude::*;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::create_proof;
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::verify_proof;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::poly::kzg::multiopen::ProverSHPLONK;
+use halo2_proofs::poly::kzg::multiopen::VerifierSHPLONK;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::transcript::Blake2bRead;
+use halo2_proofs::transcript::Blake2bWrite;
+use halo2_proofs::transcript::Challenge255;
+use halo2_proofs::transcript::TranscriptReadBuffer;
+use halo2_proofs::transcript::TranscriptWriterBuffer;
+use halo2curves::bn256::Bn256;
+use halo2curves::bn256::G1Affine as Bn256G1Affine;
+use halo2curves::group::Curve;
+use halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+use rand_core::OsRng;
+use wasm_bindgen::prelude::wasm_bindgen;
+use wasm_bindgen::JsError;
+
+/// Proves p2 = 2 * p.
+#[derive(Default, Debug, Clone, Copy)]
+struct DoubleCircuit {
+    p: Value<G1Affine>,
+    p2: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for DoubleCircuit {
+    type Config = GrumpkinConfig;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        GrumpkinChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = GrumpkinChip::construct(config);
+
+        let p = chip.witness_point(&mut layouter, self.p)?;
+        let p2 = chip.witness_point(&mut layouter, self.p2)?;
+        let p2_rec = chip.double(&mut layouter, &p)?;
+        chip.constrain_equal(&mut layouter, &p2, &p2_rec)?;
+
+        Ok(())
+    }
+}
+
+/// Proves the doubling of a random point, verifies the proof,
+/// and returns the size of the proof in bytes.
+#[wasm_bindgen]
+pub fn prove_random_double(k: u32) -> Result<usize, JsError> {
+    let p = G1::random(OsRng).to_affine();
+    let p2 = (p + p).to_affine();
+    let circuit = DoubleCircuit {
+        p: Value::known(p),
+        p2: Value::known(p2),
+    };
+
+    let params = ParamsKZG::<Bn256>::setup(k, OsRng);
+    let vk = keygen_vk(&params, &circuit.without_witnesses()).map_err(to_js_error)?;
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).map_err(to_js_error)?;
+
+    let mut transcript = Blake2bWrite::<_, Bn256G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[]],
+        OsRng,
+        &mut transcript,
+    )
+    .map_err(to_js_error)?;
+    let proof = transcript.finalize();
+
+    let mut transcript = Blake2bRead::<_, Bn256G1Affine, Challenge255<_>>::init(&proof[..]);
+    verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+        &params,
+        pk.get_vk(),
+        SingleStrategy::new(&params),
+        &[&[]],
+        &mut transcript,
+    )
+    .map_err(to_js_error)?;
+
+    Ok(proof.len())
+}
+
+fn to_js_error(e: Error) -> JsError {
+    JsError::new(&format!("{:?}", e))
+}
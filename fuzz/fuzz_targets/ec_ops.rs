@@ -0,0 +1,166 @@
+//! Differential fuzzing of the chip against halo2curves.
+//!
+//! Runs a random sequence of ops over a pool of points through the MockProver,
+//! and checks the result of each op against the arithmetic of halo2curves.
+//! The operands loaded from the pool are bound to the cells of the op that
+//! computed them, so the ops are chained as in a real circuit.
+//!
+//! ```sh
+//! cargo +nightly fuzz run ec_ops
+//! ```
+//!
+//! The exceptional cases of the incomplete formulas, i.e., p + p, p - p and
+//! the identity, are skipped: the chip rejects them by design.
+
+#![no_main]
+
+use arbitrary::Arbitrary;
+use halo2_native_ecc::cost::Op;
+use halo2_native_ecc::cost::RowEstimate;
+use halo2_native_ecc::testing::run_gadget;
+use halo2_native_ecc::ArithOps;
+use halo2_native_ecc::AssignedECPoint;
+use halo2_native_ecc::ECChip;
+use halo2_native_ecc::NativeECOps;
+use halo2_native_ecc::NativeEccError;
+use halo2_native_ecc::RegionCtx;
+use halo2_proofs::circuit::Value;
+use halo2curves::ff::Field;
+use halo2curves::group::prime::PrimeCurveAffine;
+use halo2curves::group::Curve;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use libfuzzer_sys::fuzz_target;
+
+/// The largest circuit of a run.
+const MAX_K: u32 = 14;
+
+#[derive(Arbitrary, Debug)]
+enum EcOp {
+    /// pool[i] + pool[j] if the condition is set, pool[i] otherwise.
+    Add(u8, u8, bool),
+    Double(u8),
+    Mul(u8, [u64; 4]),
+    GeneratorMul([u64; 4]),
+}
+
+#[derive(Arbitrary, Debug)]
+struct Input {
+    /// The initial points of the pool, as multiples of the generator.
+    seeds: Vec<u64>,
+    ops: Vec<EcOp>,
+}
+
+impl EcOp {
+    fn rows(&self) -> RowEstimate {
+        let estimate = RowEstimate::new();
+        match self {
+            Self::Add(..) => estimate
+                .repeat(Op::LoadPoint, 2)
+                .op(Op::LoadField)
+                .op(Op::ConditionalPointAdd),
+            Self::Double(_) => estimate.op(Op::LoadPoint).op(Op::PointDouble),
+            Self::Mul(..) => estimate.op(Op::PointMul),
+            Self::GeneratorMul(_) => estimate.op(Op::GeneratorMul),
+        }
+    }
+}
+
+/// A point of the pool, with its cells once assigned.
+type Entry = (G1Affine, Option<AssignedECPoint<G1Affine, Fq>>);
+
+/// Loads a point of the pool, bound to its cells if already assigned.
+fn load(
+    chip: &ECChip<G1Affine, Fq>,
+    ctx: &mut RegionCtx<Fq>,
+    (p, cells): &Entry,
+) -> Result<AssignedECPoint<G1Affine, Fq>, NativeEccError> {
+    let res = chip.load_private_point(ctx, chip.config(), Value::known(*p))?;
+    if let Some(cells) = cells {
+        ctx.constrain_equal(res.x().cell(), cells.x().cell())?;
+        ctx.constrain_equal(res.y().cell(), cells.y().cell())?;
+    }
+    Ok(res)
+}
+
+fuzz_target!(|input: Input| {
+    let g = G1Affine::generator();
+    let pool: Vec<Entry> = input
+        .seeds
+        .iter()
+        .filter(|seed| **seed != 0)
+        .map(|seed| ((g * Fr::from(*seed)).to_affine(), None))
+        .collect();
+    if pool.is_empty() {
+        return;
+    }
+
+    // the ops that fit in the largest circuit
+    let mut estimate = RowEstimate::new().op(Op::Pad);
+    let mut ops = vec![];
+    for op in input.ops {
+        let next = estimate.extra_rows(op.rows().total_rows());
+        if next.min_k() > MAX_K {
+            break;
+        }
+        estimate = next;
+        ops.push(op);
+    }
+
+    let prover = run_gadget::<G1Affine, Fq, _>(estimate.min_k(), |chip, ctx| {
+        let config = chip.config();
+        let mut pool = pool.clone();
+
+        for op in ops.iter() {
+            let (expected, res) = match op {
+                EcOp::Add(i, j, b) => {
+                    let p1 = &pool[*i as usize % pool.len()];
+                    let p2 = &pool[*j as usize % pool.len()];
+                    if *b && (p1.0 == p2.0 || p1.0 == -p2.0) {
+                        continue;
+                    }
+                    let expected = if *b { (p1.0 + p2.0).to_affine() } else { p1.0 };
+                    let p1 = load(chip, ctx, p1)?;
+                    let p2 = load(chip, ctx, p2)?;
+                    let b = Value::known(if *b { Fq::ONE } else { Fq::ZERO });
+                    let b = chip.load_private_field(ctx, config, b)?;
+                    let res = chip.conditional_point_add(ctx, config, &p1, &p2, &b)?;
+                    (expected, res)
+                }
+                EcOp::Double(i) => {
+                    let p = &pool[*i as usize % pool.len()];
+                    let expected = (p.0 + p.0).to_affine();
+                    let p = load(chip, ctx, p)?;
+                    (expected, chip.point_double(ctx, config, &p)?)
+                }
+                EcOp::Mul(i, s) => {
+                    let p = pool[*i as usize % pool.len()].0;
+                    let s = Fr::from_raw(*s);
+                    let expected = (p * s).to_affine();
+                    if bool::from(expected.is_identity()) {
+                        continue;
+                    }
+                    let res = chip.point_mul(ctx, config, Value::known(p), Value::known(s))?;
+                    (expected, res)
+                }
+                EcOp::GeneratorMul(s) => {
+                    let s = Fr::from_raw(*s);
+                    let expected = (g * s).to_affine();
+                    if bool::from(expected.is_identity()) {
+                        continue;
+                    }
+                    (expected, chip.generator_mul(ctx, config, Value::known(s))?)
+                }
+            };
+            res.value().assert_if_known(|res| *res == expected);
+            pool.push((expected, Some(res)));
+        }
+        Ok(())
+    })
+    .unwrap();
+
+    if let Err(failures) = prover.verify() {
+        panic!("{:#?}\nops: {:#?}", failures, ops);
+    }
+});
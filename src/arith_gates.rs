@@ -1,15 +1,19 @@
-use halo2_proofs::arithmetic::Field;
+use alloc::vec;
+use alloc::vec::Vec;
+
 use halo2_proofs::circuit::AssignedCell;
-use halo2_proofs::circuit::Region;
 use halo2_proofs::circuit::Value;
-use halo2_proofs::halo2curves::ff::PrimeField;
-use halo2_proofs::halo2curves::CurveAffine;
-use halo2_proofs::plonk::Error;
 
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::PrimeField;
+use crate::region_ctx::RegionCtx;
+use crate::util::annotation;
 use crate::ECChip;
 use crate::ECConfig;
+use crate::NativeEccError;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "pse"))]
 mod tests;
 
 pub trait ArithOps<F: Field> {
@@ -18,41 +22,84 @@ pub trait ArithOps<F: Field> {
     /// Load a private field element
     fn load_private_field(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        f: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error>;
+        f: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>;
 
     /// Load two private field elements
     fn load_two_private_fields(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        f1: &F,
-        f2: &F,
-        offset: &mut usize,
-    ) -> Result<[AssignedCell<F, F>; 2], Error>;
+        f1: Value<F>,
+        f2: Value<F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError>;
 
     /// Add two cells and return the sum
     fn add(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        a: &F,
-        b: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error>;
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>;
 
     /// Multiply two cells and return the product
     fn mul(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>;
+
+    /// The elementwise sum of two vectors of assigned cells, of the same length.
+    ///
+    /// The elements are added one after the other, 2 rows each.
+    fn vec_add(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>;
+
+    /// The elementwise product of two vectors of assigned cells, of the same length.
+    ///
+    /// The elements are multiplied one after the other, 2 rows each.
+    fn vec_mul(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>;
+
+    /// The product of a vector of assigned cells by an assigned scalar, 2 rows per element.
+    fn vec_scale(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        k: &AssignedCell<F, F>,
+        v: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>;
+
+    /// Divides a by b, returning (q, z) where z is a flag of b = 0.
+    ///
+    /// Ensures
+    /// - q * b = a and z = 0 if b != 0
+    /// - q = 0 and z = 1 if b = 0
+    ///
+    /// Witnesses the inverse of b, or 0, and takes 11 rows of the add and mul gates.
+    #[allow(clippy::type_complexity)]
+    fn safe_div(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        a: &F,
-        b: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error>;
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), NativeEccError>;
 
     /// Input x1, y1, x2, y2, x3, y3
     /// Assert that
@@ -60,11 +107,10 @@ pub trait ArithOps<F: Field> {
     /// - x1, y1, x2, y2 are all binary
     fn partial_bit_decomp(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        inputs: &[F],
-        offset: &mut usize,
-    ) -> Result<Vec<AssignedCell<F, F>>, Error>;
+        inputs: &[Value<F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>;
 
     /// Input a u128,
     /// Output
@@ -73,11 +119,10 @@ pub trait ArithOps<F: Field> {
     #[allow(clippy::type_complexity)]
     fn decompose_u128(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        input: &u128,
-        offset: &mut usize,
-    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), Error>;
+        input: Value<u128>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), NativeEccError>;
 }
 
 impl<C, F> ArithOps<F> for ECChip<C, F>
@@ -90,103 +135,133 @@ where
     // Load a private field element
     fn load_private_field(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        f: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error> {
-        let res = region.assign_advice(|| "field element", config.a, *offset, || Value::known(*f));
-        let _ = region.assign_advice(
-            || "field element",
+        f: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        let res = ctx.assign_advice(|| annotation("load field", "f"), config.a, f)?;
+        ctx.assign_advice(
+            || annotation("load field", "zero"),
             config.b,
-            *offset,
-            || Value::known(F::ZERO),
-        );
+            Value::known(F::ZERO),
+        )?;
 
-        *offset += 1;
-        res
+        ctx.next();
+        Ok(res)
     }
 
     /// Load two private field elements
     fn load_two_private_fields(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        f1: &F,
-        f2: &F,
-        offset: &mut usize,
-    ) -> Result<[AssignedCell<F, F>; 2], Error> {
-        let a =
-            region.assign_advice(|| "field element", config.a, *offset, || Value::known(*f1))?;
-        let b =
-            region.assign_advice(|| "field element", config.b, *offset, || Value::known(*f2))?;
-
-        *offset += 1;
+        f1: Value<F>,
+        f2: Value<F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError> {
+        let a = ctx.assign_advice(|| annotation("load two fields", "f1"), config.a, f1)?;
+        let b = ctx.assign_advice(|| annotation("load two fields", "f2"), config.b, f2)?;
+
+        ctx.next();
         Ok([a, b])
     }
 
     /// Add two cells and return the sum
     fn add(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        a: &F,
-        b: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error> {
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        config.require_gate(config.gates.add, "add")?;
+
         // |         add |   2  |       0      | 0  | 1  | 0  | a1 = a0 + b0
-        config.q2.enable(region, *offset)?;
-        region.assign_advice(|| "field element", config.a, *offset, || Value::known(*a))?;
-        region.assign_advice(|| "field element", config.b, *offset, || Value::known(*b))?;
-
-        let c = *a + *b;
-        let res = region.assign_advice(
-            || "field element",
-            config.a,
-            *offset + 1,
-            || Value::known(c),
-        );
-        let _ = region.assign_advice(
-            || "field element",
-            config.b,
-            *offset + 1,
-            || Value::known(F::ZERO),
-        );
+        ctx.enable_selector(&config.q2, ctx.offset())?;
+        ctx.assign_advice(|| annotation("add", "a0"), config.a, a)?;
+        ctx.assign_advice(|| annotation("add", "b0"), config.b, b)?;
+        ctx.next();
 
-        *offset += 2;
-        res
+        let c = a + b;
+        let res = ctx.assign_advice(|| annotation("add", "a1"), config.a, c)?;
+        ctx.assign_advice(|| annotation("add", "b1"), config.b, Value::known(F::ZERO))?;
+        ctx.next();
+
+        Ok(res)
     }
 
     // Multiply two cells and return the product
     fn mul(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        a: &F,
-        b: &F,
-        offset: &mut usize,
-    ) -> Result<AssignedCell<F, F>, Error> {
+        a: Value<F>,
+        b: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        config.require_gate(config.gates.mul, "mul")?;
+
         // |         mul |   2  |       0      | 0  | 0  | 1  | a1 = a0 * b0
-        config.q3.enable(region, *offset)?;
-        region.assign_advice(|| "field element", config.a, *offset, || Value::known(*a))?;
-        region.assign_advice(|| "field element", config.b, *offset, || Value::known(*b))?;
-
-        let c = *a * *b;
-        let res = region.assign_advice(
-            || "field element",
-            config.a,
-            *offset + 1,
-            || Value::known(c),
-        );
-        let _ = region.assign_advice(
-            || "field element",
-            config.b,
-            *offset + 1,
-            || Value::known(F::ZERO),
-        );
+        ctx.enable_selector(&config.q3, ctx.offset())?;
+        ctx.assign_advice(|| annotation("mul", "a0"), config.a, a)?;
+        ctx.assign_advice(|| annotation("mul", "b0"), config.b, b)?;
+        ctx.next();
+
+        let c = a * b;
+        let res = ctx.assign_advice(|| annotation("mul", "a1"), config.a, c)?;
+        ctx.assign_advice(|| annotation("mul", "b1"), config.b, Value::known(F::ZERO))?;
+        ctx.next();
+
+        Ok(res)
+    }
 
-        *offset += 2;
-        res
+    fn vec_add(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        check_same_length(a, b)?;
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| self.add_cells(ctx, config, a, b))
+            .collect()
+    }
+
+    fn vec_mul(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        check_same_length(a, b)?;
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| self.mul_cells(ctx, config, a, b))
+            .collect()
+    }
+
+    fn vec_scale(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        k: &AssignedCell<F, F>,
+        v: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        v.iter()
+            .map(|v| self.mul_cells(ctx, config, v, k))
+            .collect()
+    }
+
+    fn safe_div(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), NativeEccError> {
+        let inv = b.value().map(|b| b.invert().unwrap_or(F::ZERO));
+        self.assign_safe_div(ctx, config, a, b, inv)
     }
 
     /// Input x1, y1, x2, y2, x3, y3
@@ -195,45 +270,37 @@ where
     /// - x1, y1, x2, y2 are all binary
     fn partial_bit_decomp(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        inputs: &[F],
-        offset: &mut usize,
-    ) -> Result<Vec<AssignedCell<F, F>>, Error> {
-        assert_eq!(inputs.len(), 6, "input length is not 6");
+        inputs: &[Value<F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        if inputs.len() != 6 {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: 6,
+                actual: inputs.len(),
+            });
+        }
+        config.require_gate(config.gates.partial_bit_decomp, "partial bit decomp")?;
 
         let mut res = vec![];
         // |     partial |   3  |       0      | 1  | 0  | 0  | y3 = x1 + y1 + x2 + y2 + x3 and
         // |   decompose |      |              |    |    |    | x1, y1, x2, y2 are all binary
-        config.q1.enable(region, *offset)?;
-        res.push(region.assign_advice(|| "x0", config.a, *offset, || Value::known(inputs[0]))?);
-        res.push(region.assign_advice(|| "y0", config.b, *offset, || Value::known(inputs[1]))?);
-        res.push(region.assign_advice(
-            || "x1",
-            config.a,
-            *offset + 1,
-            || Value::known(inputs[2]),
-        )?);
-        res.push(region.assign_advice(
-            || "y1",
-            config.b,
-            *offset + 1,
-            || Value::known(inputs[3]),
-        )?);
-        res.push(region.assign_advice(
-            || "x2",
-            config.a,
-            *offset + 2,
-            || Value::known(inputs[4]),
-        )?);
-        res.push(region.assign_advice(
-            || "y2",
-            config.b,
-            *offset + 2,
-            || Value::known(inputs[5]),
-        )?);
+        ctx.enable_selector(&config.q1, ctx.offset())?;
+        for (i, pair) in inputs.chunks(2).enumerate() {
+            let (x, y) = [("x1", "y1"), ("x2", "y2"), ("x3", "y3")][i];
+            res.push(ctx.assign_advice(
+                || annotation("partial bit decomp", x),
+                config.a,
+                pair[0],
+            )?);
+            res.push(ctx.assign_advice(
+                || annotation("partial bit decomp", y),
+                config.b,
+                pair[1],
+            )?);
+            ctx.next();
+        }
 
-        *offset += 3;
         Ok(res)
     }
 
@@ -243,25 +310,25 @@ where
     /// - the cell that contains u128
     fn decompose_u128(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        input: &u128,
-        offset: &mut usize,
-    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), Error> {
-        let input_le_vec = crate::util::decompose_u128(input);
-        let input_field_vec = input_le_vec
-            .iter()
+        input: Value<u128>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), NativeEccError> {
+        config.require_gate(config.gates.partial_bit_decomp, "partial bit decomp")?;
+
+        let input_le_vec = input.map(|input| crate::util::decompose_u128(&input));
+        let input_field_vec = (0..128)
             .rev()
-            .map(|&x| F::from(x))
+            .map(|i| input_le_vec.as_ref().map(|bits| F::from(bits[i])))
             .collect::<Vec<_>>();
 
-        let two = F::from(2);
-        let four = F::from(4);
-        let eight = F::from(8);
-        let sixteen = F::from(16);
+        let two = Value::known(F::from(2));
+        let four = Value::known(F::from(4));
+        let eight = Value::known(F::from(8));
+        let sixteen = Value::known(F::from(16));
 
         let mut acc;
-        let mut prev_acc = F::ZERO;
+        let mut prev_acc = Value::known(F::ZERO);
 
         let mut res = vec![];
         let mut acc_cells = vec![];
@@ -271,33 +338,32 @@ where
             // |     partial |   3  |       0      | 1  | 0  | 0  | y3 = x1 + y1 + x2 + y2 + x3 and
             // |   decompose |      |              |    |    |    | x1, y1, x2, y2 are all binary
 
-            config.q1.enable(region, *offset)?;
+            ctx.enable_selector(&config.q1, ctx.offset())?;
 
             // allocate the four bits to be absorbed
-            res.push(region.assign_advice(
-                || "b2",
-                config.b,
-                *offset + 1,
-                || Value::known(input_field_vec[4 * i]),
-            )?);
-            res.push(region.assign_advice(
-                || "a2",
+            let a1 = ctx.assign_advice(
+                || annotation("decompose u128", "a1"),
                 config.a,
-                *offset + 1,
-                || Value::known(input_field_vec[4 * i + 1]),
-            )?);
-            res.push(region.assign_advice(
-                || "b1",
+                input_field_vec[4 * i + 3],
+            )?;
+            let b1 = ctx.assign_advice(
+                || annotation("decompose u128", "b1"),
                 config.b,
-                *offset,
-                || Value::known(input_field_vec[4 * i + 2]),
-            )?);
-            res.push(region.assign_advice(
-                || "a1",
+                input_field_vec[4 * i + 2],
+            )?;
+            ctx.next();
+            let a2 = ctx.assign_advice(
+                || annotation("decompose u128", "a2"),
                 config.a,
-                *offset,
-                || Value::known(input_field_vec[4 * i + 3]),
-            )?);
+                input_field_vec[4 * i + 1],
+            )?;
+            let b2 = ctx.assign_advice(
+                || annotation("decompose u128", "b2"),
+                config.b,
+                input_field_vec[4 * i],
+            )?;
+            ctx.next();
+            res.extend([b2, a2, b1, a1]);
 
             // compute the accumulated value
             acc = input_field_vec[4 * i + 3]
@@ -307,28 +373,30 @@ where
                 + prev_acc * sixteen;
 
             // assign accumulator
-            acc_cells.push(region.assign_advice(
-                || "a3",
+            acc_cells.push(ctx.assign_advice(
+                || annotation("decompose u128", "prev acc"),
                 config.a,
-                *offset + 2,
-                || Value::known(prev_acc),
+                prev_acc,
             )?);
-            acc_cells.push(region.assign_advice(
-                || "b3",
+            acc_cells.push(ctx.assign_advice(
+                || annotation("decompose u128", "acc"),
                 config.b,
-                *offset + 2,
-                || Value::known(acc),
+                acc,
             )?);
+            ctx.next();
             prev_acc = acc;
-            *offset += 3;
         }
 
         // sanity check
-        assert_eq!(prev_acc, F::from_u128(*input));
+        prev_acc
+            .zip(input)
+            .error_if_known_and(|(acc, input)| *acc != F::from_u128(*input))
+            .map_err(|_| NativeEccError::DecompositionMismatch)?;
 
-        // constrain the accumulators are well-formed
+        // constrain the accumulators are well-formed, starting from 0
+        ctx.constrain_constant(acc_cells[0].cell(), F::ZERO)?;
         for i in 0..31 {
-            region.constrain_equal(
+            ctx.constrain_equal(
                 // acc in the previous round
                 acc_cells[i * 2 + 1].cell(),
                 // prev_acc in the current round
@@ -339,6 +407,132 @@ where
         // format the result in little endian format
         res.reverse();
 
-        Ok((res, acc_cells.last().unwrap().clone()))
+        let acc = acc_cells[acc_cells.len() - 1].clone();
+        Ok((res, acc))
+    }
+}
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    /// `add` of two assigned cells, copied into the operands of the gate.
+    pub(crate) fn add_cells(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        config.require_gate(config.gates.add, "add")?;
+
+        // |         add |   2  |       0      | 0  | 1  | 0  | a1 = a0 + b0
+        ctx.enable_selector(&config.q2, ctx.offset())?;
+        self.copy_operands(ctx, config, "add", a, b)?;
+
+        let c = a.value().copied() + b.value().copied();
+        self.assign_result(ctx, config, "add", c)
+    }
+
+    /// `mul` of two assigned cells, copied into the operands of the gate.
+    pub(crate) fn mul_cells(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        config.require_gate(config.gates.mul, "mul")?;
+
+        // |         mul |   2  |       0      | 0  | 0  | 1  | a1 = a0 * b0
+        ctx.enable_selector(&config.q3, ctx.offset())?;
+        self.copy_operands(ctx, config, "mul", a, b)?;
+
+        let c = a.value().copied() * b.value().copied();
+        self.assign_result(ctx, config, "mul", c)
+    }
+
+    /// `safe_div` for a witnessed inverse of b.
+    ///
+    /// With t = b * inv, the flag is z = 1 - t, and
+    /// - b * z = 0, i.e., z = 0 and inv = 1 / b if b != 0, and z = 1 otherwise
+    /// - inv * z = 0, i.e., inv = 0 if b = 0, so that q = a * inv is 0
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn assign_safe_div(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+        inv: Value<F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), NativeEccError> {
+        config.require_gate(config.gates.add, "add")?;
+        config.require_gate(config.gates.mul, "mul")?;
+
+        let z = b.value().zip(inv).map(|(b, inv)| F::ONE - *b * inv);
+        let [inv, z] = self.load_two_private_fields(ctx, config, inv, z)?;
+
+        let t = self.mul_cells(ctx, config, b, &inv)?;
+        let one = self.add_cells(ctx, config, &t, &z)?;
+        ctx.constrain_constant(one.cell(), F::ONE)?;
+
+        let bz = self.mul_cells(ctx, config, b, &z)?;
+        ctx.constrain_constant(bz.cell(), F::ZERO)?;
+        let inv_z = self.mul_cells(ctx, config, &inv, &z)?;
+        ctx.constrain_constant(inv_z.cell(), F::ZERO)?;
+
+        let q = self.mul_cells(ctx, config, a, &inv)?;
+        Ok((q, z))
+    }
+
+    /// Loads a constant field element.
+    pub(crate) fn load_constant_field(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        f: F,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        let res = self.load_private_field(ctx, config, Value::known(f))?;
+        ctx.constrain_constant(res.cell(), f)?;
+        Ok(res)
+    }
+
+    fn copy_operands(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        op: &'static str,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<(), NativeEccError> {
+        ctx.copy_advice(|| annotation(op, "a0"), config.a, a)?;
+        ctx.copy_advice(|| annotation(op, "b0"), config.b, b)?;
+        ctx.next();
+        Ok(())
+    }
+
+    fn assign_result(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        op: &'static str,
+        c: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        let res = ctx.assign_advice(|| annotation(op, "a1"), config.a, c)?;
+        ctx.assign_advice(|| annotation(op, "b1"), config.b, Value::known(F::ZERO))?;
+        ctx.next();
+        Ok(res)
+    }
+}
+
+/// Errors unless the operands of an elementwise op have the same length.
+fn check_same_length<T>(a: &[T], b: &[T]) -> Result<(), NativeEccError> {
+    if a.len() != b.len() {
+        return Err(NativeEccError::InvalidInputLength {
+            expected: a.len(),
+            actual: b.len(),
+        });
     }
+    Ok(())
 }
@@ -1,7 +1,9 @@
 use ark_std::test_rng;
 use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Chip;
 use halo2_proofs::circuit::Layouter;
 use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
 use halo2_proofs::dev::MockProver;
 use halo2_proofs::plonk::Circuit;
 use halo2_proofs::plonk::ConstraintSystem;
@@ -13,14 +15,29 @@ use crate::arith_gates::ArithOps;
 use crate::chip::ECChip;
 use crate::config::ECConfig;
 use crate::ec_gates::NativeECOps;
+use crate::region_ctx::RegionCtx;
+use crate::testing::run_gadget;
+use crate::NativeEccError;
 
 #[derive(Default, Debug, Clone, Copy)]
 struct ArithTestCircuit {
-    f1: Fq,
-    f2: Fq,
-    f3: Fq,      // f3 = f1 + f2
-    f4: Fq,      // f4 = f1 * f2
-    f5: [Fq; 6], // partial bit decom
+    f1: Value<Fq>,
+    f2: Value<Fq>,
+    f3: Value<Fq>,      // f3 = f1 + f2
+    f4: Value<Fq>,      // f4 = f1 * f2
+    f5: [Value<Fq>; 6], // partial bit decom
+}
+
+impl ArithTestCircuit {
+    fn new(f1: Fq, f2: Fq, f3: Fq, f4: Fq, f5: [Fq; 6]) -> Self {
+        Self {
+            f1: Value::known(f1),
+            f2: Value::known(f2),
+            f3: Value::known(f3),
+            f4: Value::known(f4),
+            f5: f5.map(Value::known),
+        }
+    }
 }
 
 impl Circuit<Fq> for ArithTestCircuit {
@@ -44,55 +61,38 @@ impl Circuit<Fq> for ArithTestCircuit {
 
         layouter.assign_region(
             || "test field circuit",
-            |mut region| {
-                let mut offset = 0;
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
 
                 // unit test: addition
                 {
-                    let f3_rec =
-                        field_chip.add(&mut region, &config, &self.f1, &self.f2, &mut offset)?;
-                    let f3 = field_chip.load_private_field(
-                        &mut region,
-                        &config,
-                        &self.f3,
-                        &mut offset,
-                    )?;
-                    region.constrain_equal(f3.cell(), f3_rec.cell())?;
+                    let f3_rec = field_chip.add(&mut ctx, &config, self.f1, self.f2)?;
+                    let f3 = field_chip.load_private_field(&mut ctx, &config, self.f3)?;
+                    ctx.constrain_equal(f3.cell(), f3_rec.cell())?;
                 }
 
                 // unit test: multiplication
                 {
-                    let f4_rec =
-                        field_chip.mul(&mut region, &config, &self.f1, &self.f2, &mut offset)?;
-                    let f4 = field_chip.load_private_field(
-                        &mut region,
-                        &config,
-                        &self.f4,
-                        &mut offset,
-                    )?;
-                    region.constrain_equal(f4.cell(), f4_rec.cell())?;
+                    let f4_rec = field_chip.mul(&mut ctx, &config, self.f1, self.f2)?;
+                    let f4 = field_chip.load_private_field(&mut ctx, &config, self.f4)?;
+                    ctx.constrain_equal(f4.cell(), f4_rec.cell())?;
                 }
 
                 // unit test: partial bit decompose
                 {
-                    let _cells = field_chip.partial_bit_decomp(
-                        &mut region,
-                        &config,
-                        self.f5.as_ref(),
-                        &mut offset,
-                    )?;
+                    let _cells =
+                        field_chip.partial_bit_decomp(&mut ctx, &config, self.f5.as_ref())?;
                 }
 
                 // unit test: decompose u128
                 {
                     let bytes = (0..16).map(|x| x).collect::<Vec<u8>>();
                     let a = u128::from_le_bytes(bytes.try_into().unwrap());
-                    let _cells =
-                        field_chip.decompose_u128(&mut region, &config, &a, &mut offset)?;
+                    let _cells = field_chip.decompose_u128(&mut ctx, &config, Value::known(a))?;
                 }
 
-                // pad the last two rows
-                field_chip.pad(&mut region, &config, &mut offset)?;
+                // pad the rows queried by the last gates
+                field_chip.pad(&mut ctx, &config)?;
 
                 Ok(())
             },
@@ -121,7 +121,7 @@ fn test_field_ops() {
             f1,
             f1 * Fq::from(16) + Fq::from(9),
         ];
-        let circuit = ArithTestCircuit { f1, f2, f3, f4, f5 };
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         prover.assert_satisfied();
@@ -138,7 +138,7 @@ fn test_field_ops() {
             f1,
             f1 * Fq::from(16) + Fq::from(9),
         ];
-        let circuit = ArithTestCircuit { f1, f2, f3, f4, f5 };
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         assert!(prover.verify().is_err());
@@ -154,7 +154,7 @@ fn test_field_ops() {
             f1,
             f1 * Fq::from(16) + Fq::from(9),
         ];
-        let circuit = ArithTestCircuit { f1, f2, f3, f4, f5 };
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         assert!(prover.verify().is_err());
@@ -169,7 +169,37 @@ fn test_field_ops() {
             f1,
             f1 * Fq::from(16) + Fq::from(10),
         ];
-        let circuit = ArithTestCircuit { f1, f2, f3, f4, f5 };
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+    // error case: a non binary x1 cancelled by the recomposition
+    {
+        let f5 = [
+            Fq::from(2),
+            Fq::zero(),
+            Fq::zero(),
+            Fq::one(),
+            f1,
+            f1 * Fq::from(16) + Fq::from(8),
+        ];
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+    // error case: non binary x1 and y1 cancelled by the recomposition
+    {
+        let f5 = [
+            Fq::from(2),
+            -Fq::one(),
+            Fq::zero(),
+            Fq::one(),
+            f1,
+            f1 * Fq::from(16) + Fq::from(4),
+        ];
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         assert!(prover.verify().is_err());
@@ -184,9 +214,121 @@ fn test_field_ops() {
             f1,
             f1 * Fq::from(16) + Fq::from(10),
         ];
-        let circuit = ArithTestCircuit { f1, f2, f3, f4, f5 };
+        let circuit = ArithTestCircuit::new(f1, f2, f3, f4, f5);
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         assert!(prover.verify().is_err());
     }
 }
+
+#[test]
+fn test_vec_ops() {
+    let mut rng = test_rng();
+    let a: Vec<Fq> = (0..4).map(|_| Fq::random(&mut rng)).collect();
+    let b: Vec<Fq> = (0..4).map(|_| Fq::random(&mut rng)).collect();
+    let k = Fq::random(&mut rng);
+
+    let run = |tamper: Fq| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let load = |ctx: &mut RegionCtx<Fq>, v: &[Fq]| {
+                v.iter()
+                    .map(|f| chip.load_private_field(ctx, config, Value::known(*f)))
+                    .collect::<Result<Vec<_>, _>>()
+            };
+            let a_cells = load(ctx, &a)?;
+            let b_cells = load(ctx, &b)?;
+            let k_cell = chip.load_private_field(ctx, config, Value::known(k))?;
+
+            let start = ctx.offset();
+            let sum = chip.vec_add(ctx, config, &a_cells, &b_cells)?;
+            let product = chip.vec_mul(ctx, config, &a_cells, &b_cells)?;
+            let scaled = chip.vec_scale(ctx, config, &k_cell, &a_cells)?;
+            assert_eq!(
+                ctx.offset() - start,
+                3 * crate::cost::rows_for_vec_op(a.len())
+            );
+
+            for i in 0..a.len() {
+                ctx.constrain_constant(sum[i].cell(), a[i] + b[i] + tamper)?;
+                ctx.constrain_constant(product[i].cell(), a[i] * b[i])?;
+                ctx.constrain_constant(scaled[i].cell(), k * a[i])?;
+            }
+
+            // error case: vectors of different lengths
+            assert!(matches!(
+                chip.vec_add(ctx, config, &a_cells, &b_cells[1..]),
+                Err(NativeEccError::InvalidInputLength {
+                    expected: 4,
+                    actual: 3
+                })
+            ));
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(Fq::ZERO).assert_satisfied();
+
+    // error case: wrong sum
+    assert!(run(Fq::ONE).verify().is_err());
+}
+
+#[test]
+fn test_safe_div() {
+    let mut rng = test_rng();
+    let a = Fq::random(&mut rng);
+    let b = Fq::random(&mut rng);
+
+    // the quotient and the flag, for a witnessed inverse of b
+    let run = |a: Fq, b: Fq, inv: Fq, expected: (Fq, Fq)| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let a = chip.load_private_field(ctx, config, Value::known(a))?;
+            let b = chip.load_private_field(ctx, config, Value::known(b))?;
+            let start = ctx.offset();
+            let (q, z) = chip.assign_safe_div(ctx, config, &a, &b, Value::known(inv))?;
+            assert_eq!(ctx.offset() - start, crate::cost::SAFE_DIV_ROWS);
+            ctx.constrain_constant(q.cell(), expected.0)?;
+            ctx.constrain_constant(z.cell(), expected.1)?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    let inv = b.invert().unwrap();
+    run(a, b, inv, (a * inv, Fq::ZERO)).assert_satisfied();
+    run(a, Fq::ZERO, Fq::ZERO, (Fq::ZERO, Fq::ONE)).assert_satisfied();
+    run(Fq::ZERO, Fq::ZERO, Fq::ZERO, (Fq::ZERO, Fq::ONE)).assert_satisfied();
+
+    // error case: b != 0 flagged as zero
+    assert!(run(a, b, Fq::ZERO, (Fq::ZERO, Fq::ONE)).verify().is_err());
+    // error case: any quotient for b = 0
+    assert!(run(a, Fq::ZERO, inv, (a * inv, Fq::ONE)).verify().is_err());
+    // error case: a wrong inverse
+    assert!(run(
+        a,
+        b,
+        inv + Fq::ONE,
+        (a * (inv + Fq::ONE), Fq::ONE - b * (inv + Fq::ONE))
+    )
+    .verify()
+    .is_err());
+
+    // the op witnesses the inverse
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        let a_cell = chip.load_private_field(ctx, config, Value::known(a))?;
+        let b_cell = chip.load_private_field(ctx, config, Value::known(b))?;
+        let zero = chip.load_private_field(ctx, config, Value::known(Fq::ZERO))?;
+        let (q, z) = chip.safe_div(ctx, config, &a_cell, &b_cell)?;
+        ctx.constrain_constant(q.cell(), a * inv)?;
+        ctx.constrain_constant(z.cell(), Fq::ZERO)?;
+        let (q, z) = chip.safe_div(ctx, config, &a_cell, &zero)?;
+        ctx.constrain_constant(q.cell(), Fq::ZERO)?;
+        ctx.constrain_constant(z.cell(), Fq::ONE)?;
+        Ok(())
+    })
+    .unwrap();
+    prover.assert_satisfied();
+}
@@ -0,0 +1,201 @@
+//! Out-of-circuit checks of the preconditions of the incomplete formulas,
+//! with the `audit` feature.
+//!
+//! Each ec op checks its witness while it is assigned, and records the
+//! exceptional cases it hits. A circuit whose report is empty for its test
+//! vectors does not rely on the cases the gates do not cover.
+//!
+//! ```ignore
+//! let chip = ECChip::construct(config);
+//! // ... synthesize with the chip, or a clone of it ...
+//! assert!(chip.exceptional_cases().is_empty());
+//! ```
+//!
+//! The selectors enabled by the ops are recorded as well, by op and by row,
+//! for the regions assigned from `ECChip::region_ctx`, so that the rows
+//! whose combination of selectors is unintended stand out:
+//!
+//! ```ignore
+//! for (op, rows) in chip.gate_activations() {
+//!     for (row, selectors) in rows {
+//!         println!("{op} @ {row}: {selectors:?}");
+//!     }
+//! }
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::collections::BTreeSet;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::Selector;
+
+use crate::backend::Field;
+
+/// A precondition of the incomplete formulas.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Precondition {
+    /// The operands of an add are not p and ±p.
+    DistinctX,
+    /// The operands are on curve.
+    OnCurve,
+    /// The point doubled does not have y = 0.
+    NonZeroY,
+}
+
+/// An op whose witness violates a precondition of its formula.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ExceptionalCase {
+    /// The op, e.g., "conditional add".
+    pub op: &'static str,
+    /// The first row of the op, within its region.
+    pub offset: usize,
+    pub violated: Precondition,
+}
+
+/// The exceptional cases recorded during synthesis, shared by the clones of
+/// a chip.
+///
+/// The floor planner may run a region twice; the cases are deduplicated.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AuditLog(Rc<RefCell<BTreeSet<ExceptionalCase>>>);
+
+impl AuditLog {
+    /// The recorded cases, ordered by op and row.
+    pub(crate) fn report(&self) -> Vec<ExceptionalCase> {
+        self.0.borrow().iter().copied().collect()
+    }
+
+    fn record(&self, op: &'static str, offset: usize, violated: Precondition) {
+        self.0.borrow_mut().insert(ExceptionalCase {
+            op,
+            offset,
+            violated,
+        });
+    }
+
+    /// Checks an add of p2 to p1 for the given condition.
+    pub(crate) fn check_add<F: Field>(
+        &self,
+        op: &'static str,
+        offset: usize,
+        curve_b: F,
+        p1: Value<(F, F)>,
+        p2: Value<(F, F)>,
+        condition: Value<F>,
+    ) {
+        self.check_on_curve(op, offset, curve_b, p1);
+        self.check_on_curve(op, offset, curve_b, p2);
+        p1.zip(p2)
+            .zip(condition)
+            .map(|(((x1, _), (x2, _)), condition)| {
+                if condition != F::ZERO && x1 == x2 {
+                    self.record(op, offset, Precondition::DistinctX);
+                }
+            });
+    }
+
+    /// Checks a double of p.
+    pub(crate) fn check_double<F: Field>(
+        &self,
+        op: &'static str,
+        offset: usize,
+        curve_b: F,
+        p: Value<(F, F)>,
+    ) {
+        self.check_on_curve(op, offset, curve_b, p);
+        p.map(|(_, y)| {
+            if y == F::ZERO {
+                self.record(op, offset, Precondition::NonZeroY);
+            }
+        });
+    }
+
+    fn check_on_curve<F: Field>(
+        &self,
+        op: &'static str,
+        offset: usize,
+        curve_b: F,
+        p: Value<(F, F)>,
+    ) {
+        p.map(|(x, y)| {
+            if y.square() != x.square() * x + curve_b {
+                self.record(op, offset, Precondition::OnCurve);
+            }
+        });
+    }
+}
+
+/// The selectors enabled during synthesis: for each op, the rows of its
+/// regions it enabled selectors on, and their names, e.g., "q_ec_enable".
+///
+/// The rows of an op in different regions are merged, as the cases of
+/// `ExceptionalCase`; the selectors enabled outside of any op are recorded
+/// under "untraced", and the selectors of other chips as "external".
+pub type GateReport = BTreeMap<&'static str, BTreeMap<usize, BTreeSet<&'static str>>>;
+
+/// The op of the selectors enabled outside of any op.
+pub const UNTRACED: &str = "untraced";
+/// The name of the selectors of other chips.
+pub const EXTERNAL: &str = "external";
+
+/// The selectors enabled during synthesis, shared by the clones of a chip
+/// and the regions assigned from it.
+#[derive(Clone, Debug)]
+pub(crate) struct GateLog<F: Field> {
+    // the names of the selectors, and of the values of the fixed columns
+    // used as selectors, by column index
+    selectors: Rc<Vec<(Selector, &'static str)>>,
+    fixed: Rc<Vec<(usize, F, &'static str)>>,
+    report: Rc<RefCell<GateReport>>,
+}
+
+impl<F: Field> GateLog<F> {
+    pub(crate) fn new(
+        selectors: Vec<(Selector, &'static str)>,
+        fixed: Vec<(usize, F, &'static str)>,
+    ) -> Self {
+        Self {
+            selectors: Rc::new(selectors),
+            fixed: Rc::new(fixed),
+            report: Rc::default(),
+        }
+    }
+
+    /// The recorded activations.
+    pub(crate) fn report(&self) -> GateReport {
+        self.report.borrow().clone()
+    }
+
+    /// Records a selector enabled at the given row by the op.
+    pub(crate) fn record_selector(&self, op: &'static str, offset: usize, selector: &Selector) {
+        let name = self
+            .selectors
+            .iter()
+            .find(|(s, _)| s == selector)
+            .map_or(EXTERNAL, |(_, name)| *name);
+        self.record(op, offset, name);
+    }
+
+    /// Records a fixed column set to a selector value at the given row by the op.
+    pub(crate) fn record_fixed(&self, op: &'static str, offset: usize, column: usize, value: F) {
+        let name = self
+            .fixed
+            .iter()
+            .find(|(c, v, _)| *c == column && *v == value)
+            .map_or(EXTERNAL, |(_, _, name)| *name);
+        self.record(op, offset, name);
+    }
+
+    fn record(&self, op: &'static str, offset: usize, name: &'static str) {
+        self.report
+            .borrow_mut()
+            .entry(op)
+            .or_default()
+            .entry(offset)
+            .or_default()
+            .insert(name);
+    }
+}
@@ -0,0 +1,39 @@
+//! The halo2 flavour the chip is built against.
+//!
+//! The chip only relies on the API shared by the PSE fork (`pse` feature, the
+//! default) and the stable zcash `halo2_proofs` (`zcash` feature). The traits
+//! whose paths differ between the two are re-exported from here, along with the
+//! synthesis error of the backend.
+
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::arithmetic::Field;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::ff::PrimeField;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::group::prime::PrimeCurveAffine;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::group::Curve;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::group::Group;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::CurveAffine;
+
+#[cfg(feature = "zcash")]
+pub(crate) use ff::Field;
+#[cfg(feature = "zcash")]
+pub(crate) use ff::PrimeField;
+#[cfg(feature = "zcash")]
+pub(crate) use group::prime::PrimeCurveAffine;
+#[cfg(feature = "zcash")]
+pub(crate) use group::Curve;
+#[cfg(feature = "zcash")]
+pub(crate) use group::Group;
+#[cfg(feature = "zcash")]
+pub(crate) use halo2_proofs::arithmetic::CurveAffine;
+
+/// The error returned by the `Circuit` and `Layouter` APIs of the backend.
+///
+/// Both backends currently report synthesis failures as `plonk::Error`; a
+/// backend with a split frontend error only needs to change this alias and the
+/// `From` impls of `NativeEccError`.
+pub type Halo2Error = halo2_proofs::plonk::Error;
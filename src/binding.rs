@@ -0,0 +1,96 @@
+//! Binding of a signed message, for Schnorr-like signatures over the curve.
+//!
+//! The challenge e = H(R, pk, m) of a signature (R, s) of a message m under
+//! the key pk is computed by a hash chip of the circuit, through
+//! `HashToBits`, and its digest is reduced in-circuit modulo the order of the
+//! scalar field:
+//!
+//! ```ignore
+//! let (e, _) = ecc.bind_message(&mut layouter, &poseidon, &msg, &r, &pk)?;
+//! // s * G = R + e * pk
+//! let e_pk = ecc.point_mul_from_bits(..., &e)?;
+//! ```
+//!
+//! The coordinates of R and pk, then the message cells, are hashed in this
+//! order, see `ExportCells`.
+
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::PrimeField;
+use crate::AssignedECPoint;
+use crate::ECChip;
+use crate::ExportCells;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// A hash chip of the circuit, whose digest is handed over as bits.
+pub trait HashToBits<F: Field> {
+    /// Hashes the cells, in order, and returns the little endian bits of the
+    /// digest, at most 256.
+    ///
+    /// The bits need not be constrained binary; they are checked when
+    /// copied into the rows of the ec chip.
+    fn hash_to_bits(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        inputs: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>;
+}
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// The challenge e = H(R, pk, m) of a signature, as the canonical bits
+    /// and the [low, high] limbs of the digest modulo the order of the
+    /// scalar field.
+    ///
+    /// The bits of the digest are imported with `import_scalar_bits`, then
+    /// reduced in `REDUCE_SCALAR_ROWS` rows of the `cost` module. Requires
+    /// the add and mul gates.
+    #[allow(clippy::type_complexity)]
+    pub fn bind_message(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        hasher: &impl HashToBits<F>,
+        msg: &[AssignedCell<F, F>],
+        r: &AssignedECPoint<C, F>,
+        pk: &AssignedECPoint<C, F>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError> {
+        let inputs = [r.export_cells(), pk.export_cells(), msg.to_vec()].concat();
+        let digest = hasher.hash_to_bits(layouter, &inputs)?;
+
+        let config = self.config();
+        let e = layouter.assign_region(
+            || "bind message",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let (_, limbs) = self.import_scalar_bits(&mut ctx, config, &digest)?;
+                // a digest of at most 128 bits has a single limb
+                let high = match limbs.get(1) {
+                    Some(high) => high.clone(),
+                    None => self.load_constant_field(&mut ctx, config, F::ZERO)?,
+                };
+                let e = self.reduce_scalar_limbs::<C::ScalarExt>(
+                    &mut ctx,
+                    config,
+                    &[limbs[0].clone(), high],
+                )?;
+                self.pad(&mut ctx, config)?;
+                Ok(e)
+            },
+        )?;
+        Ok(e)
+    }
+}
@@ -0,0 +1,159 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::binding::HashToBits;
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+use crate::region_ctx::RegionCtx;
+use crate::util::to_le_bits;
+use crate::ArithOps;
+use crate::NativeEccError;
+
+/// A toy hash: the bits of the sum of the inputs, with the top 2 bits set
+/// so that the digest exceeds the order of the scalar field. The bits are
+/// witnessed, not bound to the inputs.
+struct ToyHash(ECChip<G1Affine, Fq>);
+
+fn toy_digest(inputs: &[Fq]) -> Vec<bool> {
+    let sum = inputs.iter().fold(Fq::ZERO, |acc, x| acc + x);
+    let mut bits = to_le_bits(&sum);
+    bits[254] = true;
+    bits[255] = true;
+    bits
+}
+
+impl HashToBits<Fq> for ToyHash {
+    fn hash_to_bits(
+        &self,
+        layouter: &mut impl Layouter<Fq>,
+        inputs: &[AssignedCell<Fq, Fq>],
+    ) -> Result<Vec<AssignedCell<Fq, Fq>>, NativeEccError> {
+        let digest: Value<Vec<Fq>> = inputs.iter().map(|x| x.value().copied()).collect();
+        let digest = digest.map(|inputs| toy_digest(&inputs));
+        let bits = layouter.assign_region(
+            || "toy hash",
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
+                (0..256)
+                    .map(|i| {
+                        let bit = digest.as_ref().map(|bits| Fq::from(bits[i] as u64));
+                        self.0.load_private_field(&mut ctx, self.0.config(), bit)
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(Error::from)
+            },
+        )?;
+        Ok(bits)
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+struct BindTestCircuit {
+    r: Value<G1Affine>,
+    pk: Value<G1Affine>,
+    msg: Vec<Value<Fq>>,
+    // the expected challenge
+    e: Fr,
+}
+
+impl Circuit<Fq> for BindTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            msg: vec![Value::unknown(); self.msg.len()],
+            e: self.e,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+        let r = ec_chip.witness_point(&mut layouter, self.r)?;
+        let pk = ec_chip.witness_point(&mut layouter, self.pk)?;
+        let msg = ec_chip.assign_scoped(
+            &mut layouter,
+            || "msg",
+            |ecc| {
+                self.msg
+                    .iter()
+                    .map(|m| ecc.load_private_field(*m))
+                    .collect::<Result<Vec<_>, _>>()
+            },
+        )?;
+
+        let hasher = ToyHash(ec_chip.clone());
+        let (bits, _) = ec_chip.bind_message(&mut layouter, &hasher, &msg, &r, &pk)?;
+        assert_eq!(bits.len(), 256);
+
+        let expected = to_le_bits(&self.e);
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "expected challenge",
+            |ecc| {
+                for (bit, expected) in bits.iter().zip(expected.iter()) {
+                    ecc.ctx()
+                        .constrain_constant(bit.cell(), Fq::from(*expected as u64))?;
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_bind_message() {
+    let k = 12;
+    let mut rng = test_rng();
+    let r = G1::random(&mut rng).to_affine();
+    let pk = G1::random(&mut rng).to_affine();
+    let msg = vec![Fq::random(&mut rng), Fq::random(&mut rng)];
+
+    // e = H(R, pk, m) mod the order of the scalar field
+    let digest = toy_digest(&[r.x, r.y, pk.x, pk.y, msg[0], msg[1]]);
+    let e = digest
+        .iter()
+        .rev()
+        .fold(Fr::ZERO, |acc, b| acc.double() + Fr::from(*b as u64));
+
+    let run = |e: Fr| {
+        let circuit = BindTestCircuit {
+            r: Value::known(r),
+            pk: Value::known(pk),
+            msg: msg.iter().copied().map(Value::known).collect(),
+            e,
+        };
+        MockProver::run(k, &circuit, vec![]).unwrap()
+    };
+
+    run(e).assert_satisfied();
+
+    // error case: the challenge of another message
+    assert!(run(e + Fr::ONE).verify().is_err());
+}
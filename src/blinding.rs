@@ -0,0 +1,72 @@
+//! Blinded public keys, for credential systems.
+//!
+//! A credential is issued to a key P committed in the instance, and shown
+//! under the blinded key P' = P + r * H, for a second generator H and a
+//! blinding r that stays private. `prove_blinded_key` binds P to the
+//! instance and re-randomizes it in one call, so that a circuit cannot
+//! expose a blinded key derived from another key:
+//!
+//! ```ignore
+//! let blinded = ecc.prove_blinded_key(&mut layouter, h, pk, r, 0)?;
+//! ecc.expose_point_public(&mut layouter, &blinded, 2)?;
+//! ```
+
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Value;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::AssignedECPoint;
+use crate::ECChip;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// The blinded key p + r * h of the key p exposed at the rows `row` and
+    /// `row + 1` of the instance column, see `point_instances`.
+    ///
+    /// p is checked on curve and h is constrained to constants. The blinding
+    /// is decomposed without a canonical check: h has the order of the scalar
+    /// field, so r and r + q blind alike. Uses `BLINDED_KEY_ROWS` rows of the
+    /// `cost` module.
+    pub fn prove_blinded_key(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        h: C,
+        p: Value<C>,
+        r: Value<C::ScalarExt>,
+        row: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let config = self.config();
+        let (p, blinded) = layouter.assign_region(
+            || "blinded key",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let h_assigned = self.load_private_point(&mut ctx, config, Value::known(h))?;
+                self.assert_equal_const(&mut ctx, config, &h_assigned, &h)?;
+                let p = self.load_private_point(&mut ctx, config, p)?;
+
+                let bits = self.decompose_scalar(&mut ctx, config, r)?;
+                let blinding = self.point_mul_with_bits(&mut ctx, config, &h_assigned, &bits)?;
+
+                let key = self.copy_point(&mut ctx, config, &p)?;
+                let blinding = self.copy_point(&mut ctx, config, &blinding)?;
+                let blinded =
+                    self.conditional_point_add_const(&mut ctx, config, &key, &blinding, true)?;
+                self.pad(&mut ctx, config)?;
+                Ok((p, blinded))
+            },
+        )?;
+        self.expose_point_public(layouter, &p, row)?;
+        Ok(blinded)
+    }
+}
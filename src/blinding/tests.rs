@@ -0,0 +1,90 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct BlindedKeyTestCircuit {
+    h: G1Affine,
+    pk: Value<G1Affine>,
+    r: Value<Fr>,
+}
+
+impl Circuit<Fq> for BlindedKeyTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            h: self.h,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        ECChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        // public inputs: (x, y) of pk, then of the blinded key
+        let blinded = ec_chip.prove_blinded_key(&mut layouter, self.h, self.pk, self.r, 0)?;
+        ec_chip.expose_point_public(&mut layouter, &blinded, 2)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_prove_blinded_key() {
+    let k = 11;
+    let mut rng = test_rng();
+    let h = G1::random(&mut rng).to_affine();
+    let pk = G1::random(&mut rng).to_affine();
+    let r = Fr::random(&mut rng);
+    let blinded = (pk + h * r).to_affine();
+
+    let circuit = BlindedKeyTestCircuit {
+        h,
+        pk: Value::known(pk),
+        r: Value::known(r),
+    };
+    let run = |points: &[G1Affine]| {
+        let instance = ECChip::<G1Affine, Fq>::point_instances(points).unwrap();
+        MockProver::run(k, &circuit, vec![instance]).unwrap()
+    };
+
+    run(&[pk, blinded]).assert_satisfied();
+
+    // error case: the blinded key of another key
+    let other = G1::random(&mut rng).to_affine();
+    assert!(run(&[other, blinded]).verify().is_err());
+
+    // error case: another blinding
+    let reblinded = (blinded + h).to_affine();
+    assert!(run(&[pk, reblinded]).verify().is_err());
+
+    // error case: blinded along the generator instead of h
+    let blinded_g = (pk + G1::generator() * r).to_affine();
+    assert!(run(&[pk, blinded_g]).verify().is_err());
+}
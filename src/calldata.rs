@@ -0,0 +1,91 @@
+//! Encoding of the public inputs in the calldata of on-chain verifiers.
+//!
+//! EVM verifiers, e.g., the ones generated by snark-verifier, read each public
+//! input as a 32 bytes big endian word, in the order of the instance column.
+//! The points exposed with `expose_point_public` take two words, x then y,
+//! as in `ECChip::point_instances`.
+//!
+//! The field reprs are assumed little endian, as for the BN254 fields.
+//!
+//! ```ignore
+//! let calldata = calldata::encode_points(&[g, q])?;
+//! assert_eq!(calldata::decode_points::<G1Affine>(&calldata)?, [g, q]);
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::util::coordinates;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// The size of a word of calldata.
+pub const WORD_BYTES: usize = 32;
+
+/// Encodes public inputs, one big endian word each.
+pub fn encode_instances<F: PrimeField<Repr = [u8; 32]>>(instances: &[F]) -> Vec<u8> {
+    let mut res = Vec::with_capacity(WORD_BYTES * instances.len());
+    for f in instances {
+        let mut word = f.to_repr();
+        word.reverse();
+        res.extend_from_slice(&word);
+    }
+    res
+}
+
+/// Decodes public inputs, checking each word is a canonical field element.
+pub fn decode_instances<F: PrimeField<Repr = [u8; 32]>>(
+    calldata: &[u8],
+) -> Result<Vec<F>, NativeEccError> {
+    if calldata.len() % WORD_BYTES != 0 {
+        return Err(NativeEccError::InvalidInputLength {
+            expected: calldata.len().div_ceil(WORD_BYTES) * WORD_BYTES,
+            actual: calldata.len(),
+        });
+    }
+    calldata
+        .chunks(WORD_BYTES)
+        .map(|word| {
+            let mut repr = [0u8; 32];
+            repr.copy_from_slice(word);
+            repr.reverse();
+            Option::from(F::from_repr(repr)).ok_or(NativeEccError::InvalidEncoding)
+        })
+        .collect()
+}
+
+/// Encodes points, two words (x, y) each.
+pub fn encode_points<C>(points: &[C]) -> Result<Vec<u8>, NativeEccError>
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let mut instances = Vec::with_capacity(2 * points.len());
+    for p in points {
+        let (x, y) = coordinates(p)?;
+        instances.extend([x, y]);
+    }
+    Ok(encode_instances(&instances))
+}
+
+/// Decodes points, checking each point is on curve.
+pub fn decode_points<C>(calldata: &[u8]) -> Result<Vec<C>, NativeEccError>
+where
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
+{
+    let instances = decode_instances::<C::Base>(calldata)?;
+    if instances.len() % 2 != 0 {
+        return Err(NativeEccError::InvalidInputLength {
+            expected: instances.len() + 1,
+            actual: instances.len(),
+        });
+    }
+    instances
+        .chunks(2)
+        .map(|xy| Option::from(C::from_xy(xy[0], xy[1])).ok_or(NativeEccError::NotOnCurve))
+        .collect()
+}
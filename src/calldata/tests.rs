@@ -0,0 +1,134 @@
+use ark_std::test_rng;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::calldata;
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::NativeEccError;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct PublicPointsTestCircuit {
+    p1: Value<G1Affine>,
+    p2: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for PublicPointsTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        ECChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let p1 = ec_chip.witness_point(&mut layouter, self.p1)?;
+        let p2 = ec_chip.witness_point(&mut layouter, self.p2)?;
+        ec_chip.expose_point_public(&mut layouter, &p1, 0)?;
+        ec_chip.expose_point_public(&mut layouter, &p2, 2)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_calldata_points() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let data = calldata::encode_points(&[p1, p2]).unwrap();
+    assert_eq!(data.len(), 4 * calldata::WORD_BYTES);
+    // big endian words
+    let mut x1 = data[..32].to_vec();
+    x1.reverse();
+    assert_eq!(x1, p1.x.to_repr());
+    assert_eq!(
+        calldata::decode_points::<G1Affine>(&data).unwrap(),
+        [p1, p2]
+    );
+
+    // the decoded calldata is the instance column of the circuit
+    let circuit = PublicPointsTestCircuit {
+        p1: Value::known(p1),
+        p2: Value::known(p2),
+    };
+    let instance = calldata::decode_instances::<Fq>(&data).unwrap();
+    assert_eq!(
+        instance,
+        ECChip::<G1Affine, Fq>::point_instances(&[p1, p2]).unwrap()
+    );
+    MockProver::run(k, &circuit, vec![instance])
+        .unwrap()
+        .assert_satisfied();
+
+    // error case: the points are swapped
+    let instance =
+        calldata::decode_instances::<Fq>(&calldata::encode_points(&[p2, p1]).unwrap()).unwrap();
+    let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_calldata_invalid() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let data = calldata::encode_points(&[p]).unwrap();
+
+    // a truncated word
+    assert!(matches!(
+        calldata::decode_points::<G1Affine>(&data[..63]),
+        Err(NativeEccError::InvalidInputLength {
+            expected: 64,
+            actual: 63
+        })
+    ));
+    // x without y
+    assert!(matches!(
+        calldata::decode_points::<G1Affine>(&data[..32]),
+        Err(NativeEccError::InvalidInputLength {
+            expected: 2,
+            actual: 1
+        })
+    ));
+    // a word above the modulus
+    assert!(matches!(
+        calldata::decode_instances::<Fq>(&[0xff; 32]),
+        Err(NativeEccError::InvalidEncoding)
+    ));
+    // (y, x) is not on curve
+    let swapped = [&data[32..], &data[..32]].concat();
+    assert!(matches!(
+        calldata::decode_points::<G1Affine>(&swapped),
+        Err(NativeEccError::NotOnCurve)
+    ));
+    // the identity has no (x, y) coordinates
+    assert!(matches!(
+        calldata::encode_points(&[G1::identity().to_affine()]),
+        Err(NativeEccError::PointAtInfinity)
+    ));
+}
@@ -0,0 +1,192 @@
+//! Round challenges of folding verifiers, e.g., of an IPA.
+//!
+//! A verifier that folds its vectors over several rounds needs one challenge
+//! per round, each depending on the commitments of the round and of all the
+//! previous rounds. halo2 only samples challenges between phases, so the
+//! commitments of all the rounds are assigned in the first phase, then a
+//! challenge `gamma`, usable after the first phase, absorbs them round by
+//! round into an accumulator in a second phase advice column:
+//!
+//! ```text
+//! acc_0 = 1
+//! acc'  = (acc * gamma + x) * gamma + y   for each point (x, y) of a round
+//! c_i   = acc * gamma                     at the end of round i
+//! ```
+//!
+//! Each round challenge is a distinct polynomial in `gamma` whose
+//! coefficients are the commitments, of a degree increasing with the round.
+//!
+//! The round challenges are second phase cells. The ops consuming them must
+//! run over second phase columns too, e.g., an `ECChip` configured with
+//! `SubChip` over columns of `advice_column_in(SecondPhase)`; a first phase
+//! cell cannot depend on a challenge.
+//!
+//! ```ignore
+//! // configure
+//! let ecc = ECChip::<G1Affine, Fq>::configure(meta);
+//! let challenges = RoundChallengeConfig::configure(meta, &ecc);
+//!
+//! // synthesize, in every phase
+//! let ls: Vec<Vec<AssignedECPoint<_, _>>> = ...; // the commitments of each round
+//! let rounds: Vec<&[_]> = ls.iter().map(Vec::as_slice).collect();
+//! let cs = challenges.derive(&mut layouter, &rounds)?;
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Challenge;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::FirstPhase;
+use halo2_proofs::plonk::SecondPhase;
+use halo2_proofs::plonk::Selector;
+use halo2_proofs::poly::Rotation;
+
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::region_ctx::RegionCtx;
+use crate::util::annotation;
+use crate::AssignedECPoint;
+use crate::ECConfig;
+use crate::NativeEccError;
+
+#[cfg(test)]
+mod tests;
+
+/// The columns and the challenge deriving the round challenges.
+///
+/// The points are copied into the advice columns of the chip, which must be
+/// in the first phase, e.g., the columns of `ECChip::configure`.
+#[derive(Clone, Copy, Debug)]
+pub struct RoundChallengeConfig {
+    challenge: Challenge,
+    x: Column<Advice>,
+    y: Column<Advice>,
+    acc: Column<Advice>,
+    q_absorb: Selector,
+    q_squeeze: Selector,
+}
+
+impl RoundChallengeConfig {
+    /// Configures the derivation over the advice columns of the chip, with a
+    /// challenge usable after the first phase and a second phase advice column.
+    pub fn configure<C, F>(meta: &mut ConstraintSystem<F>, ecc: &ECConfig<C, F>) -> Self
+    where
+        C: CurveAffine<Base = F>,
+        F: Field,
+    {
+        let challenge = meta.challenge_usable_after(FirstPhase);
+        let acc = meta.advice_column_in(SecondPhase);
+        meta.enable_equality(acc);
+        let q_absorb = meta.selector();
+        let q_squeeze = meta.selector();
+        let (x, y) = (ecc.a(), ecc.b());
+
+        meta.create_gate("round challenges", |meta| {
+            let gamma = meta.query_challenge(challenge);
+            let q_absorb = meta.query_selector(q_absorb);
+            let q_squeeze = meta.query_selector(q_squeeze);
+            let x = meta.query_advice(x, Rotation::cur());
+            let y = meta.query_advice(y, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let acc_next = meta.query_advice(acc, Rotation::next());
+
+            vec![
+                (
+                    "absorb",
+                    q_absorb
+                        * (acc_next.clone()
+                            - ((acc.clone() * gamma.clone() + x) * gamma.clone() + y)),
+                ),
+                ("squeeze", q_squeeze * (acc_next - acc * gamma)),
+            ]
+        });
+
+        Self {
+            challenge,
+            x,
+            y,
+            acc,
+            q_absorb,
+            q_squeeze,
+        }
+    }
+
+    /// The challenge the round challenges are derived from.
+    pub fn challenge(&self) -> Challenge {
+        self.challenge
+    }
+
+    /// Derives one challenge per round from the commitments of the rounds.
+    ///
+    /// Takes `1 + n + r` rows for `n` points over `r` rounds. Called in
+    /// every phase, as the rest of the synthesis; the challenges are unknown
+    /// before the second phase.
+    pub fn derive<C, F>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        rounds: &[&[AssignedECPoint<C, F>]],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        C: CurveAffine<Base = F>,
+        F: Field,
+    {
+        let gamma = layouter.get_challenge(self.challenge);
+        let challenges = layouter.assign_region(
+            || "round challenges",
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
+
+                //  index  |  x  |  y  | acc  | q_absorb | q_squeeze
+                //  -------|-----|-----|------|----------|----------
+                //  offset |  x  |  y  |  1   |    1     |
+                //         | ... | ... | ...  |          |    1
+                //         |     |     |  c0  |          |
+                let mut acc = ctx.assign_advice(
+                    || annotation("round challenges", "acc"),
+                    self.acc,
+                    Value::known(F::ONE),
+                )?;
+                ctx.constrain_constant(acc.cell(), F::ONE)?;
+
+                let mut challenges = Vec::with_capacity(rounds.len());
+                for round in rounds {
+                    for p in round.iter() {
+                        ctx.enable_selector(&self.q_absorb, ctx.offset())?;
+                        ctx.copy_advice(|| annotation("round challenges", "x"), self.x, &p.x)?;
+                        ctx.copy_advice(|| annotation("round challenges", "y"), self.y, &p.y)?;
+                        let value = acc
+                            .value()
+                            .zip(p.x.value())
+                            .zip(p.y.value())
+                            .zip(gamma)
+                            .map(|(((acc, x), y), gamma)| (*acc * gamma + x) * gamma + y);
+                        ctx.next();
+                        acc = ctx.assign_advice(
+                            || annotation("round challenges", "acc"),
+                            self.acc,
+                            value,
+                        )?;
+                    }
+
+                    ctx.enable_selector(&self.q_squeeze, ctx.offset())?;
+                    let value = acc.value().copied() * gamma;
+                    ctx.next();
+                    acc = ctx.assign_advice(
+                        || annotation("round challenges", "challenge"),
+                        self.acc,
+                        value,
+                    )?;
+                    challenges.push(acc.clone());
+                }
+                Ok(challenges)
+            },
+        )?;
+        Ok(challenges)
+    }
+}
@@ -0,0 +1,102 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::challenges::RoundChallengeConfig;
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+
+#[derive(Default, Debug, Clone)]
+struct ChallengeTestCircuit {
+    // the commitments of each round
+    rounds: Vec<Vec<Value<G1Affine>>>,
+}
+
+impl Circuit<Fq> for ChallengeTestCircuit {
+    type Config = (ECConfig<G1Affine, Fq>, RoundChallengeConfig);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            rounds: self
+                .rounds
+                .iter()
+                .map(|round| vec![Value::unknown(); round.len()])
+                .collect(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let ecc = ECChip::configure(meta);
+        let challenges = RoundChallengeConfig::configure(meta, &ecc);
+        (ecc, challenges)
+    }
+
+    fn synthesize(
+        &self,
+        (ecc, challenges): Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(ecc);
+
+        let mut rounds = vec![];
+        for round in &self.rounds {
+            let points = round
+                .iter()
+                .map(|p| ec_chip.witness_point(&mut layouter, *p))
+                .collect::<Result<Vec<_>, _>>()?;
+            rounds.push(points);
+        }
+        let rounds: Vec<&[_]> = rounds.iter().map(Vec::as_slice).collect();
+        let cs = challenges.derive(&mut layouter, &rounds)?;
+        assert_eq!(cs.len(), rounds.len());
+
+        // matches the derivation off-circuit, once the challenge is known
+        let gamma = layouter.get_challenge(challenges.challenge());
+        let mut acc = Value::known(Fq::ONE);
+        for (round, c) in self.rounds.iter().zip(cs.iter()) {
+            for p in round {
+                acc = acc
+                    .zip(*p)
+                    .zip(gamma)
+                    .map(|((acc, p), gamma)| (acc * gamma + p.x) * gamma + p.y);
+            }
+            acc = acc * gamma;
+            c.value()
+                .zip(acc)
+                .assert_if_known(|(c, expected)| **c == *expected);
+        }
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_round_challenges() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let mut point = || Value::known(G1::random(&mut rng).to_affine());
+    let rounds = vec![
+        vec![point(), point()],
+        vec![point(), point()],
+        vec![],
+        vec![point()],
+    ];
+
+    let circuit = ChallengeTestCircuit { rounds };
+    let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+}
@@ -1,13 +1,48 @@
-use std::marker::PhantomData;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
-use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::AssignedCell;
 use halo2_proofs::circuit::Chip;
-use halo2_proofs::halo2curves::ff::PrimeField;
-use halo2_proofs::halo2curves::CurveAffine;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Region;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
 use halo2_proofs::plonk::ConstraintSystem;
 use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::Fixed;
+use halo2_proofs::plonk::Instance;
+use halo2_proofs::poly::Rotation;
 
+#[cfg(feature = "audit")]
+use crate::audit::AuditLog;
+#[cfg(feature = "audit")]
+use crate::audit::ExceptionalCase;
+#[cfg(feature = "audit")]
+use crate::audit::GateLog;
+#[cfg(feature = "audit")]
+use crate::audit::GateReport;
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::PrimeField;
 use crate::config::ECConfig;
+use crate::config::EnabledGates;
+#[cfg(feature = "audit")]
+use crate::config::EC_ADD;
+#[cfg(feature = "audit")]
+use crate::config::EC_DOUBLE;
+use crate::ec_gates::NativeECOps;
+use crate::region_ctx::RegionCtx;
+use crate::util::annotation;
+use crate::util::compress;
+use crate::util::coordinates;
+use crate::AssignedECPoint;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
 
 #[derive(Clone, Debug)]
 pub struct ECChip<C, F>
@@ -18,6 +53,12 @@ where
     F: Field,
 {
     config: ECConfig<C, F>,
+    // the exceptional cases hit by the witness
+    #[cfg(feature = "audit")]
+    pub(crate) audit: AuditLog,
+    // the selectors enabled by the ops
+    #[cfg(feature = "audit")]
+    gate_log: GateLog<F>,
     _phantom: PhantomData<F>,
 }
 
@@ -44,84 +85,493 @@ where
     F: PrimeField,
 {
     pub fn construct(config: <Self as Chip<F>>::Config) -> Self {
+        #[cfg(feature = "audit")]
+        let gate_log = GateLog::new(
+            vec![
+                (config.q_ec_enable, "q_ec_enable"),
+                (config.q1, "q1"),
+                (config.q2, "q2"),
+                (config.q3, "q3"),
+                (config.q_ladder, "q_ladder"),
+                (config.q_ec_sub, "q_ec_sub"),
+            ],
+            vec![
+                (config.q_ec_op.index(), F::from(EC_ADD), "q_ec_op: add"),
+                (
+                    config.q_ec_op.index(),
+                    F::from(EC_DOUBLE),
+                    "q_ec_op: double",
+                ),
+            ],
+        );
         Self {
             config,
+            #[cfg(feature = "audit")]
+            audit: AuditLog::default(),
+            #[cfg(feature = "audit")]
+            gate_log,
             _phantom: PhantomData,
         }
     }
 
-    pub fn configure(meta: &mut ConstraintSystem<F>) -> <Self as Chip<F>>::Config {
+    /// The exceptional cases of the incomplete formulas hit by the witness so
+    /// far, by this chip and its clones; see the `audit` module.
+    #[cfg(feature = "audit")]
+    pub fn exceptional_cases(&self) -> Vec<ExceptionalCase> {
+        self.audit.report()
+    }
+
+    /// The selectors enabled so far by the ops of this chip and its clones, in
+    /// the regions assigned from `region_ctx`; see the `audit` module.
+    #[cfg(feature = "audit")]
+    pub fn gate_activations(&self) -> GateReport {
+        self.gate_log.report()
+    }
+
+    /// Wraps a region, starting from offset 0. With the `audit` feature, the
+    /// selectors enabled in the region are recorded, see `gate_activations`.
+    pub fn region_ctx<'r>(&self, region: Region<'r, F>) -> RegionCtx<'r, F> {
+        #[allow(unused_mut)]
+        let mut ctx = RegionCtx::new(region, 0);
+        #[cfg(feature = "audit")]
+        ctx.record_gates(&self.gate_log);
+        ctx
+    }
+
+    /// Checks C has the shape the gates are written for, i.e., y^2 = x^3 + b.
+    ///
+    /// The gates read b from C; they have no term for a.
+    pub fn check_curve() -> Result<(), NativeEccError> {
+        if C::a() != F::ZERO {
+            return Err(NativeEccError::UnsupportedCurve);
+        }
+        Ok(())
+    }
+
+    /// Configures the chip without an instance column, returning
+    /// `NativeEccError::UnsupportedCurve` if C does not pass `check_curve`.
+    pub fn try_configure(
+        meta: &mut ConstraintSystem<F>,
+    ) -> Result<<Self as Chip<F>>::Config, NativeEccError> {
+        Self::check_curve()?;
         let a = meta.advice_column();
-        meta.enable_equality(a);
         let b = meta.advice_column();
-        meta.enable_equality(b);
+        let f = meta.fixed_column();
+        Self::configure_with_gates(meta, a, b, f, None, EnabledGates::default())
+    }
+
+    /// Configures the chip without an instance column.
+    ///
+    /// Panics if C does not pass `check_curve`, see `try_configure`.
+    pub fn configure(meta: &mut ConstraintSystem<F>) -> <Self as Chip<F>>::Config {
+        Self::try_configure(meta).expect("the gates are written for y^2 = x^3 + b")
+    }
 
+    /// Configures the chip with an instance column for the public inputs,
+    /// see `expose_point_public` and `expose_field_public`.
+    pub fn configure_with_instance(
+        meta: &mut ConstraintSystem<F>,
+        instance: Column<Instance>,
+    ) -> <Self as Chip<F>>::Config {
+        let a = meta.advice_column();
+        let b = meta.advice_column();
         let f = meta.fixed_column();
-        meta.enable_constant(f);
+        Self::configure_with_columns(meta, a, b, f, Some(instance))
+    }
+
+    /// Configures the chip over columns allocated by the caller, e.g., to share
+    /// them with other chips of the circuit.
+    ///
+    /// Equality is enabled on `a`, `b` and `instance`; `constant` is used for the
+    /// constants of the chip. The selectors are always allocated by the chip, as
+    /// are the columns of the `complete-addition` feature.
+    ///
+    /// Panics if C does not pass `check_curve`; `ECConfigBuilder::try_configure`
+    /// is the fallible equivalent.
+    pub fn configure_with_columns(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        constant: Column<Fixed>,
+        instance: Option<Column<Instance>>,
+    ) -> <Self as Chip<F>>::Config {
+        Self::configure_with_gates(meta, a, b, constant, instance, EnabledGates::default())
+            .expect("the gates are written for y^2 = x^3 + b")
+    }
+
+    /// Configures the chip with only the given gates, see `ECConfigBuilder`.
+    ///
+    /// Returns `NativeEccError::UnsupportedCurve`, before allocating anything,
+    /// if C does not pass `check_curve`.
+    pub(crate) fn configure_with_gates(
+        meta: &mut ConstraintSystem<F>,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        constant: Column<Fixed>,
+        instance: Option<Column<Instance>>,
+        gates: EnabledGates,
+    ) -> Result<<Self as Chip<F>>::Config, NativeEccError> {
+        Self::check_curve()?;
+
+        meta.enable_equality(a);
+        meta.enable_equality(b);
+        meta.enable_constant(constant);
+        if let Some(instance) = instance {
+            meta.enable_equality(instance);
+        }
 
         // ec is enabled
         let q_ec_enable = meta.complex_selector();
-        // ec conditional add
+        // fixed base add, or partial bit decomposition
         let q1 = meta.complex_selector();
-        // ec double
+        // field add, or cswap
         let q2 = meta.complex_selector();
-        // ec on curve
+        // ec on curve, or field mul
         let q3 = meta.complex_selector();
+        // the steps of the x-only ladder
+        let q_ladder = meta.complex_selector();
+        // the conditional ec sub
+        let q_ec_sub = meta.complex_selector();
+        // ec conditional add or ec double
+        let q_ec_op = meta.fixed_column();
+        // the multiples of the generator, for the fixed base add
+        let base_x = meta.fixed_column();
+        let base_y = meta.fixed_column();
+        // the slopes of the adds and doubles, and the flag of the fixed base adds
+        #[cfg(feature = "complete-addition")]
+        let slope = meta.advice_column();
+        #[cfg(feature = "complete-addition")]
+        let q_fixed_base = meta.fixed_column();
 
-        let config = ECConfig {
+        let mut config = ECConfig {
             a,
             b,
+            instance,
             q_ec_enable,
             q1,
             q2,
             q3,
+            q_ladder,
+            q_ec_sub,
+            q_ec_op,
+            base_x,
+            base_y,
+            #[cfg(feature = "complete-addition")]
+            slope,
+            #[cfg(feature = "complete-addition")]
+            q_fixed_base,
+            // read from the curve, rather than a literal that a curve swap would
+            // leave stale; a = 0 is checked by `check_curve`
+            curve_b: C::b(),
+            gates,
+            degree: 0,
+            constraint_names: vec![],
             _phantom: PhantomData::default(),
         };
 
+        if gates == EnabledGates::none() {
+            return config;
+        }
+
         let one = Expression::Constant(F::ONE);
+        let mut degree = 0;
+        let mut constraint_names = vec![];
 
         meta.create_gate("native ec chip", |meta| {
-            // |   op codes  | cost | q_ec_enabled | q1 | q2 | q3 | statement
-            // | ----------- |:----:|:------------:| -- | -- | -- | -------------
-            // |      ec add |   4  |       1      | 1  | 0  | 0  | (x1, y1), (x2, y2) and (x3, -y3) are on a same line
-            // |   ec double |   2  |       1      | 0  | 1  | 0  | (x1, y1) and (x3, -y3) are on a tangential line of the curve
-            // | is on curve |   1  |       1      | 0  | 0  | 1  | y1^2 = x1^3 - C::b()
+            // |   op codes  | cost | q_ec_op | q_ec_enabled | q1 | q2 | q3 | statement
+            // | ----------- |:----:|:-------:|:------------:| -- | -- | -- | -------------
+            // |      ec add |   4  |    1    |       0      | 0  | 0  | 0  | (x1, y1), (x2, y2) and (x3, -y3) are on a same line
+            // |   ec double |   2  |    2    |       0      | 0  | 0  | 0  | (x1, y1) and (x3, -y3) are on a tangential line of the curve
+            // |  fixed base |   3  |    0    |       1      | 1  | 0  | 0  | ec add of the fixed base at (base_x, base_y)
+            // | is on curve |   1  |    0    |       1      | 0  | 0  | 1  | y1^2 = x1^3 - C::b()
+            // |       cswap |   5  |    0    |       1      | 0  | 1  | 0  | (x3, y3), (x4, y4) = (x1, y1), (x2, y2) swapped iff c
+            //
+            // |     partial |   3  |    0    |       0      | 1  | 0  | 0  | y3 = x1 + y1 + x2 + y2 + x3 and
+            // |   decompose |      |         |              |    |    |    | x1, y1, x2, y2 are all binary
+            // |         add |   2  |    0    |       0      | 0  | 1  | 0  | a1 = a0 + b0
+            // |         mul |   2  |    0    |       0      | 0  | 0  | 1  | a1 = a0 * b0
             //
-            // |     partial |   3  |       0      | 1  | 0  | 0  | y3 = x1 + y1 + x2 + y2 + x3 and
-            // |   decompose |      |              |    |    |    | x1, y1, x2, y2 are all binary
-            // |         add |   2  |       0      | 0  | 1  | 0  | a1 = a0 + b0
-            // |         mul |   2  |       0      | 0  | 0  | 1  | a1 = a0 * b0
+            // the steps of the x-only ladder have a selector of their own, `q_ladder`,
+            // and so does the ec sub, `q_ec_sub`: it uses the rows of the ec add
 
             let q1 = meta.query_selector(config.q1);
             let q2 = meta.query_selector(config.q2);
             let q3 = meta.query_selector(config.q3);
+            let q_ladder = meta.query_selector(config.q_ladder);
+            let q_ec_sub = meta.query_selector(config.q_ec_sub);
             let q_ec_enable = meta.query_selector(config.q_ec_enable);
+            let q_ec_disable = one.clone() - q_ec_enable.clone();
 
-            let ec_add_gate = config.conditional_ec_add_gate(meta);
-            let ec_double_gate = config.ec_double_gate(meta);
-            let on_curve_gate = config.on_curve_gate(meta);
-            let partial_bit_decom_gate = config.partial_bit_decom_gate(meta);
-            let add_gate = config.add_gate(meta);
-            let mul_gate = config.mul_gate(meta);
+            // ec add and ec double are told apart by the value of q_ec_op:
+            // q_ec_op * (2 - q_ec_op) is non-zero only for the add and
+            // q_ec_op * (q_ec_op - 1) only for the double. Either factor has degree
+            // 2, like the product of two selectors; a boolean flag times a selector
+            // would raise the degree of the gate to 6.
+            let q_ec_op = meta.query_fixed(config.q_ec_op, Rotation::cur());
+            let two = Expression::Constant(F::from(2));
+            let is_add = q_ec_op.clone() * (two - q_ec_op.clone());
+            let is_double = q_ec_op.clone() * (q_ec_op - one.clone());
 
-            vec![
-                // |      ec add |   4  |       1       | 1  | 0  | 0  |
-                ec_add_gate * q_ec_enable.clone() * q1.clone()
-                // |   ec double |   2  |       1       | 0  | 1  | 0  |
-                    + ec_double_gate * q_ec_enable.clone() * q2.clone()
-                // | is on curve |   1  |       1       | 0  | 0  | 1  |
-                    + on_curve_gate * q_ec_enable.clone() * q3.clone()
-                // |     partial |   3  |       0       | 1  | 0  | 0  | 
-                // |   decompose |      |               |    |    |    |
-                    + partial_bit_decom_gate * (one.clone() - q_ec_enable.clone()) * q1
-                // |         add |   2  |       0       | 0  | 1  | 0  |  
-                    + add_gate * (one.clone() - q_ec_enable.clone()) * q2
-                // |         mul |   2  |       0       | 0  | 0  | 1  | 
-                    + mul_gate * (one - q_ec_enable) * q3,
-            ]
+            // one named constraint per equation, so that failures point at the op
+            // and the residuals of an op cannot cancel each other; the disabled
+            // gates are left out
+            let mut constraints: Vec<(String, Expression<F>)> = vec![];
+            if gates.ec_add {
+                // |      ec add |   4  |    1    |       0      | 0  | 0  | 0  |
+                for (name, constraint) in config.conditional_ec_add_gate(meta) {
+                    constraints.push((format!("ec add: {}", name), constraint * is_add.clone()));
+                }
+            }
+            if gates.ec_sub {
+                for (name, constraint) in config.conditional_ec_sub_gate(meta) {
+                    constraints.push((format!("ec sub: {}", name), constraint * q_ec_sub.clone()));
+                }
+            }
+            if gates.ec_double {
+                // |   ec double |   2  |    2    |       0      | 0  | 0  | 0  |
+                for (name, constraint) in config.ec_double_gate(meta) {
+                    constraints.push((
+                        format!("ec double: {}", name),
+                        constraint * is_double.clone(),
+                    ));
+                }
+            }
+            if gates.fixed_base_add {
+                // |  fixed base |   3  |    0    |       1      | 1  | 0  | 0  |
+                for (name, constraint) in config.fixed_base_add_gate(meta) {
+                    constraints.push((
+                        format!("fixed base add: {}", name),
+                        constraint * q_ec_enable.clone() * q1.clone(),
+                    ));
+                }
+            }
+            if gates.cswap {
+                // |       cswap |   5  |    0    |       1      | 0  | 1  | 0  |
+                for (name, constraint) in config.cswap_gate(meta) {
+                    constraints.push((
+                        format!("cswap: {}", name),
+                        constraint * q_ec_enable.clone() * q2.clone(),
+                    ));
+                }
+            }
+            if gates.x_only_ladder {
+                for (name, constraint) in config.x_only_ladder_gate(meta) {
+                    constraints.push((
+                        format!("x-only ladder: {}", name),
+                        constraint * q_ladder.clone(),
+                    ));
+                }
+            }
+            if gates.on_curve {
+                // | is on curve |   1  |    0    |       1      | 0  | 0  | 1  |
+                constraints.push((
+                    "on curve".into(),
+                    config.on_curve_gate(meta) * q_ec_enable * q3.clone(),
+                ));
+            }
+            if gates.partial_bit_decomp {
+                // |     partial |   3  |    0    |       0      | 1  | 0  | 0  |
+                // |   decompose |      |         |              |    |    |    |
+                for (name, constraint) in config.partial_bit_decom_gate(meta) {
+                    constraints.push((
+                        format!("partial bit decomp: {}", name),
+                        constraint * q_ec_disable.clone() * q1.clone(),
+                    ));
+                }
+            }
+            if gates.add {
+                // |         add |   2  |    0    |       0      | 0  | 1  | 0  |
+                constraints.push((
+                    "add".into(),
+                    config.add_gate(meta) * q_ec_disable.clone() * q2,
+                ));
+            }
+            if gates.mul {
+                // |         mul |   2  |    0    |       0      | 0  | 0  | 1  |
+                constraints.push(("mul".into(), config.mul_gate(meta) * q_ec_disable * q3));
+            }
+
+            degree = constraints
+                .iter()
+                .map(|(_, constraint)| constraint.degree())
+                .max()
+                .unwrap_or_default();
+            constraint_names.extend(constraints.iter().map(|(name, _)| name.clone()));
+            constraints
         });
+
+        // the constraints of the `complete-addition` feature are a gate of their
+        // own, enabled by fixed columns and `q_ec_sub`: the slopes are assigned
+        // on the rows of the adds and doubles, not on every row of the other ops
+        #[cfg(feature = "complete-addition")]
+        if gates.ec_add || gates.ec_sub || gates.fixed_base_add || gates.ec_double {
+            meta.create_gate("native ec chip: complete addition", |meta| {
+                let one = Expression::Constant(F::ONE);
+                let two = Expression::Constant(F::from(2));
+                let q_ec_op = meta.query_fixed(config.q_ec_op, Rotation::cur());
+                let is_add = q_ec_op.clone() * (two - q_ec_op.clone());
+                let is_double = q_ec_op.clone() * (q_ec_op - one);
+                let q_fixed_base = meta.query_fixed(config.q_fixed_base, Rotation::cur());
+                let q_ec_sub = meta.query_selector(config.q_ec_sub);
+
+                let mut constraints: Vec<(String, Expression<F>)> = vec![];
+                if gates.ec_add {
+                    for (name, constraint) in config.complete_ec_add_gate(meta) {
+                        constraints
+                            .push((format!("ec add: {}", name), constraint * is_add.clone()));
+                    }
+                }
+                // the sub has the rows of the add
+                if gates.ec_sub {
+                    for (name, constraint) in config.complete_ec_sub_gate(meta) {
+                        constraints
+                            .push((format!("ec sub: {}", name), constraint * q_ec_sub.clone()));
+                    }
+                }
+                if gates.fixed_base_add {
+                    for (name, constraint) in config.complete_fixed_base_add_gate(meta) {
+                        constraints.push((
+                            format!("fixed base add: {}", name),
+                            constraint * q_fixed_base.clone(),
+                        ));
+                    }
+                }
+                if gates.ec_double {
+                    for (name, constraint) in config.complete_ec_double_gate(meta) {
+                        constraints.push((
+                            format!("ec double: {}", name),
+                            constraint * is_double.clone(),
+                        ));
+                    }
+                }
+
+                degree = constraints
+                    .iter()
+                    .map(|(_, constraint)| constraint.degree())
+                    .fold(degree, usize::max);
+                constraint_names.extend(constraints.iter().map(|(name, _)| name.clone()));
+                constraints
+            });
+        }
+
+        config.degree = degree;
+        config.constraint_names = constraint_names;
         #[cfg(feature = "verbose")]
         println!("custom gate's degree {}", meta.degree());
-        config
+        Ok(config)
+    }
+
+    /// Constrains the (x, y) coordinates of p to the rows `row` and `row + 1`
+    /// of the instance column.
+    pub fn expose_point_public(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        p: &AssignedECPoint<C, F>,
+        row: usize,
+    ) -> Result<(), NativeEccError> {
+        let instance = self
+            .config
+            .instance
+            .ok_or(NativeEccError::MissingInstanceColumn)?;
+        layouter.constrain_instance(p.x.cell(), instance, row)?;
+        layouter.constrain_instance(p.y.cell(), instance, row + 1)?;
+        Ok(())
+    }
+
+    /// The instance column of points exposed with `expose_point_public` at the
+    /// rows 0, 2, 4, ...: the (x, y) coordinates of each point, in order.
+    ///
+    /// Verifiers and aggregators, e.g., snark-verifier, read the public inputs
+    /// in this layout, one field element per row.
+    pub fn point_instances(points: &[C]) -> Result<Vec<F>, NativeEccError> {
+        let mut res = Vec::with_capacity(2 * points.len());
+        for p in points {
+            let (x, y) = coordinates(p)?;
+            res.extend([x, y]);
+        }
+        Ok(res)
+    }
+
+    /// Loads the point of a 32 bytes compressed encoding exposed at the rows
+    /// `row` and `row + 1` of the instance column: the low and the high 16
+    /// bytes of the encoding, as little endian integers, see
+    /// `compressed_point_instances`.
+    ///
+    /// The encoding is the x coordinate in little endian, with the parity of y
+    /// in its top bit. The point is decompressed in-circuit, checked on curve,
+    /// and the encoding is checked canonical, in `LOAD_COMPRESSED_POINT_ROWS`
+    /// rows of the `cost` module. Requires the add and mul gates.
+    pub fn load_instance_point_compressed(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        row: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        F: PrimeField<Repr = [u8; 32]>,
+    {
+        let instance = self
+            .config
+            .instance
+            .ok_or(NativeEccError::MissingInstanceColumn)?;
+        let config = &self.config;
+        let p = layouter.assign_region(
+            || "load compressed point",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let low = ctx.assign_advice_from_instance(
+                    || annotation("load compressed point", "low"),
+                    instance,
+                    row,
+                    config.a,
+                )?;
+                let high = ctx.assign_advice_from_instance(
+                    || annotation("load compressed point", "high"),
+                    instance,
+                    row + 1,
+                    config.b,
+                )?;
+                ctx.next();
+                let p = self.assign_decompressed_point(&mut ctx, config, &low, &high)?;
+                self.pad(&mut ctx, config)?;
+                Ok(p)
+            },
+        )?;
+        Ok(p)
+    }
+
+    /// The instance column of points loaded with `load_instance_point_compressed`
+    /// at the rows 0, 2, 4, ...: the low and high 16 bytes of the compressed
+    /// encoding of each point, in order.
+    pub fn compressed_point_instances(points: &[C]) -> Result<Vec<F>, NativeEccError>
+    where
+        F: PrimeField<Repr = [u8; 32]>,
+    {
+        let mut res = Vec::with_capacity(2 * points.len());
+        for p in points {
+            let bytes = compress(p)?;
+            let (low, high) = bytes.split_at(16);
+            let limb = |bytes: &[u8]| {
+                let mut limb = [0u8; 16];
+                limb.copy_from_slice(bytes);
+                F::from_u128(u128::from_le_bytes(limb))
+            };
+            res.extend([limb(low), limb(high)]);
+        }
+        Ok(res)
+    }
+
+    /// Constrains f to the row `row` of the instance column.
+    pub fn expose_field_public(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        f: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<(), NativeEccError> {
+        let instance = self
+            .config
+            .instance
+            .ok_or(NativeEccError::MissingInstanceColumn)?;
+        layouter.constrain_instance(f.cell(), instance, row)?;
+        Ok(())
     }
 }
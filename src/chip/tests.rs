@@ -0,0 +1,416 @@
+use std::ops::Mul;
+
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::halo2curves::CurveAffine;
+use halo2_proofs::plonk::create_proof;
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::verify_proof;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::Selector;
+use halo2_proofs::poly::kzg::commitment::KZGCommitmentScheme;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::poly::kzg::multiopen::ProverSHPLONK;
+use halo2_proofs::poly::kzg::multiopen::VerifierSHPLONK;
+use halo2_proofs::poly::kzg::strategy::SingleStrategy;
+use halo2_proofs::poly::Rotation;
+use halo2_proofs::transcript::Blake2bRead;
+use halo2_proofs::transcript::Blake2bWrite;
+use halo2_proofs::transcript::Challenge255;
+use halo2_proofs::transcript::TranscriptReadBuffer;
+use halo2_proofs::transcript::TranscriptWriterBuffer;
+use halo2curves::bn256::Bn256;
+use halo2curves::bn256::G1Affine as Bn256G1Affine;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+use halo2curves::pasta::pallas;
+use halo2curves::pasta::vesta;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct PublicTestCircuit {
+    s: Value<Fr>,
+    p1: Value<G1Affine>,
+    f: Value<Fq>,
+}
+
+impl Circuit<Fq> for PublicTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        ECChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let p1 = ec_chip.witness_point(&mut layouter, self.p1)?;
+        let s = ec_chip.witness_scalar(&mut layouter, self.s)?;
+        let p2 = EccInstructions::mul(&ec_chip, &mut layouter, &s, &p1)?;
+        let f = ec_chip.assign_scoped(
+            &mut layouter,
+            || "field",
+            |ecc| ecc.load_private_field(self.f),
+        )?;
+
+        // public inputs: (x, y) of p1 * s, then f
+        ec_chip.expose_point_public(&mut layouter, &p2, 0)?;
+        ec_chip.expose_field_public(&mut layouter, &f, 2)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_expose_public() {
+    let k = 14;
+
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = p1.mul(s).to_affine();
+    let f = Fq::random(&mut rng);
+
+    let circuit = PublicTestCircuit {
+        s: Value::known(s),
+        p1: Value::known(p1),
+        f: Value::known(f),
+    };
+    let p2 = p2.coordinates().unwrap();
+
+    {
+        let instance = vec![*p2.x(), *p2.y(), f];
+        let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+        prover.assert_satisfied();
+    }
+    {
+        let points = [p1.mul(s).to_affine()];
+        let mut instance = ECChip::<G1Affine, Fq>::point_instances(&points).unwrap();
+        instance.push(f);
+        let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: wrong public input
+    {
+        let instance = vec![*p2.x(), *p2.y(), f + Fq::ONE];
+        let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct CompressedTestCircuit {
+    p: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for CompressedTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        ECChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let p = ec_chip.witness_point(&mut layouter, self.p)?;
+        let q = ec_chip.load_instance_point_compressed(&mut layouter, 0)?;
+        ec_chip.constrain_equal(&mut layouter, &p, &q)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_load_instance_point_compressed() {
+    let k = 11;
+
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let circuit = CompressedTestCircuit { p: Value::known(p) };
+
+    let instance = ECChip::<G1Affine, Fq>::compressed_point_instances(&[p]).unwrap();
+    let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: the encoding of -p, with the other parity of y
+    let instance = ECChip::<G1Affine, Fq>::compressed_point_instances(&[-p]).unwrap();
+    let prover = MockProver::run(k, &circuit, vec![instance]).unwrap();
+    assert!(prover.verify().is_err());
+
+    // error case: the identity flag is set
+    let mut instance = ECChip::<G1Affine, Fq>::compressed_point_instances(&[p]).unwrap();
+    instance[1] += Fq::from_u128(1 << 126);
+    // x may then have no y, which fails the synthesis
+    assert!(MockProver::run(k, &circuit, vec![instance])
+        .map_or(true, |prover| prover.verify().is_err()));
+}
+
+#[test]
+fn test_real_prover() {
+    // the point mul and the witness regions fit in 2^11 rows
+    let k = 11;
+
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = p1.mul(s).to_affine().coordinates().unwrap();
+    let f = Fq::random(&mut rng);
+
+    let circuit = PublicTestCircuit {
+        s: Value::known(s),
+        p1: Value::known(p1),
+        f: Value::known(f),
+    };
+    let instance = vec![*p2.x(), *p2.y(), f];
+
+    let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+    let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+
+    let mut transcript = Blake2bWrite::<_, Bn256G1Affine, Challenge255<_>>::init(vec![]);
+    create_proof::<KZGCommitmentScheme<Bn256>, ProverSHPLONK<'_, Bn256>, _, _, _, _>(
+        &params,
+        &pk,
+        &[circuit],
+        &[&[&instance]],
+        &mut rng,
+        &mut transcript,
+    )
+    .unwrap();
+    let proof = transcript.finalize();
+
+    let verify = |instance: &[Fq]| {
+        let mut transcript = Blake2bRead::<_, Bn256G1Affine, Challenge255<_>>::init(&proof[..]);
+        verify_proof::<KZGCommitmentScheme<Bn256>, VerifierSHPLONK<'_, Bn256>, _, _, _>(
+            &params,
+            pk.get_vk(),
+            SingleStrategy::new(&params),
+            &[&[instance]],
+            &mut transcript,
+        )
+    };
+    assert!(verify(&instance).is_ok());
+
+    // error case: wrong public input
+    assert!(verify(&[*p2.x(), *p2.y(), f + Fq::ONE]).is_err());
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct SharedColumnsTestCircuit {
+    p1: Value<G1Affine>,
+    x: Value<Fq>, // x coordinate of 2p1
+}
+
+impl Circuit<Fq> for SharedColumnsTestCircuit {
+    type Config = (ECConfig<G1Affine, Fq>, Column<Advice>);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        // columns owned by the parent circuit
+        let a = meta.advice_column();
+        let b = meta.advice_column();
+        let constant = meta.fixed_column();
+
+        let config = ECChip::configure_with_columns(meta, a, b, constant, None);
+        assert_eq!(meta.num_advice_columns(), 2);
+
+        (config, a)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let (config, a) = config;
+        let ec_chip = ECChip::construct(config);
+
+        let p1 = ec_chip.witness_point(&mut layouter, self.p1)?;
+        let p2 = ec_chip.double(&mut layouter, &p1)?;
+
+        // another chip writing to the shared column
+        layouter.assign_region(
+            || "shared column",
+            |mut region| {
+                let x = region.assign_advice(|| "x", a, 0, || self.x)?;
+                region.constrain_equal(x.cell(), p2.x().cell())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_configure_with_columns() {
+    let k = 10;
+
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = (p1 + p1).to_affine();
+    let x = *p2.coordinates().unwrap().x();
+
+    {
+        let circuit = SharedColumnsTestCircuit {
+            p1: Value::known(p1),
+            x: Value::known(x),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: the shared cell differs
+    {
+        let circuit = SharedColumnsTestCircuit {
+            p1: Value::known(p1),
+            x: Value::known(x + Fq::ONE),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct CustomGateTestCircuit {
+    p: Value<G1Affine>, // x of p must be 1
+}
+
+impl Circuit<Fq> for CustomGateTestCircuit {
+    type Config = (ECConfig<G1Affine, Fq>, Selector);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let config = ECChip::configure(meta);
+
+        // a gate of the parent circuit over the columns of the chip
+        let q_x = meta.selector();
+        let a = config.a();
+        meta.create_gate("x is one", |meta| {
+            let q_x = meta.query_selector(q_x);
+            let x = meta.query_advice(a, Rotation::cur());
+            vec![q_x * (x - Expression::Constant(Fq::ONE))]
+        });
+
+        (config, q_x)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let (config, q_x) = config;
+        let ec_chip = ECChip::construct(config);
+
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "custom gate",
+            |ecc| {
+                let p = ecc.load_private_point(self.p)?;
+                ecc.ctx().enable_selector(&q_x, p.offset())?;
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_config_accessors() {
+    let k = 5;
+
+    {
+        let circuit = CustomGateTestCircuit {
+            p: Value::known(G1::generator().to_affine()),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: x is not 1
+    {
+        let p = G1::random(test_rng()).to_affine();
+        let circuit = CustomGateTestCircuit { p: Value::known(p) };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+/// `try_configure` over a supported (curve, field) pair, i.e., a curve
+/// y^2 = x^3 + b over the circuit field.
+fn assert_try_configure<C, F>()
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    assert!(ECChip::<C, F>::check_curve().is_ok());
+
+    let mut cs = ConstraintSystem::<F>::default();
+    let config = ECChip::<C, F>::try_configure(&mut cs).unwrap();
+    assert_eq!(config.curve_b, C::b());
+    assert_eq!(config.stats().max_degree, cs.degree());
+}
+
+#[test]
+fn test_try_configure_grumpkin() {
+    assert_try_configure::<G1Affine, Fq>();
+}
+
+#[test]
+fn test_try_configure_pallas() {
+    assert_try_configure::<pallas::Affine, pallas::Base>();
+}
+
+#[test]
+fn test_try_configure_vesta() {
+    assert_try_configure::<vesta::Affine, vesta::Base>();
+}
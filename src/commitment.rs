@@ -0,0 +1,123 @@
+//! Compact public commitments to vectors of points, e.g., the elements of a
+//! Verkle proof.
+//!
+//! A vector (p_0, ..., p_{n-1}) is committed to by its digest
+//! sum_i c^(n-1-i) * p_i for a challenge c, the only point exposed in the
+//! instance:
+//!
+//! ```ignore
+//! let digest = ecc.check_folded_commitment(&mut layouter, &points, &c, 0)?;
+//! // off-circuit, with the low 128 bits of c
+//! let instance = GrumpkinChip::folded_commitment_instances(&points, c_low)?;
+//! ```
+//!
+//! The digest binds the points only if the challenge is drawn once they are
+//! fixed, e.g., a round challenge of `challenges::RoundChallengeConfig` that
+//! absorbs the points, or the digest of a hash chip of the circuit through
+//! `binding::HashToBits`. The fold uses the low 128 bits of the challenge,
+//! split off in-circuit, so a uniform challenge of any size will do.
+//!
+//! A challenge of a later phase is only known in that phase, and so are the
+//! bits it is split into: the chip folding with it must be configured over
+//! advice columns of that phase, e.g., with `ECChip::configure_with_columns`
+//! over columns of `advice_column_in(SecondPhase)`. The points of the first
+//! phase are copied in.
+
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+
+use crate::backend::Curve;
+use crate::backend::CurveAffine;
+use crate::backend::PrimeCurveAffine;
+use crate::backend::PrimeField;
+use crate::AssignedECPoint;
+use crate::ECChip;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// The digest sum_i c^(n-1-i) * p_i of the points, folded with Horner's
+    /// rule, constrained to the rows `row` and `row + 1` of the instance
+    /// column, see `folded_commitment_instances`.
+    ///
+    /// See `fold_commitment` for the challenge.
+    pub fn check_folded_commitment(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        points: &[AssignedECPoint<C, F>],
+        challenge: &AssignedCell<F, F>,
+        row: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let digest = self.fold_commitment(layouter, points, challenge)?;
+        self.expose_point_public(layouter, &digest, row)?;
+        Ok(digest)
+    }
+
+    /// The digest sum_i c^(n-1-i) * p_i of the points, folded with Horner's
+    /// rule, where c is the low 128 bits of the challenge.
+    ///
+    /// The challenge is split into its canonical 128 bits limbs, as by
+    /// `decompose_field_limbs`, and the bits of the low limb drive the muls.
+    /// Each point after the first costs a variable base mul by c and an add,
+    /// in `rows_for_folded_commitment` rows of the `cost` module. Requires the
+    /// add and mul gates.
+    ///
+    /// c must not be 0, and the partial digests times c must not be ±the
+    /// next point, which only a challenge chosen after the points avoids with
+    /// overwhelming probability.
+    pub fn fold_commitment(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        points: &[AssignedECPoint<C, F>],
+        challenge: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let (first, rest) = points
+            .split_first()
+            .ok_or(NativeEccError::InvalidInputLength {
+                expected: 1,
+                actual: 0,
+            })?;
+
+        let config = self.config();
+        let digest = layouter.assign_region(
+            || "folded commitment",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let (bits, _) = self.assign_field_limbs(&mut ctx, config, challenge)?;
+
+                let mut acc = first.clone();
+                for p in rest {
+                    let scaled = self.point_mul_with_bits(&mut ctx, config, &acc, &bits)?;
+                    let scaled = self.copy_point(&mut ctx, config, &scaled)?;
+                    let p = self.copy_point(&mut ctx, config, p)?;
+                    acc = self.conditional_point_add_const(&mut ctx, config, &scaled, &p, true)?;
+                }
+                self.pad(&mut ctx, config)?;
+                Ok(acc)
+            },
+        )?;
+        Ok(digest)
+    }
+
+    /// The instance column of the digest of the points folded with the
+    /// low 128 bits c of the challenge, `field_decompose_u128(&challenge).1`,
+    /// as exposed by `check_folded_commitment`.
+    pub fn folded_commitment_instances(points: &[C], c: u128) -> Result<Vec<F>, NativeEccError> {
+        let c = C::ScalarExt::from_u128(c);
+        let digest = points
+            .iter()
+            .fold(C::identity().to_curve(), |acc, p| acc * c + *p);
+        Self::point_instances(&[digest.to_affine()])
+    }
+}
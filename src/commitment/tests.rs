@@ -0,0 +1,203 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::SecondPhase;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::challenges::RoundChallengeConfig;
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+use crate::util::field_decompose_u128;
+
+#[derive(Default, Debug, Clone)]
+struct FoldedCommitmentTestCircuit {
+    points: Vec<Value<G1Affine>>,
+    challenge: Value<Fq>,
+}
+
+impl Circuit<Fq> for FoldedCommitmentTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            points: vec![Value::unknown(); self.points.len()],
+            challenge: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let instance = meta.instance_column();
+        ECChip::configure_with_instance(meta, instance)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+        let points = self
+            .points
+            .iter()
+            .map(|p| ec_chip.witness_point(&mut layouter, *p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let challenge = ec_chip.assign_scoped(
+            &mut layouter,
+            || "challenge",
+            |ecc| ecc.load_private_field(self.challenge),
+        )?;
+
+        ec_chip.check_folded_commitment(&mut layouter, &points, &challenge, 0)?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_check_folded_commitment() {
+    let k = 11;
+    let mut rng = test_rng();
+    let points = (0..3)
+        .map(|_| G1::random(&mut rng).to_affine())
+        .collect::<Vec<_>>();
+    let c = 0x1234_5678_9abc_def0_0fed_cba9_8765_4321u128;
+
+    let run = |points: &[G1Affine], challenge: Fq, instance: Vec<Fq>| {
+        let circuit = FoldedCommitmentTestCircuit {
+            points: points.iter().copied().map(Value::known).collect(),
+            challenge: Value::known(challenge),
+        };
+        MockProver::run(k, &circuit, vec![instance])
+    };
+
+    let instance = ECChip::<G1Affine, Fq>::folded_commitment_instances(&points, c).unwrap();
+    run(&points, Fq::from_u128(c), instance.clone())
+        .unwrap()
+        .assert_satisfied();
+
+    // a single point is its own digest
+    let single = ECChip::<G1Affine, Fq>::point_instances(&points[..1]).unwrap();
+    run(&points[..1], Fq::from_u128(c), single)
+        .unwrap()
+        .assert_satisfied();
+
+    // error case: the points in another order
+    let swapped = [points[1], points[0], points[2]];
+    assert!(run(&swapped, Fq::from_u128(c), instance.clone())
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // error case: the digest of another challenge
+    assert!(run(&points, Fq::from_u128(c + 1), instance.clone())
+        .unwrap()
+        .verify()
+        .is_err());
+
+    // a challenge over 128 bits folds with its low 128 bits
+    let wide = Fq::from_u128(c) + Fq::from_u128(0xff) * Fq::from_u128(1 << 64).square();
+    run(&points, wide, instance.clone())
+        .unwrap()
+        .assert_satisfied();
+    let random = Fq::random(&mut rng);
+    let low = field_decompose_u128(&random).1;
+    let random_instance =
+        ECChip::<G1Affine, Fq>::folded_commitment_instances(&points, low).unwrap();
+    run(&points, random, random_instance)
+        .unwrap()
+        .assert_satisfied();
+}
+
+/// The points are committed in the first phase, and folded in the second
+/// phase with a round challenge that absorbs them, by a chip over second
+/// phase columns.
+#[derive(Default, Debug, Clone)]
+struct RoundChallengeFoldCircuit {
+    points: Vec<Value<G1Affine>>,
+}
+
+impl Circuit<Fq> for RoundChallengeFoldCircuit {
+    type Config = (
+        ECConfig<G1Affine, Fq>,
+        RoundChallengeConfig,
+        ECConfig<G1Affine, Fq>,
+    );
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            points: vec![Value::unknown(); self.points.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let ecc = ECChip::configure(meta);
+        let challenges = RoundChallengeConfig::configure(meta, &ecc);
+        let a = meta.advice_column_in(SecondPhase);
+        let b = meta.advice_column_in(SecondPhase);
+        let constant = meta.fixed_column();
+        let folding = ECChip::configure_with_columns(meta, a, b, constant, None);
+        (ecc, challenges, folding)
+    }
+
+    fn synthesize(
+        &self,
+        (ecc, challenges, folding): Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(ecc);
+        let points = self
+            .points
+            .iter()
+            .map(|p| ec_chip.witness_point(&mut layouter, *p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let cs = challenges.derive(&mut layouter, &[points.as_slice()])?;
+
+        let folding = ECChip::construct(folding);
+        let digest = folding.fold_commitment(&mut layouter, &points, &cs[0])?;
+
+        // the digest of the low 128 bits of the challenge, once it is known
+        let values: Value<Vec<G1Affine>> = self.points.iter().copied().collect();
+        let expected = cs[0].value().zip(values).map(|(c, points)| {
+            let c = Fr::from_u128(field_decompose_u128(c).1);
+            points
+                .iter()
+                .fold(G1::identity(), |acc, p| acc * c + *p)
+                .to_affine()
+        });
+        digest
+            .value()
+            .zip(expected)
+            .assert_if_known(|(digest, expected)| digest == expected);
+        Ok(())
+    }
+}
+
+// the inverses and slopes of the `complete-addition` feature are in a first
+// phase column of the chip, which cannot depend on the challenge
+#[cfg(not(feature = "complete-addition"))]
+#[test]
+fn test_fold_commitment_round_challenge() {
+    let k = 12;
+    let mut rng = test_rng();
+    let points = (0..3)
+        .map(|_| Value::known(G1::random(&mut rng).to_affine()))
+        .collect::<Vec<_>>();
+
+    let circuit = RoundChallengeFoldCircuit { points };
+    let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+}
@@ -1,15 +1,30 @@
-use std::marker::PhantomData;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
 
-use halo2_proofs::arithmetic::Field;
-use halo2_proofs::halo2curves::ff::PrimeField;
-use halo2_proofs::halo2curves::CurveAffine;
 use halo2_proofs::plonk::Advice;
 use halo2_proofs::plonk::Column;
 use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::Fixed;
+use halo2_proofs::plonk::Instance;
 use halo2_proofs::plonk::Selector;
 use halo2_proofs::plonk::VirtualCells;
 use halo2_proofs::poly::Rotation;
 
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::PrimeField;
+use crate::NativeEccError;
+
+/// The version of the layout of the chip, i.e., of its columns, of its gates and
+/// of the rows used by the ops; bumped whenever a release changes the layout.
+pub const LAYOUT_VERSION: u32 = 13;
+
+/// The value of `q_ec_op` selecting the conditional ec add.
+pub(crate) const EC_ADD: u64 = 1;
+/// The value of `q_ec_op` selecting the ec double.
+pub(crate) const EC_DOUBLE: u64 = 2;
+
 /// Three advices and two additions
 #[derive(Clone, Debug)]
 pub struct ECConfig<C, F>
@@ -23,69 +38,547 @@ where
     pub(crate) a: Column<Advice>,
     pub(crate) b: Column<Advice>,
 
+    // public inputs, if any
+    pub(crate) instance: Option<Column<Instance>>,
+
     // selectors
-    pub(crate) q_ec_enable: Selector, // ec is enabled
-    pub(crate) q1: Selector,          // ec conditional add
-    pub(crate) q2: Selector,          // ec double
-    pub(crate) q3: Selector,          // ec on curve
+    pub(crate) q_ec_enable: Selector,  // ec is enabled
+    pub(crate) q1: Selector,           // fixed base add, or partial bit decomposition
+    pub(crate) q2: Selector,           // field add, or cswap
+    pub(crate) q3: Selector,           // ec on curve, or field mul
+    pub(crate) q_ladder: Selector,     // x-only ladder step
+    pub(crate) q_ec_sub: Selector,     // ec conditional sub
+    pub(crate) q_ec_op: Column<Fixed>, // ec conditional add if 1, ec double if 2
+
+    // the coordinates of the multiples 2^i * generator, for the fixed base add
+    pub(crate) base_x: Column<Fixed>,
+    pub(crate) base_y: Column<Fixed>,
+
+    // with the `complete-addition` feature: the slopes of the adds and
+    // doubles, and the flag of the fixed base adds
+    #[cfg(feature = "complete-addition")]
+    pub(crate) slope: Column<Advice>,
+    #[cfg(feature = "complete-addition")]
+    pub(crate) q_fixed_base: Column<Fixed>,
+
+    // the constant b of the curve equation y^2 = x^3 + b, computed once at
+    // configure time and shared by the gate builders
+    pub(crate) curve_b: F,
+
+    // the gates the chip is configured with
+    pub(crate) gates: EnabledGates,
+    // the largest degree of the configured gates
+    pub(crate) degree: usize,
+    // the names of the polynomial constraints of the configured gates
+    pub(crate) constraint_names: Vec<String>,
 
     pub(crate) _phantom: PhantomData<C>,
 }
 
+/// The gates of the chip that are part of the constraint system.
+///
+/// All gates are enabled by default; see `ECConfigBuilder` to leave some out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EnabledGates {
+    pub ec_add: bool,
+    pub ec_double: bool,
+    pub fixed_base_add: bool,
+    pub on_curve: bool,
+    pub partial_bit_decomp: bool,
+    pub add: bool,
+    pub mul: bool,
+    pub cswap: bool,
+    pub x_only_ladder: bool,
+    pub ec_sub: bool,
+}
+
+impl Default for EnabledGates {
+    fn default() -> Self {
+        Self {
+            ec_add: true,
+            ec_double: true,
+            fixed_base_add: true,
+            on_curve: true,
+            partial_bit_decomp: true,
+            add: true,
+            mul: true,
+            cswap: true,
+            x_only_ladder: true,
+            ec_sub: true,
+        }
+    }
+}
+
+impl EnabledGates {
+    /// No gate enabled.
+    pub fn none() -> Self {
+        Self {
+            ec_add: false,
+            ec_double: false,
+            fixed_base_add: false,
+            on_curve: false,
+            partial_bit_decomp: false,
+            add: false,
+            mul: false,
+            cswap: false,
+            x_only_ladder: false,
+            ec_sub: false,
+        }
+    }
+
+    /// The flags, in the order of the fields.
+    pub(crate) fn to_array(self) -> [bool; 10] {
+        [
+            self.ec_add,
+            self.ec_double,
+            self.fixed_base_add,
+            self.on_curve,
+            self.partial_bit_decomp,
+            self.add,
+            self.mul,
+            self.cswap,
+            self.x_only_ladder,
+            self.ec_sub,
+        ]
+    }
+}
+
+/// The share of the constraint system used by the chip.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChipStats {
+    /// Selectors allocated by the chip; they are complex selectors and are
+    /// not combined into fixed columns.
+    pub selectors: usize,
+    pub advice_columns: usize,
+    pub fixed_columns: usize,
+    pub instance_columns: usize,
+    /// The largest degree of the configured gates, selectors included.
+    pub max_degree: usize,
+    /// The number of named constraints, one per equation of the configured gates.
+    pub constraints: usize,
+    /// The chip does not use lookups.
+    pub lookups: usize,
+}
+
+impl<C, F> ECConfig<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    /// The gates the chip is configured with.
+    pub fn gates(&self) -> EnabledGates {
+        self.gates
+    }
+
+    /// The advice column of the x coordinates, and of the first operand of the field ops.
+    ///
+    /// Custom gates may query it, e.g., to constrain the x coordinate of a point
+    /// at `AssignedECPoint::offset`.
+    pub fn a(&self) -> Column<Advice> {
+        self.a
+    }
+
+    /// The advice column of the y coordinates, and of the second operand of the field ops.
+    pub fn b(&self) -> Column<Advice> {
+        self.b
+    }
+
+    /// The instance column of the public inputs, if any.
+    pub fn instance(&self) -> Option<Column<Instance>> {
+        self.instance
+    }
+
+    /// The selector of the ec ops; the ops of `q1`, `q2` and `q3` are ec ops
+    /// when it is enabled, and field ops otherwise.
+    pub fn q_ec_enable(&self) -> Selector {
+        self.q_ec_enable
+    }
+
+    /// The selector of the fixed base add, or of the partial bit decomposition.
+    pub fn q1(&self) -> Selector {
+        self.q1
+    }
+
+    /// The selector of the field add, or of the cswap when `q_ec_enable` is enabled.
+    pub fn q2(&self) -> Selector {
+        self.q2
+    }
+
+    /// The selector of the on curve check, or of the field mul.
+    pub fn q3(&self) -> Selector {
+        self.q3
+    }
+
+    /// The selector of the steps of the x-only ladder.
+    pub fn q_ladder(&self) -> Selector {
+        self.q_ladder
+    }
+
+    /// The selector of the conditional ec sub.
+    pub fn q_ec_sub(&self) -> Selector {
+        self.q_ec_sub
+    }
+
+    /// The fixed column selecting the conditional ec add (1) or the ec double (2).
+    pub fn q_ec_op(&self) -> Column<Fixed> {
+        self.q_ec_op
+    }
+
+    /// The columns, selectors and degree of the chip, to budget the constraint
+    /// system of a larger circuit.
+    ///
+    /// Columns shared through `ECChip::configure_with_columns` are counted as well.
+    pub fn stats(&self) -> ChipStats {
+        ChipStats {
+            selectors: 6,
+            // and the slopes of the `complete-addition` feature
+            advice_columns: 2 + usize::from(cfg!(feature = "complete-addition")),
+            // the constants, `q_ec_op` and the coordinates of the fixed bases,
+            // and the flag of the fixed base adds of the `complete-addition` feature
+            fixed_columns: 4 + usize::from(cfg!(feature = "complete-addition")),
+            instance_columns: usize::from(self.instance.is_some()),
+            max_degree: self.degree,
+            constraints: self.constraint_names.len(),
+            lookups: 0,
+        }
+    }
+
+    /// The names of the constraints of the configured gates, e.g., "ec add: line",
+    /// as reported by the MockProver; see `testing::failed_constraints`.
+    ///
+    /// The names are stable within a `LAYOUT_VERSION`.
+    pub fn constraint_names(&self) -> &[String] {
+        &self.constraint_names
+    }
+
+    /// A fingerprint of the layout of the chip: `LAYOUT_VERSION`, the configured
+    /// gates, their degree and rotations, and whether there is an instance column.
+    ///
+    /// Proving keys cached for a fingerprint are not valid for another one. The
+    /// hash is FNV-1a, so that it is stable across platforms and releases.
+    pub fn layout_hash(&self) -> u64 {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        LAYOUT_VERSION
+            .to_le_bytes()
+            .into_iter()
+            .chain(self.gates.to_array().map(u8::from))
+            .chain((self.degree as u64).to_le_bytes())
+            .chain((self.max_rotation() as u64).to_le_bytes())
+            .chain([u8::from(self.instance.is_some())])
+            .chain([u8::from(cfg!(feature = "complete-addition"))])
+            .fold(FNV_OFFSET, |hash, byte| {
+                (hash ^ u64::from(byte)).wrapping_mul(FNV_PRIME)
+            })
+    }
+
+    /// The largest rotation queried by the configured gates.
+    ///
+    /// All constraints belong to one gate, so every enabled selector queries up
+    /// to this rotation.
+    pub fn max_rotation(&self) -> usize {
+        let gates = self.gates;
+        if gates.cswap {
+            4
+        } else if gates.ec_add || gates.ec_sub || gates.x_only_ladder {
+            3
+        } else if gates.fixed_base_add || gates.partial_bit_decomp {
+            2
+        } else if gates.ec_double || gates.add || gates.mul {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Errors if an op relies on a gate that is not configured.
+    pub(crate) fn require_gate(
+        &self,
+        enabled: bool,
+        gate: &'static str,
+    ) -> Result<(), NativeEccError> {
+        if enabled {
+            Ok(())
+        } else {
+            Err(NativeEccError::GateDisabled(gate))
+        }
+    }
+}
+
 impl<C, F> ECConfig<C, F>
 where
     C: CurveAffine<Base = F>,
     F: PrimeField,
 {
-    pub(crate) fn conditional_ec_add_gate(&self, meta: &mut VirtualCells<F>) -> Expression<F> {
-        let one = Expression::Constant(F::ONE);
-        // FIXME: currently hardcoded for Grumpkin curve
-        let curve_param_b = -F::from(17);
-        let curve_param_b_expr = Expression::Constant(curve_param_b);
+    pub(crate) fn conditional_ec_add_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let a0 = meta.query_advice(self.a, Rotation::cur());
+        let b0 = meta.query_advice(self.b, Rotation::cur());
+        let a1 = meta.query_advice(self.a, Rotation::next());
+        let b1 = meta.query_advice(self.b, Rotation::next());
+        let condition = meta.query_advice(self.a, Rotation(2));
+        let inv = meta.query_advice(self.b, Rotation(2));
+        let a2 = meta.query_advice(self.a, Rotation(3));
+        let b2 = meta.query_advice(self.b, Rotation(3));
+
+        // | a  | b   |
+        // ------------
+        // | x1 | y1  |
+        // | x2 | y2  |
+        // | c  | inv |
+        // | x3 | y3  |
+        self.conditional_add_expr((a0, b0), (a1, b1), condition, inv, (a2, b2))
+    }
 
+    /// The rows of the conditional ec add, with the sign of y2 folded into the
+    /// line: (x3, y3) = (x1, y1) - (x2, y2) if the condition is 1, without
+    /// a row for -(x2, y2).
+    pub(crate) fn conditional_ec_sub_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
         let a0 = meta.query_advice(self.a, Rotation::cur());
         let b0 = meta.query_advice(self.b, Rotation::cur());
         let a1 = meta.query_advice(self.a, Rotation::next());
         let b1 = meta.query_advice(self.b, Rotation::next());
         let condition = meta.query_advice(self.a, Rotation(2));
+        let inv = meta.query_advice(self.b, Rotation(2));
         let a2 = meta.query_advice(self.a, Rotation(3));
         let b2 = meta.query_advice(self.b, Rotation(3));
 
+        // | a  | b   |
+        // ------------
+        // | x1 | y1  |
+        // | x2 | y2  |
+        // | c  | inv |
+        // | x3 | y3  |
+        self.conditional_add_expr((a0, b0), (a1, -b1), condition, inv, (a2, b2))
+    }
+
+    /// Conditional add of the fixed base (x2, y2) read from the fixed columns;
+    /// the result is the next accumulator, so each step takes 2 rows.
+    pub(crate) fn fixed_base_add_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let a0 = meta.query_advice(self.a, Rotation::cur());
+        let b0 = meta.query_advice(self.b, Rotation::cur());
+        let x2 = meta.query_fixed(self.base_x, Rotation::cur());
+        let y2 = meta.query_fixed(self.base_y, Rotation::cur());
+        let condition = meta.query_advice(self.a, Rotation::next());
+        let inv = meta.query_advice(self.b, Rotation::next());
+        let a2 = meta.query_advice(self.a, Rotation(2));
+        let b2 = meta.query_advice(self.b, Rotation(2));
+
+        // | a  | b   | base_x | base_y |
+        // ------------------------------
+        // | x1 | y1  |   x2   |   y2   |
+        // | c  | inv |        |        |
+        // | x3 | y3  |        |        |
+        self.conditional_add_expr((a0, b0), (x2, y2), condition, inv, (a2, b2))
+    }
+
+    /// The constraints of the `complete-addition` feature for the ec add, with
+    /// the slope witnessed in the `slope` column of the row of (x2, y2); see
+    /// `complete_add_expr`.
+    #[cfg(feature = "complete-addition")]
+    pub(crate) fn complete_ec_add_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let [x1, y1, x2, y2, condition, lambda, x3, y3] = self.query_complete_add(meta);
+        self.complete_add_expr((x1, y1), (x2, y2), condition, lambda, (x3, y3))
+    }
+
+    /// `complete_ec_add_gate` for the ec sub, with the sign of y2 folded in.
+    #[cfg(feature = "complete-addition")]
+    pub(crate) fn complete_ec_sub_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let [x1, y1, x2, y2, condition, lambda, x3, y3] = self.query_complete_add(meta);
+        self.complete_add_expr((x1, y1), (x2, -y2), condition, lambda, (x3, y3))
+    }
+
+    // | a  | b   | slope |
+    // --------------------
+    // | x1 | y1  |       |
+    // | x2 | y2  |   λ   |
+    // | c  | inv |       |
+    // | x3 | y3  |       |
+    #[cfg(feature = "complete-addition")]
+    fn query_complete_add(&self, meta: &mut VirtualCells<F>) -> [Expression<F>; 8] {
+        [
+            meta.query_advice(self.a, Rotation::cur()),
+            meta.query_advice(self.b, Rotation::cur()),
+            meta.query_advice(self.a, Rotation::next()),
+            meta.query_advice(self.b, Rotation::next()),
+            meta.query_advice(self.a, Rotation(2)),
+            meta.query_advice(self.slope, Rotation::next()),
+            meta.query_advice(self.a, Rotation(3)),
+            meta.query_advice(self.b, Rotation(3)),
+        ]
+    }
+
+    /// `complete_ec_add_gate` for the fixed base add, with the slope
+    /// witnessed in the `slope` column of the row of (x1, y1).
+    #[cfg(feature = "complete-addition")]
+    pub(crate) fn complete_fixed_base_add_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        // | a  | b   | slope | base_x | base_y |
+        // --------------------------------------
+        // | x1 | y1  |   λ   |   x2   |   y2   |
+        // | c  | inv |       |        |        |
+        // | x3 | y3  |       |        |        |
+        let x1 = meta.query_advice(self.a, Rotation::cur());
+        let y1 = meta.query_advice(self.b, Rotation::cur());
+        let lambda = meta.query_advice(self.slope, Rotation::cur());
+        let x2 = meta.query_fixed(self.base_x, Rotation::cur());
+        let y2 = meta.query_fixed(self.base_y, Rotation::cur());
+        let condition = meta.query_advice(self.a, Rotation::next());
+        let x3 = meta.query_advice(self.a, Rotation(2));
+        let y3 = meta.query_advice(self.b, Rotation(2));
+        self.complete_add_expr((x1, y1), (x2, y2), condition, lambda, (x3, y3))
+    }
+
+    /// If the condition is 1, (x3, y3) = (x1, y1) + (x2, y2) by the affine
+    /// formulas, with the slope λ witnessed:
+    /// - y2 - y1 = λ * (x2 - x1), which determines λ as x1 != x2 is checked by
+    ///   `conditional_add_expr`
+    /// - x3 = λ^2 - x1 - x2 and y3 = λ * (x1 - x3) - y1
+    ///
+    /// The line of `conditional_add_expr` alone does not determine the sum: for
+    /// any operands, -(x1, y1) and -(x2, y2) also satisfy it.
+    #[cfg(feature = "complete-addition")]
+    fn complete_add_expr(
+        &self,
+        (x1, y1): (Expression<F>, Expression<F>),
+        (x2, y2): (Expression<F>, Expression<F>),
+        condition: Expression<F>,
+        lambda: Expression<F>,
+        (x3, y3): (Expression<F>, Expression<F>),
+    ) -> Vec<(&'static str, Expression<F>)> {
+        vec![
+            (
+                "slope",
+                condition.clone() * (y2 - y1.clone() - lambda.clone() * (x2.clone() - x1.clone())),
+            ),
+            (
+                "x3",
+                condition.clone()
+                    * (x3.clone() - (lambda.clone() * lambda.clone() - x1.clone() - x2)),
+            ),
+            ("y3", condition * (y3 - (lambda * (x1 - x3) - y1))),
+        ]
+    }
+
+    /// The constraints of the `complete-addition` feature for the ec double,
+    /// with the slope λ witnessed in the `slope` column of the row of (x1, y1):
+    /// - 2 * y1 * λ = 3 * x1^2
+    /// - x3 = λ^2 - 2 * x1 and y3 = λ * (x1 - x3) - y1
+    ///
+    /// The tangent of `ec_double_gate` alone is also satisfied by -(x1, y1).
+    /// The curve has no point with y = 0, so the slope is always defined.
+    #[cfg(feature = "complete-addition")]
+    pub(crate) fn complete_ec_double_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let two = Expression::Constant(F::from(2));
+        let three = Expression::Constant(F::from(3));
+
+        // | a  | b  | slope |
+        // -------------------
+        // | x1 | y1 |   λ   |
+        // | x3 | y3 |       |
+        let x1 = meta.query_advice(self.a, Rotation::cur());
+        let y1 = meta.query_advice(self.b, Rotation::cur());
+        let lambda = meta.query_advice(self.slope, Rotation::cur());
+        let x3 = meta.query_advice(self.a, Rotation::next());
+        let y3 = meta.query_advice(self.b, Rotation::next());
+
+        vec![
+            (
+                "slope",
+                two.clone() * y1.clone() * lambda.clone() - three * x1.clone() * x1.clone(),
+            ),
+            (
+                "x3",
+                x3.clone() - (lambda.clone() * lambda.clone() - two * x1.clone()),
+            ),
+            ("y3", y3.clone() - (lambda * (x1 - x3) - y1)),
+        ]
+    }
+
+    /// (x3, y3) = (x1, y1) + (x2, y2) if the condition is 1, (x1, y1) if it is 0,
+    /// the condition is a bit, and (x3, y3) is on curve.
+    ///
+    /// If the condition is 1, x1 != x2 via the witnessed inverse of x2 - x1,
+    /// i.e., the operands are not p and ±p, whose sum the line does not
+    /// determine or which has no (x, y) representation.
+    ///
+    /// Each equation is a constraint of its own, so that their residuals
+    /// cannot cancel each other.
+    fn conditional_add_expr(
+        &self,
+        (x1, y1): (Expression<F>, Expression<F>),
+        (x2, y2): (Expression<F>, Expression<F>),
+        condition: Expression<F>,
+        inv: Expression<F>,
+        (x3, y3): (Expression<F>, Expression<F>),
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let one = Expression::Constant(F::ONE);
+        let curve_param_b_expr = Expression::Constant(self.curve_b);
+
         //      (x2-x1)/(y2-y1) = (x3-x1)/(-y3-y1)
         // =>   (x3-x1)(y2-y1) + (x2-x1)(y3+y1) = 0
         //
         // we do not want to open up the above equations
         // a fully expanded one will require 6 muls while the current
         // one only requires 2 muls
-
-        // | a  | b  |
-        // -----------
-        // | x1 | y1 |
-        // | x2 | y2 |
-        // | c  |    |
-        // | x3 | y3 |
-        let add = (a2.clone() - a0.clone()) * (b1 - b0.clone())
-            + (a1 - a0.clone()) * (b2.clone() + b0.clone());
+        let add = (x3.clone() - x1.clone()) * (y2 - y1.clone())
+            + (x2.clone() - x1.clone()) * (y3.clone() + y1.clone());
 
         // Given (x1, y1), (x2, y2)
         // if condition is true, we return (x1, y1) + (x2, y2)
         // else we return (x1, y1)
-        condition.clone() * add
-            + (one.clone() - condition.clone()) * (a2.clone() - a0)
-            + (one - condition) * (b2.clone() - b0)
+        vec![
+            (
+                "condition binary",
+                condition.clone() * (one.clone() - condition.clone()),
+            ),
+            ("line", condition.clone() * add),
+            (
+                "distinct x",
+                condition.clone() * ((x2 - x1.clone()) * inv - one.clone()),
+            ),
+            (
+                "copy x",
+                (one.clone() - condition.clone()) * (x3.clone() - x1),
+            ),
+            ("copy y", (one - condition) * (y3.clone() - y1)),
             // enforce the result is on curve
-            + a2.clone() * a2.clone() * a2
-            - b2.clone() * b2
-            + curve_param_b_expr
+            (
+                "on curve",
+                x3.clone() * x3.clone() * x3 - y3.clone() * y3 + curve_param_b_expr,
+            ),
+        ]
     }
 
-    /// (x1, y1) and (x3, -y3) are on a tangential line of the curve
-    pub(crate) fn ec_double_gate(&self, meta: &mut VirtualCells<F>) -> Expression<F> {
+    /// (x1, y1) and (x3, -y3) are on a tangential line of the curve,
+    /// and (x3, y3) is on curve; two independent constraints.
+    pub(crate) fn ec_double_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
         let two = Expression::Constant(F::from(2));
         let three = Expression::Constant(F::from(3));
-        // FIXME: currently hardcoded for Grumpkin curve
-        let curve_param_b = -F::from(17);
-        let curve_param_b_expr = Expression::Constant(curve_param_b);
+        let curve_param_b_expr = Expression::Constant(self.curve_b);
 
         let a0 = meta.query_advice(self.a, Rotation::cur());
         let b0 = meta.query_advice(self.b, Rotation::cur());
@@ -100,29 +593,152 @@ where
         // | x1 | y1 |
         // | x3 | y3 |
 
-        two * b0.clone() * (b1.clone() + b0) + (three * a0.clone() * a0.clone()) * (a1.clone() - a0)
-        // enforce the result is on curve
-        + a1.clone() * a1.clone() * a1
-            - b1.clone() * b1
-            + curve_param_b_expr
+        vec![
+            (
+                "tangent",
+                two * b0.clone() * (b1.clone() + b0)
+                    + (three * a0.clone() * a0.clone()) * (a1.clone() - a0),
+            ),
+            // enforce the result is on curve
+            (
+                "on curve",
+                a1.clone() * a1.clone() * a1 - b1.clone() * b1 + curve_param_b_expr,
+            ),
+        ]
+    }
+
+    /// (x3, y3) = (x2, y2) and (x4, y4) = (x1, y1) if the condition is 1,
+    /// (x3, y3) = (x1, y1) and (x4, y4) = (x2, y2) if it is 0, and the
+    /// condition is a bit.
+    ///
+    /// The second output is the sum of the inputs minus the first one, so
+    /// that only the first output has a degree 2 term.
+    pub(crate) fn cswap_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let one = Expression::Constant(F::ONE);
+
+        let condition = meta.query_advice(self.a, Rotation::cur());
+        let x1 = meta.query_advice(self.a, Rotation::next());
+        let y1 = meta.query_advice(self.b, Rotation::next());
+        let x2 = meta.query_advice(self.a, Rotation(2));
+        let y2 = meta.query_advice(self.b, Rotation(2));
+        let x3 = meta.query_advice(self.a, Rotation(3));
+        let y3 = meta.query_advice(self.b, Rotation(3));
+        let x4 = meta.query_advice(self.a, Rotation(4));
+        let y4 = meta.query_advice(self.b, Rotation(4));
+
+        // | a  | b  |
+        // -----------
+        // | c  | 0  |
+        // | x1 | y1 |
+        // | x2 | y2 |
+        // | x3 | y3 |
+        // | x4 | y4 |
+        vec![
+            (
+                "condition binary",
+                condition.clone() * (one - condition.clone()),
+            ),
+            (
+                "swap x",
+                x3.clone() - x1.clone() - condition.clone() * (x2.clone() - x1.clone()),
+            ),
+            (
+                "swap y",
+                y3.clone() - y1.clone() - condition * (y2.clone() - y1.clone()),
+            ),
+            ("sum x", x3 + x4 - x1 - x2),
+            ("sum y", y3 + y4 - y1 - y2),
+        ]
+    }
+
+    /// A step of the x-only ladder, from (x0, x1), the x coordinates of the
+    /// accumulators r0 and r1 with r1 - r0 = p, to (x0', x1'):
+    /// - u is x1 if the condition is 1, x0 if it is 0, and dbl = x(2u)
+    /// - dbl is x1' if the condition is 1, x0' if it is 0, and the other
+    ///   output is x(r0 + r1)
+    ///
+    /// With a = 0, x(2u) = (u^4 - 8bu) / 4(u^3 + b), and the differential
+    /// addition x(r0 + r1) + x(r1 - r0) = (2 x0 x1 (x0 + x1) + 4b) / (x0 - x1)^2,
+    /// where x(r1 - r0) = xp.
+    pub(crate) fn x_only_ladder_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
+        let one = Expression::Constant(F::ONE);
+        let two = Expression::Constant(F::from(2));
+        let four = Expression::Constant(F::from(4));
+        let eight = Expression::Constant(F::from(8));
+        let curve_param_b_expr = Expression::Constant(self.curve_b);
+
+        let x0 = meta.query_advice(self.a, Rotation::cur());
+        let x1 = meta.query_advice(self.b, Rotation::cur());
+        let condition = meta.query_advice(self.a, Rotation::next());
+        let xp = meta.query_advice(self.b, Rotation::next());
+        let u = meta.query_advice(self.a, Rotation(2));
+        let dbl = meta.query_advice(self.b, Rotation(2));
+        let x0_next = meta.query_advice(self.a, Rotation(3));
+        let x1_next = meta.query_advice(self.b, Rotation(3));
+
+        // | a   | b   |
+        // -------------
+        // | x0  | x1  |
+        // | c   | xp  |
+        // | u   | dbl |
+        // | x0' | x1' |
+        let add = x0_next.clone() + x1_next.clone() - dbl.clone();
+        let u_square = u.clone() * u.clone();
+        let x_diff = x0.clone() - x1.clone();
+        vec![
+            (
+                "condition binary",
+                condition.clone() * (one - condition.clone()),
+            ),
+            (
+                "select",
+                u.clone() - x0.clone() - condition.clone() * (x1.clone() - x0.clone()),
+            ),
+            (
+                "double",
+                four.clone()
+                    * dbl.clone()
+                    * (u_square.clone() * u.clone() + curve_param_b_expr.clone())
+                    - (u_square.clone() * u_square - eight * curve_param_b_expr.clone() * u),
+            ),
+            (
+                "select double",
+                dbl - x0_next.clone() - condition * (x1_next - x0_next),
+            ),
+            (
+                "add",
+                (add + xp) * x_diff.clone() * x_diff
+                    - two * x0.clone() * x1.clone() * (x0 + x1)
+                    - four * curve_param_b_expr,
+            ),
+        ]
     }
 
     /// (x1, y1) is on curve
     pub(crate) fn on_curve_gate(&self, meta: &mut VirtualCells<F>) -> Expression<F> {
-        // FIXME: currently hardcoded for Grumpkin curve
-        let curve_param_b = -F::from(17);
-        let curve_param_b_expr = Expression::Constant(curve_param_b);
+        let curve_param_b_expr = Expression::Constant(self.curve_b);
 
         let a0 = meta.query_advice(self.a, Rotation::cur());
         let b0 = meta.query_advice(self.b, Rotation::cur());
-        // (1 - q1) * q2 * (a^3 - b^2 - 17) == c
+        // a^3 - b^2 + curve_b == 0
         a0.clone() * a0.clone() * a0 - b0.clone() * b0 + curve_param_b_expr
     }
 
     /// partial bit decom
     /// - y3 = x1 + 2y1 + 4x2 + 8y2 + 16x3
     /// - x1, y1, x2, y2 are all binary
-    pub(crate) fn partial_bit_decom_gate(&self, meta: &mut VirtualCells<F>) -> Expression<F> {
+    ///
+    /// as five independent constraints.
+    pub(crate) fn partial_bit_decom_gate(
+        &self,
+        meta: &mut VirtualCells<F>,
+    ) -> Vec<(&'static str, Expression<F>)> {
         let one = Expression::Constant(F::ONE);
         let two = Expression::Constant(F::from(2));
         let four = Expression::Constant(F::from(4));
@@ -136,13 +752,23 @@ where
         let a2 = meta.query_advice(self.a, Rotation(2));
         let b2 = meta.query_advice(self.b, Rotation(2));
 
-        // y3 = x1 + 2y1 + 4x2 + 8y2 + 16x3
-        a0.clone() + two * b0.clone() + four * a1.clone() + eight * b1.clone() + sixteen * a2 - b2
-        // x1, y1, x2, y2 are all binary
-            + a0.clone() * (one.clone() - a0)
-            + b0.clone() * (one.clone() - b0)
-            + a1.clone() * (one.clone() - a1)
-            + b1.clone() * (one - b1)
+        vec![
+            // y3 = x1 + 2y1 + 4x2 + 8y2 + 16x3
+            (
+                "recompose",
+                a0.clone()
+                    + two * b0.clone()
+                    + four * a1.clone()
+                    + eight * b1.clone()
+                    + sixteen * a2
+                    - b2,
+            ),
+            // x1, y1, x2, y2 are all binary
+            ("x1 binary", a0.clone() * (one.clone() - a0)),
+            ("y1 binary", b0.clone() * (one.clone() - b0)),
+            ("x2 binary", a1.clone() * (one.clone() - a1)),
+            ("y2 binary", b1.clone() * (one - b1)),
+        ]
     }
 
     /// additional gate
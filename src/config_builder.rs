@@ -0,0 +1,194 @@
+use core::marker::PhantomData;
+
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Fixed;
+use halo2_proofs::plonk::Instance;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::config::EnabledGates;
+use crate::ECChip;
+use crate::ECConfig;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// Builds an `ECConfig` with a subset of the gates.
+///
+/// Leaving out the unused gates lowers the degree of the chip's polynomial,
+/// e.g., a circuit that only adds points does not need the decomposition gate.
+/// Ops relying on a gate that is left out return `NativeEccError::GateDisabled`.
+#[derive(Clone, Debug)]
+pub struct ECConfigBuilder<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    gates: EnabledGates,
+    columns: Option<(Column<Advice>, Column<Advice>, Column<Fixed>)>,
+    instance: Option<Column<Instance>>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C, F> Default for ECConfigBuilder<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C, F> ECConfigBuilder<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    /// A builder with all gates enabled.
+    pub fn new() -> Self {
+        Self {
+            gates: EnabledGates::default(),
+            columns: None,
+            instance: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// A builder with no gate enabled.
+    pub fn empty() -> Self {
+        Self {
+            gates: EnabledGates::none(),
+            ..Self::new()
+        }
+    }
+
+    /// Enables or disables the conditional ec add gate.
+    pub fn ec_add(mut self, enable: bool) -> Self {
+        self.gates.ec_add = enable;
+        self
+    }
+
+    /// Enables or disables the conditional ec sub gate.
+    pub fn ec_sub(mut self, enable: bool) -> Self {
+        self.gates.ec_sub = enable;
+        self
+    }
+
+    /// Enables or disables the ec double gate.
+    pub fn ec_double(mut self, enable: bool) -> Self {
+        self.gates.ec_double = enable;
+        self
+    }
+
+    /// Enables or disables the fixed base add gate.
+    pub fn fixed_base_add(mut self, enable: bool) -> Self {
+        self.gates.fixed_base_add = enable;
+        self
+    }
+
+    /// Enables or disables the on curve gate.
+    pub fn on_curve(mut self, enable: bool) -> Self {
+        self.gates.on_curve = enable;
+        self
+    }
+
+    /// Enables or disables the partial bit decomposition gate.
+    pub fn partial_bit_decomp(mut self, enable: bool) -> Self {
+        self.gates.partial_bit_decomp = enable;
+        self
+    }
+
+    /// Enables or disables the field add gate.
+    pub fn add(mut self, enable: bool) -> Self {
+        self.gates.add = enable;
+        self
+    }
+
+    /// Enables or disables the field mul gate.
+    pub fn mul(mut self, enable: bool) -> Self {
+        self.gates.mul = enable;
+        self
+    }
+
+    /// Enables or disables the cswap gate.
+    pub fn cswap(mut self, enable: bool) -> Self {
+        self.gates.cswap = enable;
+        self
+    }
+
+    /// Enables or disables the x-only ladder gate.
+    pub fn x_only_ladder(mut self, enable: bool) -> Self {
+        self.gates.x_only_ladder = enable;
+        self
+    }
+
+    /// Enables the gates used by `point_mul`.
+    pub fn point_mul(self) -> Self {
+        self.ec_add(true)
+            .ec_double(true)
+            .on_curve(true)
+            .partial_bit_decomp(true)
+    }
+
+    /// Enables the gates used by `generator_mul`.
+    pub fn generator_mul(self) -> Self {
+        self.ec_add(true)
+            .fixed_base_add(true)
+            .partial_bit_decomp(true)
+    }
+
+    /// Enables the gates used by `x_only_point_mul`; `x_only_point_mul_with_y`
+    /// also needs the add and mul gates.
+    pub fn x_only_point_mul(self) -> Self {
+        self.x_only_ladder(true)
+            .ec_double(true)
+            .on_curve(true)
+            .partial_bit_decomp(true)
+    }
+
+    /// Uses columns allocated by the caller, see `ECChip::configure_with_columns`.
+    pub fn columns(
+        mut self,
+        a: Column<Advice>,
+        b: Column<Advice>,
+        constant: Column<Fixed>,
+    ) -> Self {
+        self.columns = Some((a, b, constant));
+        self
+    }
+
+    /// Uses an instance column for the public inputs.
+    pub fn instance(mut self, instance: Column<Instance>) -> Self {
+        self.instance = Some(instance);
+        self
+    }
+
+    /// `configure`, returning `NativeEccError::UnsupportedCurve` if C does not
+    /// have the shape the gates are written for.
+    pub fn try_configure(
+        self,
+        meta: &mut ConstraintSystem<F>,
+    ) -> Result<ECConfig<C, F>, NativeEccError> {
+        ECChip::<C, F>::check_curve()?;
+        let (a, b, constant) = self.columns.unwrap_or_else(|| {
+            (
+                meta.advice_column(),
+                meta.advice_column(),
+                meta.fixed_column(),
+            )
+        });
+        ECChip::configure_with_gates(meta, a, b, constant, self.instance, self.gates)
+    }
+
+    /// Configures the chip, allocating the columns that were not provided.
+    ///
+    /// Panics if C does not pass `ECChip::check_curve`, see `try_configure`.
+    pub fn configure(self, meta: &mut ConstraintSystem<F>) -> ECConfig<C, F> {
+        self.try_configure(meta)
+            .expect("the gates are written for y^2 = x^3 + b")
+    }
+}
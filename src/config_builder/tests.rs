@@ -0,0 +1,182 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::CurveAffine;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::config_builder::ECConfigBuilder;
+use crate::NativeEccError;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct ArithOnlyTestCircuit {
+    f1: Value<Fq>,
+    f2: Value<Fq>,
+    f3: Value<Fq>, // f3 = f1 * f2
+}
+
+impl Circuit<Fq> for ArithOnlyTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECConfigBuilder::empty().mul(true).configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let chip = ECChip::construct(config);
+
+        chip.assign_scoped(
+            &mut layouter,
+            || "arith only",
+            |ecc| {
+                let f3_rec = ecc.mul(self.f1, self.f2)?;
+                let f3 = ecc.load_private_field(self.f3)?;
+                ecc.ctx().constrain_equal(f3.cell(), f3_rec.cell())?;
+
+                // the add gate is not configured
+                assert!(matches!(
+                    ecc.add(self.f1, self.f2),
+                    Err(NativeEccError::GateDisabled("add"))
+                ));
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_gate_selection() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let f1 = Fq::random(&mut rng);
+    let f2 = Fq::random(&mut rng);
+
+    {
+        let circuit = ArithOnlyTestCircuit {
+            f1: Value::known(f1),
+            f2: Value::known(f2),
+            f3: Value::known(f1 * f2),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: mul not equal
+    {
+        let circuit = ArithOnlyTestCircuit {
+            f1: Value::known(f1),
+            f2: Value::known(f2),
+            f3: Value::known(f1 + f2),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[test]
+fn test_gate_selection_degree() {
+    let mut full = ConstraintSystem::<Fq>::default();
+    ECChip::<G1Affine, Fq>::configure(&mut full);
+    // degree 5 keeps the extended domain at 4N; the ec gates cannot exceed it
+    assert_eq!(full.degree(), 5);
+
+    let mut arith = ConstraintSystem::<Fq>::default();
+    ECConfigBuilder::<G1Affine, Fq>::empty()
+        .add(true)
+        .mul(true)
+        .configure(&mut arith);
+
+    assert!(arith.degree() < full.degree());
+}
+
+#[test]
+fn test_chip_stats() {
+    let mut full = ConstraintSystem::<Fq>::default();
+    let stats = ECChip::<G1Affine, Fq>::configure(&mut full).stats();
+    assert_eq!(stats.selectors, full.num_selectors());
+    assert_eq!(stats.advice_columns, full.num_advice_columns());
+    assert_eq!(stats.fixed_columns, full.num_fixed_columns());
+    assert_eq!(stats.instance_columns, full.num_instance_columns());
+    assert_eq!(stats.max_degree, full.degree());
+    // and the slopes of the `complete-addition` feature: 3 per add, of the ec
+    // add and sub and of the fixed base add, and 3 for the double
+    let complete_addition = if cfg!(feature = "complete-addition") {
+        3 * 3 + 3
+    } else {
+        0
+    };
+    assert_eq!(stats.constraints, 38 + complete_addition);
+    let names = ECChip::<G1Affine, Fq>::configure(&mut ConstraintSystem::default())
+        .constraint_names()
+        .to_vec();
+    assert_eq!(names.len(), stats.constraints);
+    assert!(names.iter().any(|name| name == "ec add: copy x"));
+    assert!(names
+        .iter()
+        .any(|name| name == "partial bit decomp: recompose"));
+    assert_eq!(stats.lookups, full.lookups().len());
+
+    let mut arith = ConstraintSystem::<Fq>::default();
+    let stats = ECConfigBuilder::<G1Affine, Fq>::empty()
+        .add(true)
+        .mul(true)
+        .configure(&mut arith)
+        .stats();
+    assert_eq!(stats.max_degree, arith.degree());
+    assert_eq!(stats.constraints, 2);
+}
+
+#[test]
+fn test_supported_curve() {
+    assert!(ECChip::<G1Affine, Fq>::check_curve().is_ok());
+
+    let mut cs = ConstraintSystem::<Fq>::default();
+    let config = ECConfigBuilder::<G1Affine, Fq>::new()
+        .try_configure(&mut cs)
+        .unwrap();
+    assert_eq!(config.curve_b, G1Affine::b());
+}
+
+#[test]
+fn test_layout_hash() {
+    let mut cs = ConstraintSystem::<Fq>::default();
+    let full = ECChip::<G1Affine, Fq>::configure(&mut cs);
+    // pinned: a change of this value must come with a bump of `LAYOUT_VERSION`
+    #[cfg(not(feature = "complete-addition"))]
+    assert_eq!(full.layout_hash(), 0x0c49dc7b92252b6f);
+
+    let mut cs = ConstraintSystem::<Fq>::default();
+    let builder = ECConfigBuilder::<G1Affine, Fq>::new().configure(&mut cs);
+    assert_eq!(builder.layout_hash(), full.layout_hash());
+
+    let mut cs = ConstraintSystem::<Fq>::default();
+    let arith = ECConfigBuilder::<G1Affine, Fq>::empty()
+        .add(true)
+        .configure(&mut cs);
+    assert_ne!(arith.layout_hash(), full.layout_hash());
+
+    let mut cs = ConstraintSystem::<Fq>::default();
+    let instance = cs.instance_column();
+    let public = ECChip::<G1Affine, Fq>::configure_with_instance(&mut cs, instance);
+    assert_ne!(public.layout_hash(), full.layout_hash());
+}
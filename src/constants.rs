@@ -0,0 +1,82 @@
+//! The parameters of Grumpkin, the curve the gates are written for.
+//!
+//! Grumpkin is `y^2 = x^3 - 17` over the scalar field of BN254. Its order is the
+//! modulus of the base field of BN254, a prime, hence there is no cofactor.
+
+use crate::backend::Field;
+use crate::backend::PrimeField;
+
+/// `b` of `y^2 = x^3 + b`, negated.
+pub const NEG_B: u64 = 17;
+
+/// The x coordinate of the generator, in decimal.
+pub const GENERATOR_X: &str = "1";
+
+/// The y coordinate of the generator, in decimal.
+pub const GENERATOR_Y: &str = "17631683881184975370165255887551781615748388533673675138860";
+
+/// The cofactor of the curve.
+pub const COFACTOR: u64 = 1;
+
+/// The order of the curve, in the format of `PrimeField::MODULUS`.
+pub const ORDER: &str = "0x30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd47";
+
+/// The x coordinate of the correction point `-2^256 * generator` of `point_mul`, in decimal.
+pub const CORRECTION_X: &str =
+    "18292374296067206172215749431916515128228165256807037435601971767767562625877";
+
+/// The y coordinate of the correction point `-2^256 * generator` of `point_mul`, in decimal.
+pub const CORRECTION_Y: &str =
+    "8411761026004062292626067694055242675827541323706122037355419552115320964415";
+
+/// `b` of `y^2 = x^3 + b`.
+pub fn b<F: Field>() -> F {
+    -F::from(NEG_B)
+}
+
+/// The coordinates of the generator; `None` if they do not fit in F.
+pub fn generator<F: PrimeField>() -> Option<(F, F)> {
+    Some((
+        F::from_str_vartime(GENERATOR_X)?,
+        F::from_str_vartime(GENERATOR_Y)?,
+    ))
+}
+
+/// The coordinates of the correction point; `None` if they do not fit in F.
+pub fn correction_point<F: PrimeField>() -> Option<(F, F)> {
+    Some((
+        F::from_str_vartime(CORRECTION_X)?,
+        F::from_str_vartime(CORRECTION_Y)?,
+    ))
+}
+
+#[cfg(all(test, feature = "pse"))]
+mod test {
+    use halo2_proofs::halo2curves::ff::PrimeField;
+    use halo2_proofs::halo2curves::CurveAffine;
+    use halo2curves::grumpkin::Fq;
+    use halo2curves::grumpkin::Fr;
+    use halo2curves::grumpkin::G1Affine;
+
+    use super::b;
+    use super::correction_point;
+    use super::generator;
+    use super::COFACTOR;
+    use super::ORDER;
+    use crate::tables::neg_generator_times_2_to_256;
+
+    #[test]
+    fn test_grumpkin_constants() {
+        assert_eq!(b::<Fq>(), G1Affine::b());
+
+        let g = G1Affine::generator().coordinates().unwrap();
+        assert_eq!(generator::<Fq>(), Some((*g.x(), *g.y())));
+
+        // prime order, i.e., the order is the modulus of the scalar field
+        assert_eq!(ORDER, Fr::MODULUS);
+        assert_eq!(COFACTOR, 1);
+
+        let (_, x, y) = neg_generator_times_2_to_256::<G1Affine, Fq>().unwrap();
+        assert_eq!(correction_point::<Fq>(), Some((x, y)));
+    }
+}
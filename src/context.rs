@@ -0,0 +1,804 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Region;
+use halo2_proofs::circuit::Value;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::region_ctx::RegionCtx;
+use crate::util::coordinates;
+use crate::ArithOps;
+use crate::AssignedECPoint;
+use crate::AssignedProjectivePoint;
+use crate::ECChip;
+use crate::ImportCells;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// An ec chip bound to a region.
+///
+/// The context owns the region and the running offset, and exposes the ops of
+/// `NativeECOps` and `ArithOps` without the region, config and offset parameters.
+/// Call `finalize` once done, to pad the rows queried by the last gates.
+pub struct ScopedEcc<'r, C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    chip: ECChip<C, F>,
+    ctx: RegionCtx<'r, F>,
+    // the constant points already loaded, keyed by their coordinates,
+    // if the cache is enabled
+    point_cache: Option<BTreeMap<([u8; 32], [u8; 32]), AssignedECPoint<C, F>>>,
+}
+
+/// A running sum of points, carried from one region to the next.
+///
+/// Each `add` copies the sum and the point next to each other in the region
+/// of the given `ScopedEcc`, so the sum of a region is bound to the next one
+/// through copy constraints, and each region keeps its own offset. The points
+/// are summed with the incomplete addition: a point must not be the sum or
+/// its negation.
+///
+/// Floor planners may synthesize a region more than once: update a clone of
+/// the accumulator in the region, and keep it once the region is assigned.
+///
+/// ```ignore
+/// let mut acc = Accumulator::new();
+/// for batch in batches {
+///     acc = ecc.assign_scoped(&mut layouter, || "batch", |ecc| {
+///         let mut acc = acc.clone();
+///         acc.add_all(ecc, &batch)?;
+///         Ok(acc)
+///     })?;
+/// }
+/// let sum = acc.finalize()?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct Accumulator<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    sum: Option<AssignedECPoint<C, F>>,
+}
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// Assigns a region through a `ScopedEcc`.
+    /// The region is finalized once `assignment` returns.
+    pub fn assign_scoped<A, AR, N, NR>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        name: N,
+        mut assignment: A,
+    ) -> Result<AR, NativeEccError>
+    where
+        A: FnMut(&mut ScopedEcc<'_, C, F>) -> Result<AR, NativeEccError>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        let res = layouter.assign_region(name, |region| {
+            let mut ecc = ScopedEcc::new(self, region);
+            let res = assignment(&mut ecc)?;
+            ecc.finalize()?;
+            Ok(res)
+        })?;
+        Ok(res)
+    }
+
+    /// Assigns one region per input through a `ScopedEcc`, e.g., for
+    /// independent scalar muls, and returns the results in order.
+    ///
+    /// Each region has its own running offset starting from 0, so the
+    /// blocks do not depend on each other's layout, and the floor planner
+    /// is free to place them. Values returned by a block can be used in the
+    /// next ones through copy constraints.
+    pub fn assign_scoped_each<T, A, AR, N, NR>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        name: N,
+        inputs: &[T],
+        mut assignment: A,
+    ) -> Result<Vec<AR>, NativeEccError>
+    where
+        A: FnMut(&mut ScopedEcc<'_, C, F>, &T) -> Result<AR, NativeEccError>,
+        N: Fn() -> NR,
+        NR: Into<String>,
+    {
+        inputs
+            .iter()
+            .map(|input| self.assign_scoped(layouter, &name, |ecc| assignment(ecc, input)))
+            .collect()
+    }
+
+    /// MSM of the terms (p_i, s_i), in chunks of `chunk_size` terms, each chunk
+    /// in its own region.
+    ///
+    /// The running sum is carried from one region to the next through copy
+    /// constraints, so the synthesis only holds the witness of one chunk at a
+    /// time. The terms are summed with the incomplete addition, as in `point_mul`.
+    ///
+    /// Panics if `chunk_size` is 0.
+    pub fn msm_chunked(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        terms: &[(Value<C>, Value<C::ScalarExt>)],
+        chunk_size: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        let mut acc = Accumulator::new();
+        for chunk in terms.chunks(chunk_size) {
+            acc = self.assign_scoped(
+                layouter,
+                || "msm chunk",
+                |ecc| {
+                    let mut acc = acc.clone();
+                    for (p, s) in chunk {
+                        let term = ecc.point_mul(*p, *s)?;
+                        acc.add(ecc, &term)?;
+                    }
+                    Ok(acc)
+                },
+            )?;
+        }
+        acc.finalize()
+    }
+
+    /// MSM of fixed base terms (b_i, s_i), with the bases known at synthesis
+    /// time, and of variable base terms (p_j, e_j), in one region, e.g.,
+    /// s * G + Σ e_j * P_j for a batch of signatures.
+    ///
+    /// The fixed base terms read their multiples from the fixed columns, as
+    /// `fixed_base_mul`, at about half the rows of a variable base mul; all
+    /// terms are summed in a single `Accumulator`, with the incomplete addition.
+    /// Fails with `InvalidInputLength` if there is no term.
+    pub fn msm_mixed(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        fixed: &[(C, Value<C::ScalarExt>)],
+        variable: &[(Value<C>, Value<C::ScalarExt>)],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        let acc = self.assign_scoped(
+            layouter,
+            || "msm mixed",
+            |ecc| {
+                let mut acc = Accumulator::new();
+                for (base, s) in fixed {
+                    let term = ecc.fixed_base_mul(base, *s)?;
+                    acc.add(ecc, &term)?;
+                }
+                for (p, e) in variable {
+                    let term = ecc.point_mul(*p, *e)?;
+                    acc.add(ecc, &term)?;
+                }
+                Ok(acc)
+            },
+        )?;
+        acc.finalize()
+    }
+}
+
+impl<C, F> Default for Accumulator<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    fn default() -> Self {
+        Self { sum: None }
+    }
+}
+
+impl<C, F> Accumulator<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// An empty accumulator.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An accumulator starting from p, e.g., an offset point.
+    pub fn from_point(p: AssignedECPoint<C, F>) -> Self {
+        Self { sum: Some(p) }
+    }
+
+    /// Adds p to the sum, in 4 rows of the region of `ecc`.
+    ///
+    /// The first point added to an empty accumulator becomes the sum, without
+    /// using any row.
+    pub fn add(
+        &mut self,
+        ecc: &mut ScopedEcc<'_, C, F>,
+        p: &AssignedECPoint<C, F>,
+    ) -> Result<(), NativeEccError> {
+        let sum = match self.sum.take() {
+            None => p.clone(),
+            Some(sum) => {
+                let sum = ecc.copy_point(&sum)?;
+                let p = ecc.copy_point(p)?;
+                ecc.conditional_point_add_const(&sum, &p, true)?
+            }
+        };
+        self.sum = Some(sum);
+        Ok(())
+    }
+
+    /// Adds the points to the sum, in order.
+    pub fn add_all(
+        &mut self,
+        ecc: &mut ScopedEcc<'_, C, F>,
+        points: &[AssignedECPoint<C, F>],
+    ) -> Result<(), NativeEccError> {
+        points.iter().try_for_each(|p| self.add(ecc, p))
+    }
+
+    /// The current sum, if any point was added.
+    pub fn sum(&self) -> Option<&AssignedECPoint<C, F>> {
+        self.sum.as_ref()
+    }
+
+    /// The sum of the added points; fails if none was added.
+    pub fn finalize(self) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.sum.ok_or(NativeEccError::InvalidInputLength {
+            expected: 1,
+            actual: 0,
+        })
+    }
+}
+
+impl<'r, C, F> ScopedEcc<'r, C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// Binds the chip to a region, starting from offset 0.
+    pub fn new(chip: &ECChip<C, F>, region: Region<'r, F>) -> Self {
+        Self {
+            chip: chip.clone(),
+            ctx: chip.region_ctx(region),
+            point_cache: None,
+        }
+    }
+
+    /// Caches the constant points, so that `load_constant_point` loads each
+    /// of them once and returns the same cells afterwards.
+    pub fn enable_point_cache(&mut self) {
+        self.point_cache.get_or_insert_with(BTreeMap::new);
+    }
+
+    /// The next free row of the region.
+    pub fn offset(&self) -> usize {
+        self.ctx.offset()
+    }
+
+    /// The underlying region context, for assignments outside of the chip.
+    pub fn ctx(&mut self) -> &mut RegionCtx<'r, F> {
+        &mut self.ctx
+    }
+
+    /// Pads the rows queried by the last gates and releases the region.
+    pub fn finalize(mut self) -> Result<(), NativeEccError> {
+        self.chip.pad(&mut self.ctx, self.chip.config())
+    }
+
+    /// Loads an ecpoint (x, y) and constraints it is on curve.
+    pub fn load_private_point(
+        &mut self,
+        p: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .load_private_point(&mut self.ctx, self.chip.config(), p)
+    }
+
+    /// Loads a point known at synthesis time, constrained to its constant
+    /// coordinates and on curve.
+    ///
+    /// With the point cache enabled, a point loaded before returns its cells
+    /// without using any row; use `copy_point` where an op expects the point
+    /// on the latest row.
+    pub fn load_constant_point(&mut self, p: C) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let (x, y) = coordinates(&p)?;
+        let key = (x.to_repr(), y.to_repr());
+        if let Some(cached) = self.point_cache.as_ref().and_then(|cache| cache.get(&key)) {
+            return Ok(cached.clone());
+        }
+
+        let res = self.load_private_point(Value::known(p))?;
+        self.assert_equal_const(&res, &p)?;
+        if let Some(cache) = self.point_cache.as_mut() {
+            cache.insert(key, res.clone());
+        }
+        Ok(res)
+    }
+
+    /// Returns 2 * acc + bit * base, for acc on the latest row, in 4 rows.
+    pub fn double_and_add_step(
+        &mut self,
+        acc: &AssignedECPoint<C, F>,
+        base: &AssignedECPoint<C, F>,
+        bit: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .double_and_add_step(&mut self.ctx, self.chip.config(), acc, base, bit)
+    }
+
+    /// Checks, during witness generation only, that res is p * s, without
+    /// any constraint or row.
+    pub fn verify_mul_witness(
+        &mut self,
+        p: &AssignedECPoint<C, F>,
+        s: Value<C::ScalarExt>,
+        res: &AssignedECPoint<C, F>,
+    ) -> Result<(), NativeEccError> {
+        self.chip
+            .verify_mul_witness(&mut self.ctx, self.chip.config(), p, s, res)
+    }
+
+    /// Constrains p to the coordinates of the constant point q, without
+    /// using any row.
+    pub fn assert_equal_const(
+        &mut self,
+        p: &AssignedECPoint<C, F>,
+        q: &C,
+    ) -> Result<(), NativeEccError> {
+        self.chip
+            .assert_equal_const(&mut self.ctx, self.chip.config(), p, q)
+    }
+
+    /// Loads a pair (x, y) without constraining it is on curve.
+    pub fn load_private_point_unchecked(
+        &mut self,
+        p: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .load_private_point_unchecked(&mut self.ctx, self.chip.config(), p)
+    }
+
+    /// Loads points already validated elsewhere, without the on curve check.
+    pub fn load_points_trusted(
+        &mut self,
+        ps: &[Value<C>],
+    ) -> Result<Vec<AssignedECPoint<C, F>>, NativeEccError> {
+        self.chip
+            .load_points_trusted(&mut self.ctx, self.chip.config(), ps)
+    }
+
+    /// Copies an assigned point, e.g., of another region, into the next row
+    /// without the on curve check.
+    pub fn copy_point(
+        &mut self,
+        p: &AssignedECPoint<C, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip.copy_point(&mut self.ctx, self.chip.config(), p)
+    }
+
+    /// Imports a value from the cells of another chip into the next row,
+    /// e.g., a point, with copy constraints and without any check.
+    pub fn import<T: ImportCells<F>>(
+        &mut self,
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<T, NativeEccError> {
+        let config = self.chip.config();
+        T::import_cells(&mut self.ctx, &[config.a, config.b], cells)
+    }
+
+    /// Enforces p is on curve; p must be the latest assigned point.
+    pub fn enforce_on_curve(&mut self, p: &AssignedECPoint<C, F>) -> Result<(), NativeEccError> {
+        self.chip
+            .enforce_on_curve(&mut self.ctx, self.chip.config(), p)
+    }
+
+    /// Returns the affine point (X / Z, Y / Z) of a projective point.
+    pub fn normalize(
+        &mut self,
+        p: &AssignedProjectivePoint<C, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip.normalize(&mut self.ctx, self.chip.config(), p)
+    }
+
+    /// Returns p1 + p2 if b == 1 and p1 if b == 0.
+    /// p1, p2 and b must be the latest assigned cells, in this order.
+    pub fn conditional_point_add(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .conditional_point_add(&mut self.ctx, self.chip.config(), p1, p2, b)
+    }
+
+    /// Returns p1 + p2 if b is true and p1 otherwise, for a b known at synthesis time.
+    /// If b is true, p1 and p2 must be the latest assigned points, in this order.
+    pub fn conditional_point_add_const(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: bool,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .conditional_point_add_const(&mut self.ctx, self.chip.config(), p1, p2, b)
+    }
+
+    /// Returns p1 + p2 if b == 1 and p1 if b == 0, for a b assigned anywhere,
+    /// e.g., by another gadget; it is copied below p1 and p2, which must be the
+    /// latest assigned points, in this order.
+    pub fn conditional_point_add_assigned(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .conditional_point_add_assigned(&mut self.ctx, self.chip.config(), p1, p2, b)
+    }
+
+    /// Returns p1 - p2 if b == 1 and p1 if b == 0, in the rows of
+    /// `conditional_point_add`.
+    /// p1, p2 and b must be the latest assigned cells, in this order.
+    pub fn conditional_point_sub(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .conditional_point_sub(&mut self.ctx, self.chip.config(), p1, p2, b)
+    }
+
+    /// Returns p1 - p2 if b == 1 and p1 if b == 0, for a b assigned anywhere,
+    /// copied as for `conditional_point_add_assigned`.
+    pub fn conditional_point_sub_assigned(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .conditional_point_sub_assigned(&mut self.ctx, self.chip.config(), p1, p2, b)
+    }
+
+    /// Returns (q, p) if b == 1 and (p, q) if b == 0; the operands are copied.
+    #[allow(clippy::type_complexity)]
+    pub fn cswap(
+        &mut self,
+        b: &AssignedCell<F, F>,
+        p: &AssignedECPoint<C, F>,
+        q: &AssignedECPoint<C, F>,
+    ) -> Result<(AssignedECPoint<C, F>, AssignedECPoint<C, F>), NativeEccError> {
+        self.chip.cswap(&mut self.ctx, self.chip.config(), b, p, q)
+    }
+
+    /// Returns p1 + p1; p1 must be the latest assigned point.
+    pub fn point_double(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .point_double(&mut self.ctx, self.chip.config(), p1)
+    }
+
+    /// Returns the window table `[1 * p, ..., (2^window_bits - 1) * p]`,
+    /// witnessed and validated entry by entry.
+    pub fn load_window_table(
+        &mut self,
+        p: &AssignedECPoint<C, F>,
+        window_bits: usize,
+    ) -> Result<Vec<AssignedECPoint<C, F>>, NativeEccError> {
+        self.chip
+            .load_window_table(&mut self.ctx, self.chip.config(), p, window_bits)
+    }
+
+    /// Decomposes a scalar into a vector of boolean cells, in little endian.
+    pub fn decompose_scalar(
+        &mut self,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .decompose_scalar::<C::ScalarExt>(&mut self.ctx, self.chip.config(), s)
+    }
+
+    /// Decomposes a scalar into its canonical bits, in little endian, and
+    /// returns them with the [low, high] limbs of the scalar.
+    #[allow(clippy::type_complexity)]
+    pub fn decompose_scalar_canonical(
+        &mut self,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .decompose_scalar_canonical::<C::ScalarExt>(&mut self.ctx, self.chip.config(), s)
+    }
+
+    /// Splits a field element into the cells of its canonical [low, high]
+    /// 128 bits limbs.
+    pub fn decompose_field_limbs(
+        &mut self,
+        x: &AssignedCell<F, F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError> {
+        self.chip
+            .decompose_field_limbs(&mut self.ctx, self.chip.config(), x)
+    }
+
+    /// Decomposes a scalar of `num_bits` bits into as many boolean cells, in
+    /// little endian, constraining the scalar to [0, 2^num_bits).
+    pub fn decompose_scalar_sized(
+        &mut self,
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip.decompose_scalar_sized::<C::ScalarExt>(
+            &mut self.ctx,
+            self.chip.config(),
+            s,
+            num_bits,
+        )
+    }
+
+    /// Returns p * s.
+    pub fn point_mul(
+        &mut self,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .point_mul::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s)
+    }
+
+    /// Returns p * s, and the [low, high] limbs of s the mul is bound to.
+    #[allow(clippy::type_complexity)]
+    pub fn point_mul_with_scalar(
+        &mut self,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(AssignedECPoint<C, F>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .point_mul_with_scalar::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s)
+    }
+
+    /// Returns p * s, for a scalar of `num_bits` bits.
+    pub fn point_mul_sized(
+        &mut self,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .point_mul_sized::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s, num_bits)
+    }
+
+    /// Returns p * s for a scalar known at synthesis time.
+    pub fn point_mul_const(
+        &mut self,
+        p: Value<C>,
+        s: C::ScalarExt,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .point_mul_const::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s)
+    }
+
+    /// Copies the little endian bits of a scalar assigned outside the chip,
+    /// checking they are binary, and returns the copies and the 128 bits limbs
+    /// they recompose to.
+    #[allow(clippy::type_complexity)]
+    pub fn import_scalar_bits(
+        &mut self,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<(Vec<AssignedCell<F, F>>, Vec<AssignedCell<F, F>>), NativeEccError> {
+        self.chip
+            .import_scalar_bits(&mut self.ctx, self.chip.config(), bits)
+    }
+
+    /// Returns p * s, for the bits of s returned by `decompose_scalar`.
+    pub fn point_mul_from_bits(
+        &mut self,
+        p: Value<C>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.chip
+            .point_mul_from_bits(&mut self.ctx, self.chip.config(), p, bits)
+    }
+
+    /// Multiplies the generator by a scalar, reading its multiples from the fixed columns.
+    pub fn generator_mul(
+        &mut self,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .generator_mul::<C::ScalarExt>(&mut self.ctx, self.chip.config(), s)
+    }
+
+    /// Returns base * s for a base known at synthesis time, via the fixed columns.
+    pub fn fixed_base_mul(
+        &mut self,
+        base: &C,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .fixed_base_mul::<C::ScalarExt>(&mut self.ctx, self.chip.config(), base, s)
+    }
+
+    /// Returns the Pedersen commitments value * G + blinding * h of the openings.
+    pub fn pedersen_commit_batch(
+        &mut self,
+        h: C,
+        openings: &[(Value<C::ScalarExt>, Value<C::ScalarExt>)],
+    ) -> Result<Vec<AssignedECPoint<C, F>>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip.pedersen_commit_batch::<C::ScalarExt>(
+            &mut self.ctx,
+            self.chip.config(),
+            h,
+            openings,
+        )
+    }
+
+    /// Returns the x coordinate of p * s, via the x-only ladder.
+    pub fn x_only_point_mul(
+        &mut self,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .x_only_point_mul::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s)
+    }
+
+    /// Returns p * s, via the x-only ladder and a y recovery at the end.
+    pub fn x_only_point_mul_with_y(
+        &mut self,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    {
+        self.chip
+            .x_only_point_mul_with_y::<C::ScalarExt>(&mut self.ctx, self.chip.config(), p, s)
+    }
+
+    /// Loads a private field element.
+    pub fn load_private_field(
+        &mut self,
+        f: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        self.chip
+            .load_private_field(&mut self.ctx, self.chip.config(), f)
+    }
+
+    /// Loads two private field elements.
+    pub fn load_two_private_fields(
+        &mut self,
+        f1: Value<F>,
+        f2: Value<F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError> {
+        self.chip
+            .load_two_private_fields(&mut self.ctx, self.chip.config(), f1, f2)
+    }
+
+    /// Returns a + b.
+    pub fn add(&mut self, a: Value<F>, b: Value<F>) -> Result<AssignedCell<F, F>, NativeEccError> {
+        self.chip.add(&mut self.ctx, self.chip.config(), a, b)
+    }
+
+    /// Returns a * b.
+    pub fn mul(&mut self, a: Value<F>, b: Value<F>) -> Result<AssignedCell<F, F>, NativeEccError> {
+        self.chip.mul(&mut self.ctx, self.chip.config(), a, b)
+    }
+
+    /// Returns the elementwise sum a + b.
+    pub fn vec_add(
+        &mut self,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        self.chip.vec_add(&mut self.ctx, self.chip.config(), a, b)
+    }
+
+    /// Returns the elementwise product a * b.
+    pub fn vec_mul(
+        &mut self,
+        a: &[AssignedCell<F, F>],
+        b: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        self.chip.vec_mul(&mut self.ctx, self.chip.config(), a, b)
+    }
+
+    /// Returns k * v, elementwise.
+    pub fn vec_scale(
+        &mut self,
+        k: &AssignedCell<F, F>,
+        v: &[AssignedCell<F, F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        self.chip.vec_scale(&mut self.ctx, self.chip.config(), k, v)
+    }
+
+    /// Returns (a / b, 0), or (0, 1) if b = 0.
+    #[allow(clippy::type_complexity)]
+    pub fn safe_div(
+        &mut self,
+        a: &AssignedCell<F, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<(AssignedCell<F, F>, AssignedCell<F, F>), NativeEccError> {
+        self.chip.safe_div(&mut self.ctx, self.chip.config(), a, b)
+    }
+
+    /// Asserts the partial bit decomposition of the six inputs.
+    pub fn partial_bit_decomp(
+        &mut self,
+        inputs: &[Value<F>],
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError> {
+        self.chip
+            .partial_bit_decomp(&mut self.ctx, self.chip.config(), inputs)
+    }
+
+    /// Decomposes a u128 into its little endian bit cells,
+    /// and returns them along with the cell that contains the u128.
+    #[allow(clippy::type_complexity)]
+    pub fn decompose_u128(
+        &mut self,
+        input: Value<u128>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, AssignedCell<F, F>), NativeEccError> {
+        self.chip
+            .decompose_u128(&mut self.ctx, self.chip.config(), input)
+    }
+
+    /// Constrains two assigned points are equal.
+    pub fn constrain_equal(
+        &mut self,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+    ) -> Result<(), NativeEccError> {
+        self.ctx.constrain_equal(p1.x.cell(), p2.x.cell())?;
+        self.ctx.constrain_equal(p1.y.cell(), p2.y.cell())?;
+        Ok(())
+    }
+}
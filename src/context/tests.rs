@@ -0,0 +1,530 @@
+use std::ops::Mul;
+
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::context::Accumulator;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct ScopedTestCircuit {
+    s: Value<Fr>,
+    p1: Value<G1Affine>,
+    p2: Value<G1Affine>,
+    p3: Value<G1Affine>, // p1 + p2
+    p4: Value<G1Affine>, // 2p1
+    p5: Value<G1Affine>, // p1 * s
+}
+
+impl Circuit<Fq> for ScopedTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "test scoped ec circuit",
+            |ecc| {
+                let p3 = ecc.load_private_point(self.p3)?;
+                let p4 = ecc.load_private_point(self.p4)?;
+                let p5 = ecc.load_private_point(self.p5)?;
+
+                // unit test: point addition with 1
+                {
+                    let p1 = ecc.load_private_point_unchecked(self.p1)?;
+                    let p2 = ecc.load_private_point_unchecked(self.p2)?;
+                    let bit = ecc.load_private_field(Value::known(Fq::ONE))?;
+                    let p3_rec = ecc.conditional_point_add(&p1, &p2, &bit)?;
+                    ecc.constrain_equal(&p3, &p3_rec)?;
+                }
+
+                // unit test: point doubling
+                {
+                    let p1 = ecc.load_private_point(self.p1)?;
+                    let p4_rec = ecc.point_double(&p1)?;
+                    ecc.constrain_equal(&p4, &p4_rec)?;
+                }
+
+                // unit test: curve mul
+                {
+                    let p5_rec = ecc.point_mul(self.p1, self.s)?;
+                    ecc.constrain_equal(&p5, &p5_rec)?;
+                }
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_scoped_ec_ops() {
+    let k = 14;
+
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+    let p3 = (p1 + p2).to_affine();
+    let p4 = (p1 + p1).to_affine();
+    let p5 = p1.mul(s).to_affine();
+
+    {
+        let circuit = ScopedTestCircuit {
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: double not equal
+    {
+        let p4 = (p1 + p2).to_affine();
+        let circuit = ScopedTestCircuit {
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+struct ScopedEachTestCircuit {
+    s: Value<Fr>,
+    // (p, p * s), one region each
+    muls: Vec<(Value<G1Affine>, Value<G1Affine>)>,
+}
+
+impl Circuit<Fq> for ScopedEachTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            s: Value::unknown(),
+            muls: vec![(Value::unknown(), Value::unknown()); self.muls.len()],
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let results = ec_chip.assign_scoped_each(
+            &mut layouter,
+            || "scalar mul",
+            &self.muls,
+            |ecc, (p, expected)| {
+                let res = ecc.point_mul(*p, self.s)?;
+                let expected = ecc.load_private_point(*expected)?;
+                ecc.constrain_equal(&res, &expected)?;
+                Ok(res)
+            },
+        )?;
+        assert_eq!(results.len(), self.muls.len());
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_scoped_each() {
+    let k = 13;
+
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let circuit = |p2_s: G1Affine| ScopedEachTestCircuit {
+        s: Value::known(s),
+        muls: vec![
+            (Value::known(p1), Value::known(p1.mul(s).to_affine())),
+            (Value::known(p2), Value::known(p2_s)),
+        ],
+    };
+
+    let prover = MockProver::run(k, &circuit(p2.mul(s).to_affine()), vec![]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: the second mul is not equal
+    let prover = MockProver::run(k, &circuit(p1.mul(s).to_affine()), vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[derive(Default, Debug, Clone)]
+struct MsmChunkedTestCircuit {
+    terms: Vec<(Value<G1Affine>, Value<Fr>)>,
+    expected: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for MsmChunkedTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            terms: vec![(Value::unknown(), Value::unknown()); self.terms.len()],
+            expected: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let res = ec_chip.msm_chunked(&mut layouter, &self.terms, 2)?;
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "msm result",
+            |ecc| {
+                let expected = ecc.load_private_point(self.expected)?;
+                ecc.constrain_equal(&res, &expected)
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_msm_chunked() {
+    let k = 13;
+
+    let mut rng = test_rng();
+    let terms = (0..3)
+        .map(|_| (G1::random(&mut rng).to_affine(), Fr::random(&mut rng)))
+        .collect::<Vec<_>>();
+    let expected = terms
+        .iter()
+        .fold(G1::identity(), |acc, (p, s)| acc + (*p).mul(*s))
+        .to_affine();
+
+    let circuit = |expected: G1Affine| MsmChunkedTestCircuit {
+        terms: terms
+            .iter()
+            .map(|(p, s)| (Value::known(*p), Value::known(*s)))
+            .collect(),
+        expected: Value::known(expected),
+    };
+
+    let prover = MockProver::run(k, &circuit(expected), vec![]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: the last chunk is not added
+    let partial = (terms[0].0.mul(terms[0].1) + terms[1].0.mul(terms[1].1)).to_affine();
+    let prover = MockProver::run(k, &circuit(partial), vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[derive(Default, Debug, Clone)]
+struct MsmMixedTestCircuit {
+    fixed: Vec<(G1Affine, Value<Fr>)>,
+    variable: Vec<(Value<G1Affine>, Value<Fr>)>,
+    expected: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for MsmMixedTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            fixed: self
+                .fixed
+                .iter()
+                .map(|(base, _)| (*base, Value::unknown()))
+                .collect(),
+            variable: vec![(Value::unknown(), Value::unknown()); self.variable.len()],
+            expected: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let res = ec_chip.msm_mixed(&mut layouter, &self.fixed, &self.variable)?;
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "msm result",
+            |ecc| {
+                let expected = ecc.load_private_point(self.expected)?;
+                ecc.constrain_equal(&res, &expected)
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_msm_mixed() {
+    let k = 12;
+
+    // s * G + t * H + e_1 * P_1 + e_2 * P_2
+    let mut rng = test_rng();
+    let g = G1::generator().to_affine();
+    let h = G1::random(&mut rng).to_affine();
+    let fixed = [(g, Fr::random(&mut rng)), (h, Fr::random(&mut rng))];
+    let variable = (0..2)
+        .map(|_| (G1::random(&mut rng).to_affine(), Fr::random(&mut rng)))
+        .collect::<Vec<_>>();
+    let expected = fixed
+        .iter()
+        .chain(variable.iter())
+        .fold(G1::identity(), |acc, (p, s)| acc + (*p).mul(*s))
+        .to_affine();
+
+    let circuit = |expected: G1Affine| MsmMixedTestCircuit {
+        fixed: fixed
+            .iter()
+            .map(|(base, s)| (*base, Value::known(*s)))
+            .collect(),
+        variable: variable
+            .iter()
+            .map(|(p, e)| (Value::known(*p), Value::known(*e)))
+            .collect(),
+        expected: Value::known(expected),
+    };
+
+    let prover = MockProver::run(k, &circuit(expected), vec![]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: the fixed base term of H is left out
+    let partial = (G1::from(expected) - h.mul(fixed[1].1)).to_affine();
+    let prover = MockProver::run(k, &circuit(partial), vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct PointCacheTestCircuit {
+    p: Value<G1Affine>, // 2 * generator
+}
+
+impl Circuit<Fq> for PointCacheTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+        let g = G1::generator().to_affine();
+
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "test point cache",
+            |ecc| {
+                ecc.enable_point_cache();
+                let g1 = ecc.load_constant_point(g)?;
+                let offset = ecc.offset();
+                let g2 = ecc.load_constant_point(g)?;
+                assert_eq!(ecc.offset(), offset);
+                assert_eq!(g1.cells(), g2.cells());
+
+                let g2 = ecc.copy_point(&g2)?;
+                let p_rec = ecc.point_double(&g2)?;
+                let p = ecc.load_private_point(self.p)?;
+                ecc.constrain_equal(&p, &p_rec)
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_point_cache() {
+    let k = 5;
+    let g = G1::generator();
+
+    let circuit = PointCacheTestCircuit {
+        p: Value::known(g.double().to_affine()),
+    };
+    let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: not 2 * generator
+    let circuit = PointCacheTestCircuit {
+        p: Value::known((g.double() + g).to_affine()),
+    };
+    let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[derive(Default, Debug, Clone)]
+struct AccumulatorTestCircuit {
+    // the points of each region
+    batches: Vec<Vec<Value<G1Affine>>>,
+    expected: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for AccumulatorTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            batches: self
+                .batches
+                .iter()
+                .map(|batch| vec![Value::unknown(); batch.len()])
+                .collect(),
+            expected: Value::unknown(),
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let mut acc = Accumulator::new();
+        for batch in &self.batches {
+            acc = ec_chip.assign_scoped(
+                &mut layouter,
+                || "batch",
+                |ecc| {
+                    let mut acc = acc.clone();
+                    let points = batch
+                        .iter()
+                        .map(|p| ecc.load_private_point(*p))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    acc.add_all(ecc, &points)?;
+                    Ok(acc)
+                },
+            )?;
+        }
+        let res = acc.finalize()?;
+
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "accumulated sum",
+            |ecc| {
+                let expected = ecc.load_private_point(self.expected)?;
+                ecc.constrain_equal(&res, &expected)
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_accumulator() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let batches = [3, 1, 2]
+        .iter()
+        .map(|n| {
+            (0..*n)
+                .map(|_| G1::random(&mut rng).to_affine())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+    let sum = batches
+        .iter()
+        .flatten()
+        .fold(G1::identity(), |acc, p| acc + p)
+        .to_affine();
+
+    let circuit = |expected: G1Affine| AccumulatorTestCircuit {
+        batches: batches
+            .iter()
+            .map(|batch| batch.iter().copied().map(Value::known).collect())
+            .collect(),
+        expected: Value::known(expected),
+    };
+
+    let prover = MockProver::run(k, &circuit(sum), vec![]).unwrap();
+    prover.assert_satisfied();
+
+    // error case: the sum without the last region
+    let partial = (sum - batches[2][0] - batches[2][1]).to_affine();
+    let prover = MockProver::run(k, &circuit(partial), vec![]).unwrap();
+    assert!(prover.verify().is_err());
+
+    // an empty accumulator has no sum
+    assert!(Accumulator::<G1Affine, Fq>::new().finalize().is_err());
+}
@@ -0,0 +1,302 @@
+//! Row counts of the ops, to size a circuit without synthesizing it.
+//!
+//! The counts follow the layout of `NativeECOps` and `ArithOps` within a region,
+//! i.e., the operands of an op are already assigned at the rows it expects.
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// Rows reserved at the end of the circuit, for the blinding factors of the
+/// advice columns (queried at up to 5 rotations) and the last unusable row.
+pub const RESERVED_ROWS: usize = 8;
+
+/// Rows of `decompose_u128`: 3 rows per 4 bits.
+pub const DECOMPOSE_U128_ROWS: usize = 96;
+
+/// Rows to decompose a scalar of `bits` bits, in chunks of 128 bits.
+pub fn rows_for_decompose_scalar(bits: usize) -> usize {
+    bits.div_ceil(128) * DECOMPOSE_U128_ROWS
+}
+
+/// Rows of the canonical check of a decomposed scalar: the decompositions of
+/// the two limbs of the difference to the order, the borrow, 2 muls, 2 constants and 4 adds.
+pub const CANONICAL_SCALAR_ROWS: usize = 2 * DECOMPOSE_U128_ROWS + 1 + 2 * 2 + 2 + 4 * 2;
+
+/// Rows of `decompose_field_limbs`: the decompositions of the two limbs, their
+/// canonical check, a constant, a mul and an add.
+pub const DECOMPOSE_FIELD_LIMBS_ROWS: usize =
+    2 * DECOMPOSE_U128_ROWS + CANONICAL_SCALAR_ROWS + 1 + 2 + 2;
+
+/// Rows of the reduction of 256 bits modulo the order of the scalar field:
+/// the canonical decomposition of the result, the quotient and the carry as
+/// 4 bits values, 3 constants, 3 muls and 5 adds.
+pub const REDUCE_SCALAR_ROWS: usize =
+    2 * DECOMPOSE_U128_ROWS + CANONICAL_SCALAR_ROWS + 2 * 3 + 3 + 3 * 2 + 5 * 2;
+
+/// Rows of the double-then-add ladder over `bits` already decomposed bits:
+/// the generator, 4 rows per bit and 3 rows for the final correction.
+pub fn rows_for_point_mul_with_bits(bits: usize) -> usize {
+    1 + 4 * bits + 3
+}
+
+/// Rows of the window table of `load_window_table`: the copy of the base,
+/// its double, and 3 rows per next entry.
+pub fn rows_for_window_table(window_bits: usize) -> usize {
+    let size = (1 << window_bits) - 1;
+    match size {
+        0 | 1 => size,
+        _ => 2 + 3 * (size - 2),
+    }
+}
+
+/// Rows of a variable base scalar mul, including the scalar decomposition
+/// and the load of the base.
+pub fn rows_for_point_mul(bits: usize) -> usize {
+    rows_for_decompose_scalar(bits) + rows_for_point_mul_from_bits(bits)
+}
+
+/// Rows of a variable base scalar mul by a scalar known at synthesis time,
+/// whose `ones` bits are 1: the loads of the base and of the generator,
+/// a double per bit, 3 rows per 1 bit and 3 rows for the final correction.
+pub fn rows_for_point_mul_const(bits: usize, ones: usize) -> usize {
+    2 + bits + 3 * ones + 3
+}
+
+/// Rows of a variable base scalar mul of an already decomposed scalar,
+/// including the load of the base.
+pub fn rows_for_point_mul_from_bits(bits: usize) -> usize {
+    1 + rows_for_point_mul_with_bits(bits)
+}
+
+/// Rows of the fixed base ladder of the generator over `bits` already
+/// decomposed bits: the start point, 2 rows per bit and 3 rows for the final correction.
+pub fn rows_for_generator_mul_with_bits(bits: usize) -> usize {
+    1 + 2 * bits + 3
+}
+
+/// Rows of a fixed base scalar mul of the generator, including the scalar decomposition.
+pub fn rows_for_generator_mul(bits: usize) -> usize {
+    rows_for_decompose_scalar(bits) + rows_for_generator_mul_with_bits(bits)
+}
+
+/// Rows of the x-only ladder from a loaded base, for a scalar of `bits` bits
+/// with its top bit set: the double of the base, the start row and 3 rows
+/// per bit below the top bit.
+pub fn rows_for_x_only_ladder(bits: usize) -> usize {
+    2 + 3 * (bits - 1)
+}
+
+/// Rows of `msm_mixed` of `fixed` fixed base terms and `variable` variable
+/// base terms, over 256 bits scalars and without its padding: the scalar muls,
+/// and an add of the results copied next to each other per term after the first.
+pub fn rows_for_msm_mixed(fixed: usize, variable: usize) -> usize {
+    fixed * rows_for_generator_mul(256)
+        + variable * rows_for_point_mul(256)
+        + (fixed + variable).saturating_sub(1) * 4
+}
+
+/// Rows of an x-only scalar mul, including the scalar decomposition and the
+/// load of the base.
+pub fn rows_for_x_only_point_mul(bits: usize) -> usize {
+    rows_for_decompose_scalar(bits) + 1 + rows_for_x_only_ladder(bits)
+}
+
+/// Rows of the y recovery of `x_only_point_mul_with_y`: 2 loads, 10 adds or
+/// muls, and the point.
+pub const RECOVER_Y_ROWS: usize = 2 + 10 * 2 + 1;
+
+/// Rows of `load_instance_point_compressed`, without its padding: the row
+/// of the two limbs of the encoding, their decompositions, the recomposition
+/// of x with its canonical check, the point, and the decomposition of y with
+/// its canonical check and recomposition.
+pub const LOAD_COMPRESSED_POINT_ROWS: usize = 1
+    + 2 * DECOMPOSE_U128_ROWS
+    + (1 + 2 + 1 + 2)
+    + CANONICAL_SCALAR_ROWS
+    + (1 + 2 + 2)
+    + 1
+    + 2 * DECOMPOSE_U128_ROWS
+    + CANONICAL_SCALAR_ROWS
+    + (2 + 2);
+
+/// Rows of `normalize`: the inverse of Z, 3 muls and the point.
+pub const NORMALIZE_ROWS: usize = 1 + 3 * 2 + 1;
+
+/// Rows of a batch of `n` Pedersen commitments: the load of the second
+/// generator, then per commitment the two scalar decompositions, a fixed base
+/// mul of the generator, a variable base mul of the second generator and an
+/// add of the two terms copied next to each other.
+pub fn rows_for_pedersen_commit_batch(n: usize) -> usize {
+    1 + n
+        * (2 * rows_for_decompose_scalar(256)
+            + rows_for_generator_mul_with_bits(256)
+            + rows_for_point_mul_with_bits(256)
+            + 4)
+}
+
+/// Rows of `prove_blinded_key`, without its padding: the loads of the second
+/// generator and of the key, the decomposition of the blinding, a variable
+/// base mul of the second generator and an add of the two terms copied next
+/// to each other.
+pub const BLINDED_KEY_ROWS: usize = 2 + 2 * DECOMPOSE_U128_ROWS + (1 + 4 * 256 + 3) + 4;
+
+/// Rows of `check_folded_commitment` of `n` points, without its padding: the
+/// split of the challenge into its limbs, then per point after the first a
+/// variable base mul by the 128 bits low limb and an add of the two terms
+/// copied next to each other.
+pub fn rows_for_folded_commitment(n: usize) -> usize {
+    DECOMPOSE_FIELD_LIMBS_ROWS + n.saturating_sub(1) * (rows_for_point_mul_with_bits(128) + 4)
+}
+
+/// Rows of an elementwise op over vectors of `n` assigned cells, i.e.,
+/// `vec_add`, `vec_mul` or `vec_scale`: an add or a mul per element.
+pub fn rows_for_vec_op(n: usize) -> usize {
+    2 * n
+}
+
+/// Rows of `safe_div`: the load of the inverse and the flag, and 4 muls and an add.
+pub const SAFE_DIV_ROWS: usize = 1 + 5 * 2;
+
+/// Rows of a naive MSM of `n` points: `n` scalar muls, and `n - 1` additions
+/// of the results copied next to each other.
+pub fn rows_for_msm(n: usize, bits: usize) -> usize {
+    n * rows_for_point_mul(bits) + n.saturating_sub(1) * 4
+}
+
+/// The smallest k whose circuit fits `rows` rows.
+pub fn min_k(rows: usize) -> u32 {
+    (rows + RESERVED_ROWS).next_power_of_two().trailing_zeros()
+}
+
+/// An op of the chip, for `RowEstimate`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Op {
+    LoadPoint,
+    LoadPointUnchecked,
+    ConditionalPointAdd,
+    /// Subtracts a point, in the rows of `ConditionalPointAdd`.
+    ConditionalPointSub,
+    /// Adds a point, copying the condition.
+    ConditionalPointAddAssigned,
+    /// Subtracts a point, copying the condition.
+    ConditionalPointSubAssigned,
+    /// Swaps two points, copying the condition and the points.
+    Cswap,
+    PointDouble,
+    /// A step of the double-then-add loop, on an accumulator on the latest row.
+    DoubleAndAddStep,
+    /// Normalizes a projective point to affine coordinates.
+    Normalize,
+    /// Decomposes a 256 bits scalar.
+    DecomposeScalar,
+    /// Decomposes a 256 bits scalar, checking the decomposition is canonical.
+    DecomposeScalarCanonical,
+    /// Decomposes a scalar of the given number of bits.
+    DecomposeScalarSized(usize),
+    /// Splits a field element into its canonical 128 bits limbs.
+    DecomposeFieldLimbs,
+    /// Multiplies a point by a 256 bits scalar.
+    PointMul,
+    /// Multiplies a point by a scalar of the given number of bits.
+    PointMulSized(usize),
+    /// Multiplies a point by an already decomposed 256 bits scalar.
+    PointMulFromBits,
+    /// Multiplies the generator by a 256 bits scalar.
+    GeneratorMul,
+    /// Multiplies a base known at synthesis time by a 256 bits scalar.
+    FixedBaseMul,
+    /// The x coordinate of a point times a 256 bits scalar, via the x-only ladder.
+    XOnlyPointMul,
+    /// Multiplies a point by a 256 bits scalar, via the x-only ladder.
+    XOnlyPointMulWithY,
+    LoadField,
+    LoadTwoFields,
+    Add,
+    Mul,
+    /// Divides two field elements, flagging a zero divisor.
+    SafeDiv,
+    PartialBitDecomp,
+    DecomposeU128,
+    /// The padding of a region; an upper bound.
+    Pad,
+}
+
+impl Op {
+    /// The rows used by the op.
+    pub fn rows(&self) -> usize {
+        match self {
+            Self::LoadPoint
+            | Self::LoadPointUnchecked
+            | Self::ConditionalPointAdd
+            | Self::ConditionalPointSub
+            | Self::PointDouble
+            | Self::LoadField
+            | Self::LoadTwoFields => 1,
+            Self::DecomposeScalar => rows_for_decompose_scalar(256),
+            Self::DecomposeScalarCanonical => {
+                rows_for_decompose_scalar(256) + CANONICAL_SCALAR_ROWS
+            }
+            Self::DecomposeScalarSized(bits) => rows_for_decompose_scalar(*bits),
+            Self::DecomposeFieldLimbs => DECOMPOSE_FIELD_LIMBS_ROWS,
+            Self::PointMul => rows_for_point_mul(256),
+            Self::PointMulSized(bits) => rows_for_point_mul(*bits),
+            Self::PointMulFromBits => rows_for_point_mul_from_bits(256),
+            Self::GeneratorMul | Self::FixedBaseMul => rows_for_generator_mul(256),
+            Self::XOnlyPointMul => rows_for_x_only_point_mul(256),
+            Self::XOnlyPointMulWithY => rows_for_x_only_point_mul(256) + RECOVER_Y_ROWS,
+            Self::ConditionalPointAddAssigned | Self::ConditionalPointSubAssigned => 2,
+            Self::Cswap => 5,
+            Self::DoubleAndAddStep => 4,
+            Self::Normalize => NORMALIZE_ROWS,
+            Self::Add | Self::Mul => 2,
+            Self::SafeDiv => SAFE_DIV_ROWS,
+            Self::PartialBitDecomp => 3,
+            Self::DecomposeU128 => DECOMPOSE_U128_ROWS,
+            Self::Pad => 5,
+        }
+    }
+}
+
+/// A dry run of a planned sequence of ops.
+///
+/// ```ignore
+/// let estimate = RowEstimate::new().repeat(Op::PointMul, 2).op(Op::Pad);
+/// println!("{} rows, k = {}", estimate.total_rows(), estimate.min_k());
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RowEstimate {
+    rows: usize,
+}
+
+impl RowEstimate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an op.
+    pub fn op(self, op: Op) -> Self {
+        self.extra_rows(op.rows())
+    }
+
+    /// Adds `n` times the same op.
+    pub fn repeat(self, op: Op, n: usize) -> Self {
+        self.extra_rows(op.rows() * n)
+    }
+
+    /// Adds the given number of rows, e.g., for the regions of other chips.
+    pub fn extra_rows(self, rows: usize) -> Self {
+        Self {
+            rows: self.rows + rows,
+        }
+    }
+
+    /// The total number of rows.
+    pub fn total_rows(&self) -> usize {
+        self.rows
+    }
+
+    /// The smallest k whose circuit fits the ops.
+    pub fn min_k(&self) -> u32 {
+        min_k(self.rows)
+    }
+}
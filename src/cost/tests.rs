@@ -0,0 +1,143 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::cost::min_k;
+use crate::cost::rows_for_folded_commitment;
+use crate::cost::rows_for_generator_mul;
+use crate::cost::rows_for_msm;
+use crate::cost::rows_for_msm_mixed;
+use crate::cost::rows_for_pedersen_commit_batch;
+use crate::cost::rows_for_point_mul;
+use crate::cost::rows_for_x_only_point_mul;
+use crate::cost::Op;
+use crate::cost::RowEstimate;
+use crate::cost::BLINDED_KEY_ROWS;
+use crate::ec_gates::NativeECOps;
+use crate::region_ctx::RegionCtx;
+use crate::testing::run_gadget;
+use crate::ArithOps;
+
+#[test]
+fn test_row_estimate() {
+    let mut rng = test_rng();
+    let s = Value::known(Fr::random(&mut rng));
+    let short = Value::known(Fr::from(0xdead_beef));
+    let p = Value::known(G1::random(&mut rng).to_affine());
+    let h = G1::random(&mut rng).to_affine();
+    let f = Value::known(Fq::random(&mut rng));
+
+    let run = |k| {
+        run_gadget::<G1Affine, Fq, _>(k, |chip, ctx| {
+            let config = chip.config();
+            let mut start = ctx.offset();
+            let mut check = |ctx: &RegionCtx<Fq>, op: Op| {
+                assert_eq!(ctx.offset() - start, op.rows(), "{:?}", op);
+                start = ctx.offset();
+            };
+
+            let p_assigned = chip.load_private_point(ctx, config, p)?;
+            check(ctx, Op::LoadPoint);
+            chip.point_double(ctx, config, &p_assigned)?;
+            check(ctx, Op::PointDouble);
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            check(ctx, Op::LoadField);
+            chip.cswap(ctx, config, &bit, &p_assigned, &p_assigned)?;
+            check(ctx, Op::Cswap);
+            let f_cell = chip.load_private_field(ctx, config, f)?;
+            check(ctx, Op::LoadField);
+            chip.add(ctx, config, f, f)?;
+            check(ctx, Op::Add);
+            chip.mul(ctx, config, f, f)?;
+            check(ctx, Op::Mul);
+            chip.safe_div(ctx, config, &f_cell, &f_cell)?;
+            check(ctx, Op::SafeDiv);
+            chip.decompose_scalar(ctx, config, s)?;
+            check(ctx, Op::DecomposeScalar);
+            chip.decompose_scalar_canonical(ctx, config, s)?;
+            check(ctx, Op::DecomposeScalarCanonical);
+            chip.decompose_scalar_sized(ctx, config, short, 64)?;
+            check(ctx, Op::DecomposeScalarSized(64));
+            chip.decompose_field_limbs(ctx, config, &f_cell)?;
+            check(ctx, Op::DecomposeFieldLimbs);
+            chip.point_mul(ctx, config, p, s)?;
+            check(ctx, Op::PointMul);
+            chip.point_mul_sized(ctx, config, p, short, 192)?;
+            check(ctx, Op::PointMulSized(192));
+            chip.generator_mul(ctx, config, s)?;
+            check(ctx, Op::GeneratorMul);
+            chip.fixed_base_mul(ctx, config, &h, s)?;
+            check(ctx, Op::FixedBaseMul);
+            chip.x_only_point_mul(ctx, config, p, s)?;
+            check(ctx, Op::XOnlyPointMul);
+            chip.x_only_point_mul_with_y(ctx, config, p, s)?;
+            check(ctx, Op::XOnlyPointMulWithY);
+
+            chip.pad(ctx, config)?;
+            assert!(ctx.offset() - start <= Op::Pad.rows());
+            Ok(())
+        })
+    };
+
+    let estimate = RowEstimate::new()
+        .op(Op::LoadPoint)
+        .op(Op::PointDouble)
+        .op(Op::LoadField)
+        .op(Op::Cswap)
+        .op(Op::LoadField)
+        .op(Op::Add)
+        .op(Op::Mul)
+        .op(Op::SafeDiv)
+        .op(Op::DecomposeScalar)
+        .op(Op::DecomposeScalarCanonical)
+        .op(Op::DecomposeScalarSized(64))
+        .op(Op::DecomposeFieldLimbs)
+        .op(Op::PointMul)
+        .op(Op::PointMulSized(192))
+        .op(Op::GeneratorMul)
+        .op(Op::FixedBaseMul)
+        .op(Op::XOnlyPointMul)
+        .op(Op::XOnlyPointMulWithY)
+        .op(Op::Pad);
+
+    let prover = run(estimate.min_k()).unwrap();
+    prover.assert_satisfied();
+    assert!(run(estimate.min_k() - 1).is_err());
+}
+
+#[test]
+fn test_row_counts() {
+    // matches the 1221 rows of a point mul
+    assert_eq!(rows_for_point_mul(256), 1221);
+    assert_eq!(rows_for_msm(1, 256), 1221);
+    assert_eq!(rows_for_msm(2, 256), 2 * 1221 + 4);
+    assert_eq!(rows_for_point_mul(64), 357);
+    assert_eq!(Op::PointMulSized(256).rows(), Op::PointMul.rows());
+    assert_eq!(rows_for_generator_mul(256), 708);
+    assert_eq!(rows_for_msm_mixed(1, 2), 708 + 2 * 1221 + 2 * 4);
+    assert_eq!(rows_for_msm_mixed(0, 2), rows_for_msm(2, 256));
+    assert_eq!(rows_for_x_only_point_mul(256), 960);
+    assert_eq!(rows_for_pedersen_commit_batch(2), 1 + 2 * 1932);
+    assert_eq!(BLINDED_KEY_ROWS, 1226);
+    assert_eq!(rows_for_folded_commitment(1), 404);
+    assert_eq!(rows_for_folded_commitment(3), 404 + 2 * 520);
+    assert_eq!(
+        rows_for_point_mul(256),
+        Op::DecomposeScalar.rows() + Op::PointMulFromBits.rows()
+    );
+    assert_eq!(
+        RowEstimate::new().repeat(Op::Add, 3),
+        RowEstimate::new().extra_rows(6)
+    );
+
+    assert_eq!(min_k(1), 4);
+    assert_eq!(min_k(8), 4);
+    assert_eq!(min_k(9), 5);
+}
@@ -0,0 +1,28 @@
+//! The curves the crate is instantiated with, named through this module only.
+//!
+//! With `pse`, the curves are the ones of the halo2curves release that
+//! `halo2_proofs` is built against, so that the chip follows the release of
+//! the fork without a pin of its own; with `zcash`, they are the Pasta curves
+//! re-exported by `halo2_proofs`. Moving to a release that renames or moves a
+//! curve module only changes the paths below.
+
+// the curve of the KZG keys
+#[cfg(all(feature = "pse", feature = "std"))]
+pub(crate) use halo2_proofs::halo2curves::bn256;
+#[cfg(feature = "pse")]
+pub(crate) use halo2_proofs::halo2curves::grumpkin;
+#[cfg(feature = "zcash")]
+pub(crate) use halo2_proofs::pasta::pallas;
+
+/// The embedded curve of the backend, whose base field is the circuit field:
+/// Grumpkin over the scalar field of BN254 with `pse`, Pallas with `zcash`.
+#[cfg(feature = "pse")]
+pub(crate) type EmbeddedCurve = grumpkin::G1Affine;
+#[cfg(feature = "zcash")]
+pub(crate) type EmbeddedCurve = pallas::Affine;
+
+/// The base field of `EmbeddedCurve`, i.e., the circuit field.
+#[cfg(feature = "pse")]
+pub(crate) type EmbeddedBase = grumpkin::Fq;
+#[cfg(feature = "zcash")]
+pub(crate) type EmbeddedBase = pallas::Base;
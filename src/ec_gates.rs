@@ -1,20 +1,41 @@
+use alloc::format;
+use alloc::vec::Vec;
+
 use halo2_proofs::circuit::AssignedCell;
-use halo2_proofs::circuit::Region;
 use halo2_proofs::circuit::Value;
-use halo2_proofs::halo2curves::ff::PrimeField;
-use halo2_proofs::halo2curves::group::Curve;
-use halo2_proofs::halo2curves::CurveAffine;
-use halo2_proofs::plonk::Error;
 
+use crate::backend::Curve;
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
 use crate::chip::ECChip;
 use crate::config::ECConfig;
+use crate::config::EC_ADD;
+use crate::config::EC_DOUBLE;
+use crate::error::NativeEccError;
+use crate::region_ctx::RegionCtx;
+use crate::tables::fixed_base_multiples;
+use crate::tables::neg_generator_times_2_to;
+use crate::tables::neg_generator_times_2_to_256;
+use crate::tables::SCALAR_MUL_BITS;
+use crate::util::annotation;
+use crate::util::bits_value;
+use crate::util::coordinates;
+use crate::util::coordinates_value;
+use crate::util::decompress_y;
+use crate::util::double_then_add_witness;
+use crate::util::field_decompose;
 use crate::util::field_decompose_u128;
-use crate::util::leak;
-use crate::util::neg_generator_times_2_to_256;
+use crate::util::fixed_base_witness;
+use crate::util::le_bits;
+use crate::util::point_mul_native;
+use crate::util::scalar_with_top_bit;
+use crate::util::window_table;
+use crate::util::x_only_ladder_witness;
 use crate::ArithOps;
 use crate::AssignedECPoint;
+use crate::AssignedProjectivePoint;
 
-#[cfg(test)]
+#[cfg(all(test, feature = "pse"))]
 mod tests;
 
 pub trait NativeECOps<C, F>
@@ -33,13 +54,12 @@ where
     /// Will allocate the (x, y) to columns (a, b); and use column c to enforce point is on curve
     fn load_private_point(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        p: &C,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error> {
-        let p = self.load_private_point_unchecked(region, config, p, offset)?;
-        self.enforce_on_curve(region, config, &p, offset)?;
+        p: Value<C>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        let p = self.load_private_point_unchecked(ctx, config, p)?;
+        self.enforce_on_curve(ctx, config, &p)?;
         Ok(p)
     }
 
@@ -49,20 +69,73 @@ where
     /// Will allocate the (x, y) to columns (a, b)
     fn load_private_point_unchecked(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// Loads points without the on curve check, one row each.
+    ///
+    /// For points already validated elsewhere in the circuit, e.g., outputs of
+    /// previous ops, that the caller binds with copy constraints. The points
+    /// are checked on curve during witness generation in debug builds only.
+    fn load_points_trusted(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        p: &C,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error>;
+        ps: &[Value<C>],
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError>;
 
     /// For an input pair (x, y), enforces the point is on curve.
     fn enforce_on_curve(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &Self::AssignedECPoint,
+    ) -> Result<(), NativeEccError>;
+
+    /// Checks, during witness generation only, that res is p * s, recomputing
+    /// p * s natively; a debug op, e.g., for a point mul whose result fails a
+    /// constraint further down.
+    ///
+    /// Adds no constraint and uses no row. Fails with `WitnessMismatch`,
+    /// reporting both points, if p, s and res are known and res is not p * s.
+    fn verify_mul_witness(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &Self::AssignedECPoint,
+        s: Value<C::ScalarExt>,
+        res: &Self::AssignedECPoint,
+    ) -> Result<(), NativeEccError>;
+
+    /// Constrains p to the coordinates of the constant point q, e.g., a
+    /// generator or a public key fixed by the protocol, via the fixed column
+    /// of the constants. Uses no row.
+    ///
+    /// Fails with `PointAtInfinity` if q is the identity.
+    fn assert_equal_const(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p: &Self::AssignedECPoint,
-        offset: &mut usize,
-    ) -> Result<(), Error>;
+        q: &C,
+    ) -> Result<(), NativeEccError>;
+
+    /// Normalizes a projective point (X : Y : Z) to the affine point
+    /// (X / Z, Y / Z), witnessing the inverse of Z.
+    ///
+    /// Ensures
+    /// - Z * Z^-1 = 1, i.e., the point is not the identity
+    /// - the result is on curve
+    ///
+    /// Requires the mul gate, on top of the on curve check.
+    fn normalize(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &AssignedProjectivePoint<C, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
 
     /// Input p1 and p2 that are on the curve.
     /// Input an additional bit b.
@@ -71,58 +144,410 @@ where
     /// - p3 = p1 + p2 if b == 1.
     /// - p3 = p1 if b == 0.
     ///
-    /// Caller must check p1 and p2 are on curve and b is a bit.
+    /// Ensures
+    /// - b is a bit
+    /// - p1 is not ±p2 if b == 1
+    /// - p3 is on curve
+    ///
+    /// Caller must check p1 and p2 are on curve.
+    ///
+    /// The formula is incomplete: for b == 1, p1 must not be ±p2. p1 + p1 is
+    /// not determined by the line through the points, use `point_double`, and
+    /// p1 - p1 is the identity, which has no (x, y) representation. The gate
+    /// rejects both with the inverse of x2 - x1, witnessed in the b cell of the
+    /// condition row, so the condition must be loaded alone on its row, e.g.,
+    /// with `load_private_field`; the witness generator returns
+    /// `NativeEccError::IncompleteAddition` for both.
     fn conditional_point_add(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// `conditional_point_add` for a condition known at synthesis time.
+    ///
+    /// Returns p1 without using any row if b is false. Otherwise p1 and p2
+    /// must be on the two rows above, as for `conditional_point_add`, and the
+    /// condition is assigned as the constant 1.
+    fn conditional_point_add_const(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p1: &Self::AssignedECPoint,
         p2: &Self::AssignedECPoint,
+        b: bool,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// `conditional_point_add` for a condition assigned anywhere, e.g., by
+    /// another gadget: it is copied into the condition row, below p1 and p2,
+    /// which must be on the two latest rows.
+    ///
+    /// The gate constrains the copy, hence b, to be a bit.
+    fn conditional_point_add_assigned(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// Input an additional bit b.
+    ///
+    /// Returns
+    /// - p3 = p1 - p2 if b == 1.
+    /// - p3 = p1 if b == 0.
+    ///
+    /// Ensures
+    /// - b is a bit
+    /// - p3 is on curve
+    ///
+    /// Caller must check p1 and p2 are on curve. The rows are those of
+    /// `conditional_point_add`; the sign of p2 is folded into the gate, so -p2
+    /// takes no row.
+    ///
+    /// The formula is incomplete: for b == 1, p1 must not be ±p2, as for
+    /// `conditional_point_add`.
+    fn conditional_point_sub(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// `conditional_point_sub` for a condition assigned anywhere, copied into
+    /// the condition row as for `conditional_point_add_assigned`.
+    fn conditional_point_sub_assigned(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// Input a bit b and two points p and q.
+    ///
+    /// Returns
+    /// - (q, p) if b == 1.
+    /// - (p, q) if b == 0.
+    ///
+    /// Ensures
+    /// - b is a bit
+    ///
+    /// The operands are copied, so they may be anywhere in the circuit; the
+    /// outputs are on curve if p and q are.
+    #[allow(clippy::type_complexity)]
+    fn cswap(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
         b: &AssignedCell<F, F>,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error>;
+        p: &Self::AssignedECPoint,
+        q: &Self::AssignedECPoint,
+    ) -> Result<(Self::AssignedECPoint, Self::AssignedECPoint), NativeEccError>;
 
     /// Return p2 = p1 + p1
     fn point_double(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p1: &Self::AssignedECPoint,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error>;
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// The window table `[1 * p, ..., (2^window_bits - 1) * p]` of a
+    /// windowed mul by a variable base p.
+    ///
+    /// The entries are computed off-circuit and witnessed, then validated
+    /// in-circuit: the first entry is a copy of p, the second its double and
+    /// each next entry the sum of the previous one and p.
+    ///
+    /// Caller must check p is on curve.
+    fn load_window_table(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &Self::AssignedECPoint,
+        window_bits: usize,
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError>;
+
+    /// One step of the double-then-add loop of `point_mul`: returns
+    /// 2 * acc + bit * base, in 4 rows.
+    ///
+    /// acc must be on the latest row, as for `point_double`, e.g., the result
+    /// of the previous step; base and bit are copied, so they may be anywhere.
+    /// The identity has no cells: a loop starts from an offset point, e.g., the
+    /// generator, whose multiple is subtracted at the end, as in `point_mul`.
+    ///
+    /// Ensures
+    /// - bit is a bit
+    ///
+    /// Caller must check acc and base are on curve.
+    fn double_and_add_step(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        acc: &Self::AssignedECPoint,
+        base: &Self::AssignedECPoint,
+        bit: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
 
     /// Decompose a scalar into a vector of boolean Cells
     fn decompose_scalar<S>(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Decompose a scalar into a vector of boolean Cells, along with the cells of
+    /// its low and high 128 bits limbs, s = low + 2^128 * high, that the bits
+    /// recompose to.
+    #[allow(clippy::type_complexity)]
+    fn decompose_scalar_with_limbs<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// `decompose_scalar_with_limbs` for a canonical decomposition.
+    ///
+    /// Ensures
+    /// - the bits recompose to an integer smaller than the order of the
+    ///   scalar field, so that a scalar has a single decomposition
+    ///
+    /// Requires the add and mul gates, on top of the partial bit decomposition.
+    #[allow(clippy::type_complexity)]
+    fn decompose_scalar_canonical<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Splits a field element of the circuit into the cells of its [low, high]
+    /// 128 bits limbs, x = low + 2^128 * high: the in-circuit counterpart of
+    /// `field_decompose_u128`, which returns them as (high, low).
+    ///
+    /// Ensures
+    /// - the limbs are 128 bits
+    /// - the limbs recompose to x without wrapping around the modulus, so
+    ///   that x has a single split
+    ///
+    /// Requires the add and mul gates, on top of the partial bit decomposition.
+    fn decompose_field_limbs(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        x: &AssignedCell<F, F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError>;
+
+    /// Decompose a scalar of `num_bits` bits, 0 < num_bits <= 256, into a
+    /// vector of `num_bits` boolean Cells.
+    ///
+    /// Only the 128 bits limbs holding the bits are decomposed; the bits of
+    /// the limbs above `num_bits` are constrained to 0, so that the scalar is
+    /// range checked to [0, 2^num_bits).
+    fn decompose_scalar_sized<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        s: &C::ScalarExt,
-        offset: &mut usize,
-    ) -> Result<Vec<AssignedCell<F, F>>, Error>
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
     where
         S: PrimeField<Repr = [u8; 32]>,
         C: CurveAffine<ScalarExt = S>;
 
+    /// Copies the little endian bits of a scalar, assigned outside the chip,
+    /// e.g., by a hash chip, into the rows of `decompose_u128`, 128 bits per
+    /// limb, the bits of the last limb beyond the input being 0.
+    ///
+    /// Ensures
+    /// - the bits are binary
+    ///
+    /// Returns the copies of the bits, for `point_mul_from_bits`, and the
+    /// limbs they recompose to, least significant first.
+    #[allow(clippy::type_complexity)]
+    fn import_scalar_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<(Vec<AssignedCell<F, F>>, Vec<AssignedCell<F, F>>), NativeEccError>;
+
     /// Point mul via double-then-add method
+    ///
+    /// Ensures
+    /// - the result is on curve, by the gate of the last conditional add;
+    ///   there is no need to call `enforce_on_curve` on it
     fn point_mul<S>(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Point mul via double-then-add method, returning along with p * s the
+    /// [low, high] limbs of s that the bits of the ladder recompose to, e.g.,
+    /// to bind the mul to a committed scalar with copy constraints.
+    #[allow(clippy::type_complexity)]
+    fn point_mul_with_scalar<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Self::AssignedECPoint, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// `point_mul` for a scalar of `num_bits` bits, decomposed by
+    /// `decompose_scalar_sized`; the ladder runs over `num_bits` bits only.
+    fn point_mul_sized<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Point mul by a scalar known at synthesis time.
+    ///
+    /// The scalar is not decomposed in the circuit, and the conditional adds
+    /// of the 0 bits are skipped: 256 doubles and 3 rows per 1 bit.
+    fn point_mul_const<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: C::ScalarExt,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Point mul via double-then-add method, for the little endian bits of a
+    /// scalar returned by `decompose_scalar` or `decompose_scalar_sized`.
+    ///
+    /// The bit cells are copied, so one decomposition can be shared by the
+    /// muls of several points by the same scalar.
+    fn point_mul_from_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<Self::AssignedECPoint, NativeEccError>;
+
+    /// Fixed base mul of the generator, via the fixed base add gate:
+    /// one conditional add per bit and no doubling.
+    fn generator_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Fixed base mul of a base known at synthesis time, e.g., a second
+    /// generator: as for `generator_mul`, the multiples 2^i * base are read
+    /// from the fixed columns, one conditional add per bit and no doubling.
+    fn fixed_base_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        base: &C,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Pedersen commitments value * G + blinding * H of a batch of openings,
+    /// laid out one after the other, for the generator G and a second
+    /// generator h known at synthesis time.
+    ///
+    /// h is loaded and checked on curve once for the whole batch, and the
+    /// multiples of G are read from the fixed columns, as in `generator_mul`.
+    /// The two terms are summed with the incomplete addition: an opening whose
+    /// value * G is ±blinding * h is an `IncompleteAddition` error.
+    fn pedersen_commit_batch<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        p: &C,
-        s: &C::ScalarExt,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error>
+        h: C,
+        openings: &[(Value<C::ScalarExt>, Value<C::ScalarExt>)],
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError>
     where
         S: PrimeField<Repr = [u8; 32]>,
         C: CurveAffine<ScalarExt = S>;
 
-    /// Pad the row with empty cells.
-    fn pad(
+    /// x-only scalar mul via the Montgomery ladder, carrying only the x
+    /// coordinates of its two accumulators: 3 rows per bit, instead of the 4
+    /// rows of the double-then-add loop. Returns the x coordinate of p * s,
+    /// e.g., the shared secret of an ECDH.
+    ///
+    /// The curve has no Montgomery form (its order is odd), so the ladder uses
+    /// the x-only double and differential add of y^2 = x^3 + b. s is offset
+    /// by a multiple of the order so that its top bit is set, and the ladder
+    /// starts from (p, 2p).
+    ///
+    /// s * p and (s + 1) * p must not be the identity.
+    fn x_only_point_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// `x_only_point_mul`, reconstructing the y coordinate of p * s at the
+    /// end from p and the x coordinate of (s + 1) * p.
+    ///
+    /// Requires the add and mul gates, on top of the x-only ladder.
+    fn x_only_point_mul_with_y<S>(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        offset: &mut usize,
-    ) -> Result<(), Error>;
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>;
+
+    /// Pads with empty cells the rows queried by the gates beyond the last assigned row.
+    fn pad(&self, ctx: &mut RegionCtx<F>, config: &Self::Config) -> Result<(), NativeEccError>;
 }
 
 impl<C, F> NativeECOps<C, F> for ECChip<C, F>
@@ -139,49 +564,140 @@ where
     /// Will allocate the (x, y) to columns (a, b)
     fn load_private_point_unchecked(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        p: &C,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error> {
-        let p = p.coordinates().unwrap();
-        let x = region.assign_advice(|| "x", config.a, *offset, || Value::known(*p.x()))?;
-        let y = region.assign_advice(|| "y", config.b, *offset, || Value::known(*p.y()))?;
-        let res = Self::AssignedECPoint::new(x, y, *offset);
-        *offset += 1;
-        Ok(res)
+        p: Value<C>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        self.assign_point(ctx, config, p, "load point", ("x", "y"))
+    }
+
+    fn load_points_trusted(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        ps: &[Value<C>],
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError> {
+        ps.iter()
+            .map(|p| {
+                p.map(|p| debug_assert!(bool::from(p.is_on_curve()), "trusted point not on curve"));
+                self.assign_point(ctx, config, *p, "load trusted point", ("x", "y"))
+            })
+            .collect()
     }
 
     /// For an input pair (x, y), enforces the point is on curve.
     /// The point must locate at (offset - 1) row
     fn enforce_on_curve(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p: &Self::AssignedECPoint,
-        offset: &mut usize,
-    ) -> Result<(), Error> {
-        assert_eq!(
-            p.offset,
-            *offset - 1,
-            "on curve: p is not the latest assigned cells"
-        );
+    ) -> Result<(), NativeEccError> {
+        config.require_gate(config.gates.on_curve, "on curve")?;
+
+        // p must be the latest assigned cells
+        let offset = ctx.offset();
+        if p.offset + 1 != offset {
+            return Err(NativeEccError::UnexpectedOffset {
+                op: "on curve",
+                expected: offset.saturating_sub(1),
+                actual: p.offset,
+            });
+        }
 
         #[cfg(feature = "verbose")]
         {
             println!(
                 "[on curve check]           selector: {}, point: {}",
-                *offset - 1,
+                offset - 1,
                 p.offset
             );
         }
 
         // | is on curve |   1  |       1      | 0  | 0  | 1  | y1^2 = x1^3 - C::b()
-        config.q_ec_enable.enable(region, *offset - 1)?;
-        config.q3.enable(region, *offset - 1)?;
+        ctx.enable_selector(&config.q_ec_enable, offset - 1)?;
+        ctx.enable_selector(&config.q3, offset - 1)?;
         Ok(())
     }
 
+    fn verify_mul_witness(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        _config: &Self::Config,
+        p: &Self::AssignedECPoint,
+        s: Value<C::ScalarExt>,
+        res: &Self::AssignedECPoint,
+    ) -> Result<(), NativeEccError> {
+        ctx.traced("verify mul witness", |_| {
+            let mut check = Ok(());
+            p.value()
+                .zip(s)
+                .zip(res.coordinates())
+                .map(|((p, s), actual)| {
+                    let expected = coordinates(&point_mul_native(p, s)).ok();
+                    if expected != Some(actual) {
+                        check = Err(NativeEccError::WitnessMismatch {
+                            op: "point mul",
+                            offset: res.offset,
+                            expected: match expected {
+                                Some(xy) => format!("{:?}", xy),
+                                None => "the identity".into(),
+                            },
+                            actual: format!("{:?}", actual),
+                        });
+                    }
+                });
+            check
+        })
+    }
+
+    fn assert_equal_const(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        _config: &Self::Config,
+        p: &Self::AssignedECPoint,
+        q: &C,
+    ) -> Result<(), NativeEccError> {
+        ctx.traced("assert equal const", |ctx| {
+            let (x, y) = coordinates(q)?;
+            ctx.constrain_constant(p.x.cell(), x)?;
+            ctx.constrain_constant(p.y.cell(), y)?;
+            Ok(())
+        })
+    }
+
+    fn normalize(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &AssignedProjectivePoint<C, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("normalize", |ctx| {
+            config.require_gate(config.gates.mul, "mul")?;
+
+            let mut res = Ok(());
+            let z_inv = p.z.value().map(|z| {
+                Option::from(z.invert()).unwrap_or_else(|| {
+                    res = Err(NativeEccError::PointAtInfinity);
+                    F::ZERO
+                })
+            });
+            res?;
+            let z_inv = self.load_private_field(ctx, config, z_inv)?;
+            let one = self.mul_cells(ctx, config, &p.z, &z_inv)?;
+            ctx.constrain_constant(one.cell(), F::ONE)?;
+
+            let x = self.mul_cells(ctx, config, &p.x, &z_inv)?;
+            let y = self.mul_cells(ctx, config, &p.y, &z_inv)?;
+            let x = ctx.copy_advice(|| annotation("normalize", "x"), config.a, &x)?;
+            let y = ctx.copy_advice(|| annotation("normalize", "y"), config.b, &y)?;
+            let point = AssignedECPoint::new(x, y, ctx.offset());
+            ctx.next();
+            self.enforce_on_curve(ctx, config, &point)?;
+            Ok(point)
+        })
+    }
+
     /// Input p1 and p2 that are on the curve.
     /// Input an additional bit b.
     ///
@@ -190,52 +706,163 @@ where
     /// - p3 = p1 if b == 0.
     ///
     /// Ensures
+    /// - b is a bit
     /// - p3 is on curve
     ///
-    /// Caller must check p1 and p2 are on curve and b is a bit.
+    /// Caller must check p1 and p2 are on curve.
     fn conditional_point_add(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p1: &Self::AssignedECPoint,
         p2: &Self::AssignedECPoint,
         b: &AssignedCell<F, F>,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error> {
-        //  index  |  a   |  b
-        //  -------|------|------
-        //         | p1.x | p1.y
-        //         | p2.x | p2.y
-        //         | cond |
-        //  offset | p3.x | p3.y
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("conditional add", |ctx| {
+            let mut res = Ok(());
+            let p3 = p1
+                .value()
+                .zip(p2.value())
+                .zip(b.value())
+                .map(|((p1, p2), b)| {
+                    if *b == F::ZERO {
+                        p1
+                    } else if p1 == p2 || p1 == -p2 {
+                        res = Err(NativeEccError::IncompleteAddition);
+                        p1
+                    } else {
+                        (p1 + p2).to_affine()
+                    }
+                });
+            res?;
+            self.assign_conditional_point_add(ctx, config, p1, p2, b, p3)
+        })
+    }
 
-        // |      ec add |   4  |       1      | 1  | 0  | 0  | (x1, y1), (x2, y2) and (x3, -y3) are on a same line
-        config.q_ec_enable.enable(region, *offset - 3)?;
-        config.q1.enable(region, *offset - 3)?;
+    fn conditional_point_add_const(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: bool,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        if !b {
+            return Ok(p1.clone());
+        }
+        let bit = self.load_private_field(ctx, config, Value::known(F::ONE))?;
+        ctx.constrain_constant(bit.cell(), F::ONE)?;
+        self.conditional_point_add(ctx, config, p1, p2, &bit)
+    }
 
-        let p1_witness = p1.witness();
-        let p2_witness = p2.witness();
-        let p3_witness = (p1_witness + p2_witness).to_affine();
-        let bit = leak(&b.value());
+    fn conditional_point_add_assigned(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        let bit = self.copy_condition(ctx, config, b)?;
+        self.conditional_point_add(ctx, config, p1, p2, &bit)
+    }
 
-        let p3 = if bit == F::ZERO {
-            self.load_private_point_unchecked(region, config, &p1_witness, offset)?
-        } else {
-            self.load_private_point_unchecked(region, config, &p3_witness, offset)?
-        };
+    fn conditional_point_sub(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("conditional sub", |ctx| {
+            let mut res = Ok(());
+            let p3 = p1
+                .value()
+                .zip(p2.value())
+                .zip(b.value())
+                .map(|((p1, p2), b)| {
+                    if *b == F::ZERO {
+                        p1
+                    } else if p1 == p2 || p1 == -p2 {
+                        res = Err(NativeEccError::IncompleteAddition);
+                        p1
+                    } else {
+                        (p1 - p2).to_affine()
+                    }
+                });
+            res?;
+            self.assign_conditional_point_sub(ctx, config, p1, p2, b, p3)
+        })
+    }
 
-        #[cfg(feature = "verbose")]
-        {
-            println!(
-                "[conditional point add]    selector: {}, points: {} {} {}",
-                *offset - 3,
-                p1.offset,
-                p2.offset,
-                p3.offset
-            );
-        }
+    fn conditional_point_sub_assigned(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p1: &Self::AssignedECPoint,
+        p2: &Self::AssignedECPoint,
+        b: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        let bit = self.copy_condition(ctx, config, b)?;
+        self.conditional_point_sub(ctx, config, p1, p2, &bit)
+    }
 
-        Ok(p3)
+    fn cswap(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        b: &AssignedCell<F, F>,
+        p: &Self::AssignedECPoint,
+        q: &Self::AssignedECPoint,
+    ) -> Result<(Self::AssignedECPoint, Self::AssignedECPoint), NativeEccError> {
+        ctx.traced("cswap", |ctx| {
+            //  index  |  a   |  b
+            //  -------|------|------
+            //  offset | cond |  0
+            //         | p.x  | p.y
+            //         | q.x  | q.y
+            //         | r.x  | r.y
+            //         | s.x  | s.y
+            config.require_gate(config.gates.cswap, "cswap")?;
+
+            // |       cswap |   5  |    0    |       1      | 0  | 1  | 0  | (r, s) = (p, q) swapped iff cond
+            let offset = ctx.offset();
+            ctx.enable_selector(&config.q_ec_enable, offset)?;
+            ctx.enable_selector(&config.q2, offset)?;
+            let bit = ctx.copy_advice(|| annotation("cswap", "cond"), config.a, b)?;
+            ctx.assign_advice(
+                || annotation("cswap", "zero"),
+                config.b,
+                Value::known(F::ZERO),
+            )?;
+            ctx.next();
+            let p = self.copy_point(ctx, config, p)?;
+            let q = self.copy_point(ctx, config, q)?;
+
+            // r = p + cond * (q - p) and s = p + q - r, for either coordinate
+            let swap = |p: Value<&F>, q: Value<&F>| {
+                let r = p
+                    .zip(q)
+                    .zip(bit.value())
+                    .map(|((p, q), b)| *p + *b * (*q - p));
+                (r, p.zip(q).zip(r).map(|((p, q), r)| *p + q - r))
+            };
+            let (r_x, s_x) = swap(p.x.value(), q.x.value());
+            let (r_y, s_y) = swap(p.y.value(), q.y.value());
+            let r = self.assign_coordinates(ctx, config, r_x.zip(r_y), "cswap", ("r.x", "r.y"))?;
+            let s = self.assign_coordinates(ctx, config, s_x.zip(s_y), "cswap", ("s.x", "s.y"))?;
+
+            #[cfg(feature = "verbose")]
+            {
+                println!(
+                    "[cswap]                    selector: {}, points: {} {} {} {}",
+                    offset, p.offset, q.offset, r.offset, s.offset
+                );
+            }
+
+            Ok((r, s))
+        })
     }
 
     /// Return p2 = p1 + p1
@@ -246,151 +873,1471 @@ where
     /// Caller must check p1 is on curve.
     fn point_double(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
         p1: &Self::AssignedECPoint,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error> {
-        assert_eq!(
-            p1.offset,
-            *offset - 1,
-            "point double: p is not the latest assigned cells"
-        );
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("point double", |ctx| {
+            let p2 = p1.value().map(|p1| (p1 + p1).to_affine());
+            self.assign_point_double(ctx, config, p1, p2)
+        })
+    }
 
-        // |   ec double |   2  |       1      | 0  | 1  | 0  | (x1, y1) and (x3, -y3) are on a tangential line of the curve
-        config.q_ec_enable.enable(region, *offset - 1)?;
-        config.q2.enable(region, *offset - 1)?;
-        let p1_witness = p1.witness();
-        let p2 = (p1_witness + p1_witness).to_affine();
-        let p2 = self.load_private_point_unchecked(region, config, &p2, offset)?;
+    fn load_window_table(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: &Self::AssignedECPoint,
+        window_bits: usize,
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError> {
+        ctx.traced("window table", |ctx| {
+            let table = p.value().map(|p| window_table(p, window_bits));
+            self.assign_window_table(ctx, config, p, window_bits, table)
+        })
+    }
 
-        #[cfg(feature = "verbose")]
-        {
-            println!(
-                "[point double]             selector: {}, points: {} {}",
-                *offset - 1,
-                p1.offset,
-                p2.offset,
-            );
-        }
-
-        Ok(p2)
+    fn double_and_add_step(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        acc: &Self::AssignedECPoint,
+        base: &Self::AssignedECPoint,
+        bit: &AssignedCell<F, F>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("double and add step", |ctx| {
+            let mut res = Ok(());
+            let witness =
+                acc.value()
+                    .zip(base.value())
+                    .zip(bit.value())
+                    .map(|((acc, base), bit)| {
+                        let double = (acc + acc).to_affine();
+                        let sum = if *bit == F::ZERO {
+                            double
+                        } else if double == base || double == -base {
+                            res = Err(NativeEccError::IncompleteAddition);
+                            double
+                        } else {
+                            (double + base).to_affine()
+                        };
+                        (double, sum)
+                    });
+            res?;
+            self.assign_double_and_add_step(
+                ctx,
+                config,
+                acc,
+                base,
+                bit,
+                witness.map(|(double, _)| double),
+                witness.map(|(_, sum)| sum),
+            )
+        })
     }
 
     /// Decompose a scalar into a vector of boolean Cells
     fn decompose_scalar<S>(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        s: &C::ScalarExt,
-        offset: &mut usize,
-    ) -> Result<Vec<AssignedCell<F, F>>, Error>
+        s: Value<C::ScalarExt>,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
     where
         S: PrimeField<Repr = [u8; 32]>,
         C: CurveAffine<ScalarExt = S>,
     {
-        let (high, low) = field_decompose_u128(s);
-        let (low_cells, _res) = self.decompose_u128(region, config, &low, offset)?;
-        let (high_cells, _res) = self.decompose_u128(region, config, &high, offset)?;
-        let res = [low_cells.as_slice(), high_cells.as_slice()].concat();
+        let (bits, _limbs) = self.decompose_scalar_with_limbs(ctx, config, s)?;
+        Ok(bits)
+    }
 
-        Ok(res)
+    fn decompose_scalar_with_limbs<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("decompose scalar", |ctx| {
+            let high_low = s.map(|s| field_decompose_u128(&s));
+            let (low_cells, low) =
+                self.decompose_u128(ctx, config, high_low.map(|(_, low)| low))?;
+            let (high_cells, high) =
+                self.decompose_u128(ctx, config, high_low.map(|(high, _)| high))?;
+            let res = [low_cells.as_slice(), high_cells.as_slice()].concat();
+
+            Ok((res, [low, high]))
+        })
+    }
+
+    fn decompose_scalar_canonical<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("decompose scalar canonical", |ctx| {
+            config.require_gate(config.gates.add, "add")?;
+            config.require_gate(config.gates.mul, "mul")?;
+
+            let (bits, limbs) = self.decompose_scalar_with_limbs(ctx, config, s)?;
+            self.enforce_canonical_limbs::<S>(ctx, config, &limbs)?;
+            Ok((bits, limbs))
+        })
+    }
+
+    fn decompose_field_limbs(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        x: &AssignedCell<F, F>,
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError> {
+        ctx.traced("decompose field limbs", |ctx| {
+            let (_, limbs) = self.assign_field_limbs(ctx, config, x)?;
+            Ok(limbs)
+        })
+    }
+
+    fn decompose_scalar_sized<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        if num_bits == 0 || num_bits > SCALAR_MUL_BITS {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: SCALAR_MUL_BITS,
+                actual: num_bits,
+            });
+        }
+
+        ctx.traced("decompose scalar sized", |ctx| {
+            // the high limb is not decomposed for up to 128 bits, it must be 0
+            let mut res = Ok(());
+            let high_low = s.map(|s| {
+                let (high, low) = field_decompose_u128(&s);
+                if num_bits <= 128 && high != 0 {
+                    res = Err(NativeEccError::DecompositionMismatch);
+                }
+                (high, low)
+            });
+            res?;
+            let (mut bits, _low) =
+                self.decompose_u128(ctx, config, high_low.map(|(_, low)| low))?;
+            if num_bits > 128 {
+                let (high_bits, _high) =
+                    self.decompose_u128(ctx, config, high_low.map(|(high, _)| high))?;
+                bits.extend(high_bits);
+            }
+
+            // range check: the bits above num_bits are 0
+            for bit in bits.drain(num_bits..) {
+                ctx.constrain_constant(bit.cell(), F::ZERO)?;
+            }
+            Ok(bits)
+        })
     }
 
     /// Point mul via double-then-add method
     // todo: assigned point -> point
     fn point_mul<S>(
         &self,
-        region: &mut Region<F>,
+        ctx: &mut RegionCtx<F>,
         config: &Self::Config,
-        p: &C,
-        s: &C::ScalarExt,
-        offset: &mut usize,
-    ) -> Result<Self::AssignedECPoint, Error>
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
     where
         S: PrimeField<Repr = [u8; 32]>,
         C: CurveAffine<ScalarExt = S>,
     {
-        let gen = C::generator();
-        let bits = self.decompose_scalar(region, config, s, offset)?;
+        ctx.traced("point mul", |ctx| {
+            let bits = self.decompose_scalar(ctx, config, s)?;
+            self.point_mul_from_bits(ctx, config, p, &bits)
+        })
+    }
+
+    fn point_mul_with_scalar<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<(Self::AssignedECPoint, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("point mul", |ctx| {
+            let (bits, limbs) = self.decompose_scalar_with_limbs(ctx, config, s)?;
+            let res = self.point_mul_from_bits(ctx, config, p, &bits)?;
+            Ok((res, limbs))
+        })
+    }
+
+    fn import_scalar_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<(Vec<AssignedCell<F, F>>, Vec<AssignedCell<F, F>>), NativeEccError> {
+        if bits.is_empty() || bits.len() > SCALAR_MUL_BITS {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: SCALAR_MUL_BITS,
+                actual: bits.len(),
+            });
+        }
+
+        ctx.traced("import scalar bits", |ctx| {
+            let mut res = Vec::with_capacity(bits.len());
+            let mut limbs = Vec::with_capacity(bits.len().div_ceil(128));
+            for chunk in bits.chunks(128) {
+                // a bit other than 0 or 1 is witnessed as 1, and fails its copy
+                let limb = bits_value(chunk).map(|bits| {
+                    bits.iter()
+                        .rev()
+                        .fold(0u128, |acc, b| (acc << 1) | u128::from(*b))
+                });
+                let (limb_bits, limb) = self.decompose_u128(ctx, config, limb)?;
+                for (i, bit) in limb_bits.into_iter().enumerate() {
+                    match chunk.get(i) {
+                        Some(b) => {
+                            ctx.constrain_equal(bit.cell(), b.cell())?;
+                            res.push(bit);
+                        }
+                        None => ctx.constrain_constant(bit.cell(), F::ZERO)?,
+                    }
+                }
+                limbs.push(limb);
+            }
+            Ok((res, limbs))
+        })
+    }
+
+    fn point_mul_sized<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+        num_bits: usize,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("point mul sized", |ctx| {
+            let bits = self.decompose_scalar_sized(ctx, config, s, num_bits)?;
+            self.point_mul_from_bits(ctx, config, p, &bits)
+        })
+    }
+
+    fn point_mul_const<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: C::ScalarExt,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("point mul const", |ctx| {
+            let p_assigned = self.load_private_point(ctx, config, p)?;
+            let mut res = self.load_generator(ctx, config)?;
+
+            // the same `double-then-add` loop as `point_mul_with_bits`,
+            // with the adds of the 0 bits folded away
+            for b in le_bits(&s).rev() {
+                res = self.point_double(ctx, config, &res)?;
+                if b {
+                    let p_copied = self.copy_point(ctx, config, &p_assigned)?;
+                    res = self.conditional_point_add_const(ctx, config, &res, &p_copied, true)?;
+                }
+            }
+
+            // now we subtract 2^256 * generator from res
+            self.subtract_offset_generator(ctx, config, &res, SCALAR_MUL_BITS)
+        })
+    }
+
+    fn point_mul_from_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<Self::AssignedECPoint, NativeEccError> {
+        ctx.traced("point mul from bits", |ctx| {
+            let p_assigned = self.load_private_point(ctx, config, p)?;
+            self.point_mul_with_bits(ctx, config, &p_assigned, bits)
+        })
+    }
+
+    fn generator_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("generator mul", |ctx| {
+            let bits = self.decompose_scalar(ctx, config, s)?;
+            self.generator_mul_with_bits(ctx, config, &bits)
+        })
+    }
+
+    fn fixed_base_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        base: &C,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("fixed base mul", |ctx| {
+            let bits = self.decompose_scalar(ctx, config, s)?;
+            self.fixed_base_mul_with_bits(ctx, config, base, &bits)
+        })
+    }
+
+    fn pedersen_commit_batch<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        h: C,
+        openings: &[(Value<C::ScalarExt>, Value<C::ScalarExt>)],
+    ) -> Result<Vec<Self::AssignedECPoint>, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("pedersen commit batch", |ctx| {
+            // the second generator, shared by the whole batch
+            let h_assigned = self.load_private_point(ctx, config, Value::known(h))?;
+            self.assert_equal_const(ctx, config, &h_assigned, &h)?;
+
+            openings
+                .iter()
+                .map(|(value, blinding)| {
+                    let bits = self.decompose_scalar(ctx, config, *value)?;
+                    let value_term = self.generator_mul_with_bits(ctx, config, &bits)?;
+                    let bits = self.decompose_scalar(ctx, config, *blinding)?;
+                    let blinding_term =
+                        self.point_mul_with_bits(ctx, config, &h_assigned, &bits)?;
+
+                    let value_term = self.copy_point(ctx, config, &value_term)?;
+                    let blinding_term = self.copy_point(ctx, config, &blinding_term)?;
+                    self.conditional_point_add_const(ctx, config, &value_term, &blinding_term, true)
+                })
+                .collect()
+        })
+    }
+
+    fn x_only_point_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("x-only point mul", |ctx| {
+            let (_, [x, _]) = self.assign_x_only_point_mul(ctx, config, p, s)?;
+            Ok(x)
+        })
+    }
+
+    fn x_only_point_mul_with_y<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &Self::Config,
+        p: Value<C>,
+        s: Value<C::ScalarExt>,
+    ) -> Result<Self::AssignedECPoint, NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        ctx.traced("x-only point mul", |ctx| {
+            config.require_gate(config.gates.add, "add")?;
+            config.require_gate(config.gates.mul, "mul")?;
+
+            let (p, xs) = self.assign_x_only_point_mul(ctx, config, p, s)?;
+            self.recover_y(ctx, config, &p, &xs)
+        })
+    }
+
+    /// Pads with empty cells the rows queried by the gates beyond the last assigned row.
+    ///
+    /// The number of rows depends on the last enabled selector and on the
+    /// rotations queried by the configured gates, i.e., `max_rotation() + 1` rows
+    /// at most: 5 with the cswap gate, which queries rotation 4.
+    fn pad(&self, ctx: &mut RegionCtx<F>, config: &Self::Config) -> Result<(), NativeEccError> {
+        let end = ctx
+            .last_selector_offset()
+            .map_or(0, |offset| offset + config.max_rotation() + 1);
+        while ctx.offset() < end {
+            ctx.assign_advice(|| annotation("pad", "a"), config.a, Value::known(F::ZERO))?;
+            ctx.assign_advice(|| annotation("pad", "b"), config.b, Value::known(F::ZERO))?;
+            ctx.next();
+        }
+        Ok(())
+    }
+}
+
+impl<C, F> ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    /// `conditional_point_add` with the witness p3 computed by the caller.
+    #[cfg_attr(not(feature = "verbose"), allow(unused_variables))]
+    fn assign_conditional_point_add(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+        p3: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        //  index  |  a   |  b
+        //  -------|------|------
+        //         | p1.x | p1.y
+        //         | p2.x | p2.y
+        //         | cond | inv of p2.x - p1.x
+        //  offset | p3.x | p3.y
+        config.require_gate(config.gates.ec_add, "ec add")?;
+
+        // |      ec add |   4  |    1    |       0      | 0  | 0  | 0  | (x1, y1), (x2, y2) and (x3, -y3) are on a same line
+        let offset = ctx.offset();
+        self.enable_ec_op(ctx, config, "conditional add", offset - 3, EC_ADD)?;
+        #[cfg(feature = "audit")]
+        self.audit.check_add(
+            "conditional add",
+            offset - 3,
+            config.curve_b,
+            p1.coordinates(),
+            p2.coordinates(),
+            b.value().copied(),
+        );
+
+        self.assign_add_inverse(ctx, config, offset - 1, p1.coordinates(), p2.coordinates())?;
+        #[cfg(feature = "complete-addition")]
+        self.assign_add_slope(ctx, config, offset - 2, p1.coordinates(), p2.coordinates())?;
+
+        let p3 = self.assign_point(ctx, config, p3, "conditional add", ("x3", "y3"))?;
+
+        #[cfg(feature = "verbose")]
+        {
+            println!(
+                "[conditional point add]    selector: {}, points: {} {} {}",
+                offset - 3,
+                p1.offset,
+                p2.offset,
+                p3.offset
+            );
+        }
+
+        Ok(p3)
+    }
+
+    /// `conditional_point_sub` with the witness p3 computed by the caller.
+    fn assign_conditional_point_sub(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p1: &AssignedECPoint<C, F>,
+        p2: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+        p3: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        //  index  |  a   |  b
+        //  -------|------|------
+        //         | p1.x | p1.y
+        //         | p2.x | p2.y
+        //         | cond | inv of p2.x - p1.x
+        //  offset | p3.x | p3.y
+        config.require_gate(config.gates.ec_sub, "ec sub")?;
+
+        let offset = ctx.offset();
+        ctx.enable_selector(&config.q_ec_sub, offset - 3)?;
+        #[cfg(feature = "audit")]
+        self.audit.check_add(
+            "conditional sub",
+            offset - 3,
+            config.curve_b,
+            p1.coordinates(),
+            p2.coordinates(),
+            b.value().copied(),
+        );
+
+        self.assign_add_inverse(ctx, config, offset - 1, p1.coordinates(), p2.coordinates())?;
+        // the slope of p1 - p2 is the slope of the add of -p2
+        #[cfg(feature = "complete-addition")]
+        self.assign_add_slope(
+            ctx,
+            config,
+            offset - 2,
+            p1.coordinates(),
+            p2.coordinates().map(|(x2, y2)| (x2, -y2)),
+        )?;
+
+        self.assign_point(ctx, config, p3, "conditional sub", ("x3", "y3"))
+    }
+
+    /// Copies a condition into the current row, the condition row of a
+    /// conditional add or sub; the gate constrains it to be a bit.
+    fn copy_condition(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        b: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        let bit = self.load_private_field(ctx, config, b.value().copied())?;
+        ctx.constrain_equal(bit.cell(), b.cell())?;
+        Ok(bit)
+    }
+
+    /// `point_double` with the witness p2 computed by the caller.
+    fn assign_point_double(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p1: &AssignedECPoint<C, F>,
+        p2: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        config.require_gate(config.gates.ec_double, "ec double")?;
+
+        // p1 must be the latest assigned cells
+        let offset = ctx.offset();
+        if p1.offset + 1 != offset {
+            return Err(NativeEccError::UnexpectedOffset {
+                op: "point double",
+                expected: offset.saturating_sub(1),
+                actual: p1.offset,
+            });
+        }
+
+        // |   ec double |   2  |    2    |       0      | 0  | 0  | 0  | (x1, y1) and (x3, -y3) are on a tangential line of the curve
+        self.enable_ec_op(ctx, config, "point double", offset - 1, EC_DOUBLE)?;
+        #[cfg(feature = "complete-addition")]
+        self.assign_double_slope(ctx, config, offset - 1, p1.coordinates())?;
+        #[cfg(feature = "audit")]
+        self.audit
+            .check_double("point double", offset - 1, config.curve_b, p1.coordinates());
+        let p2 = self.assign_point(ctx, config, p2, "point double", ("x3", "y3"))?;
+
+        #[cfg(feature = "verbose")]
+        {
+            println!(
+                "[point double]             selector: {}, points: {} {}",
+                offset - 1,
+                p1.offset,
+                p2.offset,
+            );
+        }
+
+        Ok(p2)
+    }
+
+    /// `decompose_field_limbs`, also returning the little endian bits of the
+    /// low limb, e.g., for a 128 bits truncation of x.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn assign_field_limbs(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        x: &AssignedCell<F, F>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError> {
+        config.require_gate(config.gates.add, "add")?;
+        config.require_gate(config.gates.mul, "mul")?;
 
-        let p_assigned = self.load_private_point(region, config, p, offset)?;
-        let gen_assigned = self.load_private_point(region, config, &gen, offset)?;
+        let high_low = x.value().map(field_decompose_u128);
+        let (bits, low) = self.decompose_u128(ctx, config, high_low.map(|(_, low)| low))?;
+        let (_, high) = self.decompose_u128(ctx, config, high_low.map(|(high, _)| high))?;
+        self.enforce_canonical_limbs::<F>(ctx, config, &[low.clone(), high.clone()])?;
+
+        // x = low + 2^128 * high
+        let two_to_128 = self.load_constant_field(ctx, config, F::from_u128(1 << 64).square())?;
+        let sum = self.mul_cells(ctx, config, &high, &two_to_128)?;
+        let sum = self.add_cells(ctx, config, &low, &sum)?;
+        ctx.constrain_equal(sum.cell(), x.cell())?;
+
+        Ok((bits, [low, high]))
+    }
+
+    /// Ensures the limbs [low, high] of a scalar, each of 128 bits, recompose
+    /// to an integer at most m = r - 1, where r is the order of the scalar field.
+    ///
+    /// With m = m_hi * 2^128 + m_lo, we witness the limbs of m - s, where
+    /// `borrow` is the carry between the limbs, and range check them:
+    /// - low + d_lo = m_lo + borrow * 2^128
+    /// - high + borrow + d_hi = m_hi
+    /// - borrow is a bit, d_lo and d_hi are 128 bits
+    ///
+    /// All the values are below 2^130, so none of the equations wraps around.
+    fn enforce_canonical_limbs<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        [low, high]: &[AssignedCell<F, F>; 2],
+    ) -> Result<(), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+    {
+        let (m_hi, m_lo) = field_decompose_u128(&-S::ONE);
+
+        let limbs = low.value().zip(high.value()).map(|(low, high)| {
+            let low = field_decompose_u128(low).1;
+            let high = field_decompose_u128(high).1;
+            let (d_lo, borrow) = m_lo.overflowing_sub(low);
+            let d_hi = m_hi.wrapping_sub(high).wrapping_sub(borrow as u128);
+            (d_lo, d_hi, borrow)
+        });
+        let (_, d_lo) = self.decompose_u128(ctx, config, limbs.map(|(d_lo, _, _)| d_lo))?;
+        let (_, d_hi) = self.decompose_u128(ctx, config, limbs.map(|(_, d_hi, _)| d_hi))?;
+
+        // borrow is a bit
+        let borrow = self.load_private_field(
+            ctx,
+            config,
+            limbs.map(|(_, _, borrow)| F::from(borrow as u64)),
+        )?;
+        let borrow_square = self.mul_cells(ctx, config, &borrow, &borrow)?;
+        ctx.constrain_equal(borrow_square.cell(), borrow.cell())?;
+
+        // low + d_lo = m_lo + borrow * 2^128
+        let two_to_128 = self.load_constant_field(ctx, config, F::from_u128(1 << 64).square())?;
+        let carry = self.mul_cells(ctx, config, &borrow, &two_to_128)?;
+        let m_lo = self.load_constant_field(ctx, config, F::from_u128(m_lo))?;
+        let lhs = self.add_cells(ctx, config, low, &d_lo)?;
+        let rhs = self.add_cells(ctx, config, &m_lo, &carry)?;
+        ctx.constrain_equal(lhs.cell(), rhs.cell())?;
+
+        // high + borrow + d_hi = m_hi
+        let sum = self.add_cells(ctx, config, high, &borrow)?;
+        let sum = self.add_cells(ctx, config, &sum, &d_hi)?;
+        ctx.constrain_constant(sum.cell(), F::from_u128(m_hi))?;
+
+        Ok(())
+    }
+
+    /// Reduces d = low + 2^128 * high, of 128 bits limbs, modulo the order q
+    /// of the scalar field, e.g., a 256 bits digest to a scalar.
+    ///
+    /// Returns the canonical bits and limbs of e = d mod q.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn reduce_scalar_limbs<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        d: &[AssignedCell<F, F>; 2],
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        let two_to_128 = S::from_u128(1 << 64).square();
+        let e = d[0].value().zip(d[1].value()).map(|(low, high)| {
+            let low = field_decompose_u128(low).1;
+            let high = field_decompose_u128(high).1;
+            S::from_u128(low) + S::from_u128(high) * two_to_128
+        });
+        self.assign_reduced_scalar_limbs::<S>(ctx, config, d, e)
+    }
+
+    /// `reduce_scalar_limbs` for a claimed reduction e.
+    ///
+    /// With d = k * q + e, the quotient k and the carry c between the limbs
+    /// are witnessed as 4 bits values, and
+    /// - k * q_lo + e_lo = d_lo + c * 2^128
+    /// - k * q_hi + e_hi + c = d_hi
+    ///
+    /// where e is canonical and all the limbs are 128 bits. All the values
+    /// are below 2^133, so none of the equations wraps around.
+    ///
+    /// Requires the add and mul gates, on top of the partial bit decomposition.
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn assign_reduced_scalar_limbs<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        d: &[AssignedCell<F, F>; 2],
+        e: Value<S>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        let (q_lo, q_hi) = Self::scalar_order_limbs::<S>();
+        let two_to_128 = F::from_u128(1 << 64).square();
+
+        // the quotient and the carry, found by search; none if e is not d mod q
+        let quotient = d[0]
+            .value()
+            .zip(d[1].value())
+            .zip(e)
+            .map(|((d_lo, d_hi), e)| {
+                let (e_hi, e_lo) = field_decompose::<F, S>(&e);
+                (0..16u64)
+                    .find_map(|k| {
+                        let k = F::from(k);
+                        let c = *d_hi - k * q_hi - e_hi;
+                        let is_nibble = (0..16u64).any(|j| c == F::from(j));
+                        (is_nibble && k * q_lo + e_lo == *d_lo + c * two_to_128).then_some((k, c))
+                    })
+                    .unwrap_or((F::ZERO, F::ZERO))
+            });
+        self.assign_reduced_scalar_limbs_with_quotient::<S>(ctx, config, d, e, quotient)
+    }
+
+    /// `assign_reduced_scalar_limbs` for a claimed quotient and carry (k, c).
+    #[allow(clippy::type_complexity)]
+    pub(crate) fn assign_reduced_scalar_limbs_with_quotient<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        [d_lo, d_hi]: &[AssignedCell<F, F>; 2],
+        e: Value<S>,
+        quotient: Value<(F, F)>,
+    ) -> Result<(Vec<AssignedCell<F, F>>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+        C: CurveAffine<ScalarExt = S>,
+    {
+        config.require_gate(config.gates.add, "add")?;
+        config.require_gate(config.gates.mul, "mul")?;
+
+        let (q_lo, q_hi) = Self::scalar_order_limbs::<S>();
+        let two_to_128 = F::from_u128(1 << 64).square();
+
+        let (bits, [e_lo, e_hi]) = self.decompose_scalar_canonical::<S>(ctx, config, e)?;
+        let k = self.load_nibble(ctx, config, quotient.map(|(k, _)| k))?;
+        let c = self.load_nibble(ctx, config, quotient.map(|(_, c)| c))?;
+
+        // k * q_lo + e_lo = d_lo + c * 2^128
+        let q_lo = self.load_constant_field(ctx, config, q_lo)?;
+        let lhs = self.mul_cells(ctx, config, &k, &q_lo)?;
+        let lhs = self.add_cells(ctx, config, &lhs, &e_lo)?;
+        let two_to_128 = self.load_constant_field(ctx, config, two_to_128)?;
+        let rhs = self.mul_cells(ctx, config, &c, &two_to_128)?;
+        let rhs = self.add_cells(ctx, config, d_lo, &rhs)?;
+        ctx.constrain_equal(lhs.cell(), rhs.cell())?;
+
+        // k * q_hi + e_hi + c = d_hi
+        let q_hi = self.load_constant_field(ctx, config, q_hi)?;
+        let lhs = self.mul_cells(ctx, config, &k, &q_hi)?;
+        let lhs = self.add_cells(ctx, config, &lhs, &e_hi)?;
+        let lhs = self.add_cells(ctx, config, &lhs, &c)?;
+        ctx.constrain_equal(lhs.cell(), d_hi.cell())?;
+
+        Ok((bits, [e_lo, e_hi]))
+    }
+
+    /// The (low, high) 128 bits limbs of the order q of the scalar field.
+    fn scalar_order_limbs<S: PrimeField>() -> (F, F) {
+        // q = (r - 1) + 1
+        let (m_hi, m_lo) = field_decompose_u128(&-S::ONE);
+        let (q_lo, carry) = m_lo.overflowing_add(1);
+        let q_hi = m_hi + u128::from(carry);
+        (F::from_u128(q_lo), F::from_u128(q_hi))
+    }
+
+    /// Loads a value below 16, via a partial bit decomposition of its 4 bits
+    /// with a 0 accumulator.
+    fn load_nibble(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        v: Value<F>,
+    ) -> Result<AssignedCell<F, F>, NativeEccError> {
+        let low = v.map(|v| field_decompose_u128(&v).1);
+        let bit = |i: u32| low.map(|low| F::from(((low >> i) & 1) as u64));
+        let cells = self.partial_bit_decomp(
+            ctx,
+            config,
+            &[bit(0), bit(1), bit(2), bit(3), Value::known(F::ZERO), v],
+        )?;
+        ctx.constrain_constant(cells[4].cell(), F::ZERO)?;
+        Ok(cells[5].clone())
+    }
+
+    /// Decompresses the point of a 32 bytes compressed encoding, given as the
+    /// cells of its low and high 16 bytes as little endian integers, see
+    /// `util::compress`.
+    ///
+    /// Ensures
+    /// - the limbs are 128 bits, and bit 254 of the encoding is unset
+    /// - the x coordinate, the encoding without its top bit, is canonical
+    /// - (x, y) is on curve, with y canonical and of the parity of the top bit
+    pub(crate) fn assign_decompressed_point(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        low: &AssignedCell<F, F>,
+        high: &AssignedCell<F, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError>
+    where
+        F: PrimeField<Repr = [u8; 32]>,
+    {
+        config.require_gate(config.gates.add, "add")?;
+        config.require_gate(config.gates.mul, "mul")?;
+
+        let limb = |cell: &AssignedCell<F, F>| cell.value().map(|f| field_decompose_u128(f).1);
+        let (_, low_acc) = self.decompose_u128(ctx, config, limb(low))?;
+        ctx.constrain_equal(low_acc.cell(), low.cell())?;
+        let (high_bits, high_acc) = self.decompose_u128(ctx, config, limb(high))?;
+        ctx.constrain_equal(high_acc.cell(), high.cell())?;
+        ctx.constrain_constant(high_bits[126].cell(), F::ZERO)?;
+        let sign = &high_bits[127];
+
+        // x = low + (high - sign * 2^127) * 2^128, canonical
+        let two_to_127 = self.load_constant_field(ctx, config, F::from_u128(1 << 127))?;
+        let sign_term = self.mul_cells(ctx, config, sign, &two_to_127)?;
+        let x_high = self.load_private_field(
+            ctx,
+            config,
+            high.value().copied() - sign_term.value().copied(),
+        )?;
+        let sum = self.add_cells(ctx, config, &x_high, &sign_term)?;
+        ctx.constrain_equal(sum.cell(), high.cell())?;
+        self.enforce_canonical_limbs::<F>(ctx, config, &[low.clone(), x_high.clone()])?;
+        let two_to_128 = self.load_constant_field(ctx, config, F::from_u128(1 << 64).square())?;
+        let x = self.mul_cells(ctx, config, &x_high, &two_to_128)?;
+        let x = self.add_cells(ctx, config, low, &x)?;
+
+        // the y coordinate of the parity of the sign
+        let mut res = Ok(());
+        let y = x.value().zip(sign.value()).map(|(x, sign)| {
+            decompress_y(*x, config.curve_b, *sign == F::ONE).unwrap_or_else(|| {
+                res = Err(NativeEccError::NotOnCurve);
+                F::ZERO
+            })
+        });
+        res?;
+        let x = ctx.copy_advice(|| annotation("decompress", "x"), config.a, &x)?;
+        let y = ctx.assign_advice(|| annotation("decompress", "y"), config.b, y)?;
+        let p = AssignedECPoint::new(x, y, ctx.offset());
+        ctx.next();
+        self.enforce_on_curve(ctx, config, &p)?;
+
+        // y is canonical, and its lowest bit is the sign
+        let y_limbs = p.y.value().map(field_decompose_u128);
+        let (y_low_bits, y_low) = self.decompose_u128(ctx, config, y_limbs.map(|(_, low)| low))?;
+        let (_, y_high) = self.decompose_u128(ctx, config, y_limbs.map(|(high, _)| high))?;
+        self.enforce_canonical_limbs::<F>(ctx, config, &[y_low.clone(), y_high.clone()])?;
+        let y = self.mul_cells(ctx, config, &y_high, &two_to_128)?;
+        let y = self.add_cells(ctx, config, &y_low, &y)?;
+        ctx.constrain_equal(y.cell(), p.y.cell())?;
+        ctx.constrain_equal(y_low_bits[0].cell(), sign.cell())?;
+
+        Ok(p)
+    }
+
+    /// Assigns the inverse of x2 - x1 of an add (x1, y1) + (x2, y2) to the b
+    /// cell of its condition row, for the distinct x check of
+    /// `ECConfig::conditional_add_expr`.
+    ///
+    /// The inverse of 0 is assigned as 0, which fails the check if the
+    /// condition is 1.
+    fn assign_add_inverse(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        row: usize,
+        p1: Value<(F, F)>,
+        p2: Value<(F, F)>,
+    ) -> Result<(), NativeEccError> {
+        let inv = p1
+            .zip(p2)
+            .map(|((x1, _), (x2, _))| (x2 - x1).invert().unwrap_or(F::ZERO));
+        ctx.region().assign_advice(
+            || annotation("add", "inv of x2 - x1"),
+            config.b,
+            row,
+            || inv,
+        )?;
+        Ok(())
+    }
+
+    /// Assigns the slope of an add (x1, y1) + (x2, y2) to the `slope` column,
+    /// for the `complete-addition` feature; see `ECConfig::complete_add_expr`
+    /// for the rows.
+    #[cfg(feature = "complete-addition")]
+    fn assign_add_slope(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        row: usize,
+        p1: Value<(F, F)>,
+        p2: Value<(F, F)>,
+    ) -> Result<(), NativeEccError> {
+        let slope = p1
+            .zip(p2)
+            .map(|((x1, y1), (x2, y2))| (y2 - y1) * (x2 - x1).invert().unwrap_or(F::ZERO));
+        ctx.region()
+            .assign_advice(|| annotation("add", "slope"), config.slope, row, || slope)?;
+        Ok(())
+    }
+
+    /// Assigns the slope of the tangent at (x1, y1) to the `slope` column of
+    /// its row, for the `complete-addition` feature.
+    #[cfg(feature = "complete-addition")]
+    fn assign_double_slope(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        row: usize,
+        p1: Value<(F, F)>,
+    ) -> Result<(), NativeEccError> {
+        let slope = p1.map(|(x1, y1)| {
+            let inv = y1.double().invert().unwrap_or(F::ZERO);
+            x1.square() * F::from(3) * inv
+        });
+        ctx.region().assign_advice(
+            || annotation("double", "slope"),
+            config.slope,
+            row,
+            || slope,
+        )?;
+        Ok(())
+    }
+
+    /// Selects the ec add or the ec double at the given row, via `q_ec_op`.
+    ///
+    /// Both ops cannot start on the same row, e.g., p1 + 2 * p1 with 2 * p1
+    /// right below p1; copy the operands first in that case.
+    fn enable_ec_op(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        op: &'static str,
+        offset: usize,
+        value: u64,
+    ) -> Result<(), NativeEccError> {
+        let value = F::from(value);
+        if ctx
+            .fixed_selector(config.q_ec_op, offset)
+            .is_some_and(|assigned| assigned != value)
+        {
+            return Err(NativeEccError::RowConflict { op, offset });
+        }
+        ctx.enable_fixed_selector(op, config.q_ec_op, offset, value)?;
+        Ok(())
+    }
+
+    /// Assigns a point to the current row without the on curve check,
+    /// annotating its cells as operands of `op`.
+    pub(crate) fn assign_point(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: Value<C>,
+        op: &'static str,
+        (x, y): (&'static str, &'static str),
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let xy = coordinates_value(p)?;
+        self.assign_coordinates(ctx, config, xy, op, (x, y))
+    }
+
+    /// Assigns the (x, y) coordinates of a point to the current row, e.g.,
+    /// for the outputs of an op computed without the curve arithmetic.
+    fn assign_coordinates(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        xy: Value<(F, F)>,
+        op: &'static str,
+        (x, y): (&'static str, &'static str),
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let x = ctx.assign_advice(|| annotation(op, x), config.a, xy.map(|(x, _)| x))?;
+        let y = ctx.assign_advice(|| annotation(op, y), config.b, xy.map(|(_, y)| y))?;
+        let res = AssignedECPoint::new(x, y, ctx.offset());
+        ctx.next();
+        Ok(res)
+    }
+
+    /// Copies an assigned point into the current row without the on curve check.
+    pub(crate) fn copy_point(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: &AssignedECPoint<C, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let x = ctx.copy_advice(|| annotation("copy point", "x"), config.a, &p.x)?;
+        let y = ctx.copy_advice(|| annotation("copy point", "y"), config.b, &p.y)?;
+        let res = AssignedECPoint::new(x, y, ctx.offset());
+        ctx.next();
+        Ok(res)
+    }
+
+    /// `load_window_table` for a claimed table, of `2^window_bits - 1` entries.
+    pub(crate) fn assign_window_table(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: &AssignedECPoint<C, F>,
+        window_bits: usize,
+        table: Value<Vec<C>>,
+    ) -> Result<Vec<AssignedECPoint<C, F>>, NativeEccError> {
+        if window_bits == 0 {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: 1,
+                actual: 0,
+            });
+        }
+        let size = (1 << window_bits) - 1;
+        let mut res = Ok(());
+        let table = table.map(|table| {
+            if table.len() != size {
+                res = Err(NativeEccError::InvalidInputLength {
+                    expected: size,
+                    actual: table.len(),
+                });
+            }
+            table
+        });
+        res?;
+        let entry = |j: usize| table.as_ref().map(|table| table[j]);
+
+        //  index  |  a   |  b
+        //  -------|------|------
+        //         |  p.x |  p.y   1 * p
+        //         | 2p.x | 2p.y   the double of the row above
+        //         |  p.x |  p.y
+        //         |  1   | inv
+        //         | 3p.x | 3p.y   the sum of 2p and p, and so on
+        let mut entries = Vec::with_capacity(size);
+        entries.push(self.copy_point(ctx, config, p)?);
+        if size > 1 {
+            let double = self.assign_point_double(ctx, config, &entries[0], entry(1))?;
+            entries.push(double);
+        }
+        for j in 2..size {
+            let p_copied = self.copy_point(ctx, config, p)?;
+            let bit = self.load_constant_field(ctx, config, F::ONE)?;
+            let sum = self.assign_conditional_point_add(
+                ctx,
+                config,
+                &entries[j - 1],
+                &p_copied,
+                &bit,
+                entry(j),
+            )?;
+            entries.push(sum);
+        }
+        Ok(entries)
+    }
+
+    /// Point mul via double-then-add method, for an assigned point p and
+    /// the n little endian bits of the scalar, 0 < n <= 256.
+    ///
+    /// Caller must check p is on curve and the bits are all binary.
+    pub(crate) fn point_mul_with_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p_assigned: &AssignedECPoint<C, F>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        if bits.is_empty() || bits.len() > SCALAR_MUL_BITS {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: SCALAR_MUL_BITS,
+                actual: bits.len(),
+            });
+        }
+
+        let gen = C::generator();
+        let gen_assigned = self.load_generator(ctx, config)?;
 
         // we do not have a cell representation for infinity point
         // therefore we first compute
-        //  res = 2^256 * generator + p *s
-        // ans then subtract 2^256 * generator from res
+        //  res = 2^n * generator + p *s
+        // ans then subtract 2^n * generator from res
         let mut res: AssignedECPoint<C, F> = gen_assigned;
 
+        // the witness of the whole loop, with a single batch inversion
+        let witness = p_assigned
+            .value()
+            .zip(bits_value(bits))
+            .map(|(p, bits)| double_then_add_witness(gen, p, &bits));
+
         // begin the `double-then-add` loop
-        for b in bits.iter().rev() {
-            // double
-            let res_double = self.point_double(region, config, &res, offset)?;
-
-            // conditional add depending on the bit b
-            res = {
-                let p_copied = if leak(&b.value()) == F::ONE {
-                    // copy the base point cells
-                    let p_copied: AssignedECPoint<C, F> =
-                        self.load_private_point_unchecked(region, config, p, offset)?;
-                    region.constrain_equal(p_copied.x.cell(), p_assigned.x.cell())?;
-                    region.constrain_equal(p_copied.y.cell(), p_assigned.y.cell())?;
-                    p_copied
-                } else {
-                    // the point here doesn't matter but we do need to fill in the cells
-                    self.load_private_point_unchecked(region, config, &gen, offset)?
-                };
-
-                // copy the bit cell; already constraint `bit` is either 0 or 1
-                let bit = self.load_two_private_fields(
-                    region,
+        for (i, b) in bits.iter().rev().enumerate() {
+            let double = witness.as_ref().map(|w| w[2 * i]);
+            let sum = witness.as_ref().map(|w| w[2 * i + 1]);
+            res = self.assign_double_and_add_step(ctx, config, &res, p_assigned, b, double, sum)?;
+        }
+
+        // now we subtract 2^n * generator from res
+        self.subtract_offset_generator(ctx, config, &res, bits.len())
+    }
+
+    /// A step of the double-then-add loop, for the witnessed 2 * acc and
+    /// 2 * acc + b * p: the double, the copy of p, the copy of b and the
+    /// conditional add, in 4 rows.
+    ///
+    /// acc must be on the latest row.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn assign_double_and_add_step(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        acc: &AssignedECPoint<C, F>,
+        p: &AssignedECPoint<C, F>,
+        b: &AssignedCell<F, F>,
+        double: Value<C>,
+        sum: Value<C>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let double = self.assign_point_double(ctx, config, acc, double)?;
+
+        // copy the base point cells regardless of the bit, so that the
+        // layout does not depend on the witness; p is ignored if b == 0
+        let p_copied = self.copy_point(ctx, config, p)?;
+
+        // copy the bit cell; the conditional add constrains it is either 0 or 1
+        let bit = self.copy_condition(ctx, config, b)?;
+
+        self.assign_conditional_point_add(ctx, config, &double, &p_copied, &bit, sum)
+    }
+
+    /// Fixed base mul of the generator for the 256 little endian bits of the scalar.
+    ///
+    /// Caller must check the bits are all binary.
+    pub(crate) fn generator_mul_with_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.fixed_base_mul_with_bits(ctx, config, &C::generator(), bits)
+    }
+
+    /// Fixed base mul of a base known at synthesis time for the 256 little
+    /// endian bits of the scalar.
+    ///
+    /// The multiples 2^i * base are read from the fixed columns, and each
+    /// step takes 2 rows: the bit and the next accumulator.
+    ///
+    /// Caller must check the bits are all binary.
+    pub(crate) fn fixed_base_mul_with_bits(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        base: &C,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        config.require_gate(config.gates.fixed_base_add, "fixed base add")?;
+        if bits.len() != SCALAR_MUL_BITS {
+            return Err(NativeEccError::InvalidInputLength {
+                expected: SCALAR_MUL_BITS,
+                actual: bits.len(),
+            });
+        }
+
+        // as in `point_mul_with_bits`, the accumulator starts from
+        // 2^256 * generator, which is subtracted at the end
+        let (offset_generator, x, y) = neg_generator_times_2_to_256::<C, C::Base>()?;
+        let mut res = self.assign_point(
+            ctx,
+            config,
+            Value::known(-offset_generator),
+            "fixed base mul",
+            ("start x", "start y"),
+        )?;
+        ctx.constrain_constant(res.x.cell(), x)?;
+        ctx.constrain_constant(res.y.cell(), -y)?;
+
+        // |  index  |  a   |  b   | base_x | base_y |
+        // |---------|------|------|--------|--------|
+        // |  offset | acc  | acc  |   2^i * base    |
+        // |         | bit  | inv  |        |        |
+        // |         | acc' | acc' |        |        |
+        let bases = fixed_base_multiples(base, SCALAR_MUL_BITS);
+        let witness = res
+            .value()
+            .zip(bits_value(bits))
+            .map(|(start, bits)| fixed_base_witness(start, &bases, &bits));
+        for (i, (b, base)) in bits.iter().zip(bases.iter()).enumerate() {
+            let (base_x, base_y) = coordinates(base)?;
+            let offset = res.offset;
+            ctx.assign_fixed(
+                || annotation("fixed base mul", "base x"),
+                config.base_x,
+                offset,
+                base_x,
+            )?;
+            ctx.assign_fixed(
+                || annotation("fixed base mul", "base y"),
+                config.base_y,
+                offset,
+                base_y,
+            )?;
+            ctx.enable_selector(&config.q_ec_enable, offset)?;
+            ctx.enable_selector(&config.q1, offset)?;
+            #[cfg(feature = "complete-addition")]
+            {
+                ctx.assign_fixed(
+                    || annotation("fixed base mul", "fixed base flag"),
+                    config.q_fixed_base,
+                    offset,
+                    F::ONE,
+                )?;
+                self.assign_add_slope(
+                    ctx,
                     config,
-                    &leak(&b.value()),
-                    &F::ZERO,
                     offset,
+                    res.coordinates(),
+                    Value::known((base_x, base_y)),
                 )?;
-                region.constrain_equal(bit[0].cell(), b.cell())?;
+            }
+
+            // copy the bit cell; already constraint `bit` is either 0 or 1
+            let bit = self.load_private_field(ctx, config, b.value().copied())?;
+            ctx.constrain_equal(bit.cell(), b.cell())?;
+            self.assign_add_inverse(
+                ctx,
+                config,
+                offset + 1,
+                res.coordinates(),
+                Value::known((base_x, base_y)),
+            )?;
+            #[cfg(feature = "audit")]
+            self.audit.check_add(
+                "fixed base add",
+                offset,
+                config.curve_b,
+                res.coordinates(),
+                Value::known((base_x, base_y)),
+                b.value().copied(),
+            );
+
+            let sum = witness.as_ref().map(|w| w[i]);
+            res = self.assign_point(ctx, config, sum, "fixed base mul", ("x3", "y3"))?;
+        }
+
+        self.subtract_offset_generator(ctx, config, &res, SCALAR_MUL_BITS)
+    }
+
+    /// Decomposes s + c * r, the scalar with a fixed top bit of
+    /// `scalar_with_top_bit`, loads p and runs the x-only ladder over the
+    /// bits below the top bit.
+    ///
+    /// Returns p, and the x coordinates of s * p and (s + 1) * p.
+    #[allow(clippy::type_complexity)]
+    fn assign_x_only_point_mul<S>(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: Value<C>,
+        s: Value<S>,
+    ) -> Result<(AssignedECPoint<C, F>, [AssignedCell<F, F>; 2]), NativeEccError>
+    where
+        S: PrimeField<Repr = [u8; 32]>,
+    {
+        let mut res = Ok(());
+        let high_low = s.map(|s| {
+            scalar_with_top_bit(&s).unwrap_or_else(|| {
+                res = Err(NativeEccError::UnsupportedCurve);
+                (0, 0)
+            })
+        });
+        res?;
+        let (low, _) = self.decompose_u128(ctx, config, high_low.map(|(_, low)| low))?;
+        let (high, _) = self.decompose_u128(ctx, config, high_low.map(|(high, _)| high))?;
+        // the top bit is set, the ladder starts from (p, 2p)
+        ctx.constrain_constant(high[127].cell(), F::ONE)?;
+        let bits = [low.as_slice(), &high[..127]].concat();
+
+        let p = self.load_private_point(ctx, config, p)?;
+        let xs = self.x_only_ladder(ctx, config, &p, &bits)?;
+        Ok((p, xs))
+    }
+
+    /// The x-only ladder from the accumulators (p, 2p), for the little endian
+    /// bits of the scalar below its top bit, returning the x coordinates of
+    /// the accumulators s * p and (s + 1) * p, where s has its top bit set.
+    ///
+    /// p must be the latest assigned point. Caller must check p is on curve
+    /// and the bits are all binary.
+    fn x_only_ladder(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: &AssignedECPoint<C, F>,
+        bits: &[AssignedCell<F, F>],
+    ) -> Result<[AssignedCell<F, F>; 2], NativeEccError> {
+        config.require_gate(config.gates.x_only_ladder, "x-only ladder")?;
+        let p2 = self.point_double(ctx, config, p)?;
+
+        let mut res = Ok(());
+        let witness = p.value().zip(bits_value(bits)).map(|(p, bits)| {
+            x_only_ladder_witness(p, &bits).unwrap_or_else(|e| {
+                res = Err(e);
+                vec![]
+            })
+        });
+        res?;
+
+        //  index  |  a   |  b
+        //  -------|------|------
+        //  offset |  x0  |  x1
+        //         | cond |  xp
+        //         |  u   | dbl
+        //         |  x0' |  x1'
+        let mut row = ctx.offset();
+        let mut x0 = ctx.copy_advice(|| annotation("x-only ladder", "x0"), config.a, &p.x)?;
+        let mut x1 = ctx.copy_advice(|| annotation("x-only ladder", "x1"), config.b, &p2.x)?;
+        ctx.next();
+        for (i, b) in bits.iter().rev().enumerate() {
+            ctx.enable_selector(&config.q_ladder, row)?;
 
-                // conditional add
-                self.conditional_point_add(region, config, &res_double, &p_copied, &bit[0], offset)?
+            // copy the bit cell; already constraint `bit` is either 0 or 1
+            let bit = ctx.copy_advice(|| annotation("x-only ladder", "cond"), config.a, b)?;
+            ctx.copy_advice(|| annotation("x-only ladder", "xp"), config.b, &p.x)?;
+            ctx.next();
+
+            // the accumulator to double, and its double
+            let next_x0 = witness.as_ref().map(|w| w[2 * i]);
+            let next_x1 = witness.as_ref().map(|w| w[2 * i + 1]);
+            let select = |x0: Value<F>, x1: Value<F>| {
+                x0.zip(x1)
+                    .zip(bit.value())
+                    .map(|((x0, x1), b)| x0 + *b * (x1 - x0))
             };
+            let u = select(x0.value().copied(), x1.value().copied());
+            let dbl = select(next_x0, next_x1);
+            ctx.assign_advice(|| annotation("x-only ladder", "u"), config.a, u)?;
+            ctx.assign_advice(|| annotation("x-only ladder", "dbl"), config.b, dbl)?;
+            ctx.next();
+
+            row = ctx.offset();
+            x0 = ctx.assign_advice(|| annotation("x-only ladder", "x0"), config.a, next_x0)?;
+            x1 = ctx.assign_advice(|| annotation("x-only ladder", "x1"), config.b, next_x1)?;
+            ctx.next();
         }
 
-        // now we subtract 2^256 * generator from res
-        let (offset_generator, x, y) = neg_generator_times_2_to_256::<C, C::Base>();
-        let offset_generator_assigned =
-            self.load_private_point_unchecked(region, config, &offset_generator, offset)?;
-        let bit = self.load_two_private_fields(region, config, &F::ONE, &F::ZERO, offset)?;
-        res = self.conditional_point_add(
-            region,
-            config,
-            &res,
-            &offset_generator_assigned,
-            &bit[0],
-            offset,
-        )?;
-        // ensure the `subtract 2^256 * generator` cells are fixed constants
-        region.constrain_constant(offset_generator_assigned.x.cell(), x)?;
-        region.constrain_constant(offset_generator_assigned.y.cell(), y)?;
+        Ok([x0, x1])
+    }
+
+    /// Recovers s * p from p, x0 = x(s * p) and x1 = x((s + 1) * p), with
+    /// 2 yp y0 + x1 (x0 - xp)^2 = 2b + xp x0 (xp + x0), which determines y0 as
+    /// yp != 0.
+    fn recover_y(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        p: &AssignedECPoint<C, F>,
+        [x0, x1]: &[AssignedCell<F, F>; 2],
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        // d = x0 - xp, as d + xp = x0
+        let d = self.load_private_field(ctx, config, x0.value().copied() - p.x.value().copied())?;
+        let sum = self.add_cells(ctx, config, &d, &p.x)?;
+        ctx.constrain_equal(sum.cell(), x0.cell())?;
+        let d_square = self.mul_cells(ctx, config, &d, &d)?;
+        let lhs_x1 = self.mul_cells(ctx, config, x1, &d_square)?;
+
+        let product = self.mul_cells(ctx, config, &p.x, x0)?;
+        let sum = self.add_cells(ctx, config, &p.x, x0)?;
+        let rhs = self.mul_cells(ctx, config, &product, &sum)?;
+        let two_b = self.load_constant_field(ctx, config, config.curve_b.double())?;
+        let rhs = self.add_cells(ctx, config, &rhs, &two_b)?;
+
+        let y0 = rhs
+            .value()
+            .zip(lhs_x1.value())
+            .zip(p.y.value())
+            .map(|((rhs, lhs_x1), yp)| (*rhs - lhs_x1) * yp.double().invert().unwrap_or(F::ZERO));
+        let x = ctx.copy_advice(|| annotation("x-only point mul", "x"), config.a, x0)?;
+        let y = ctx.assign_advice(|| annotation("x-only point mul", "y"), config.b, y0)?;
+        let res = AssignedECPoint::new(x, y, ctx.offset());
+        ctx.next();
+        self.enforce_on_curve(ctx, config, &res)?;
+
+        let lhs_y = self.mul_cells(ctx, config, &p.y, &res.y)?;
+        let lhs_y = self.add_cells(ctx, config, &lhs_y, &lhs_y)?;
+        let lhs = self.add_cells(ctx, config, &lhs_y, &lhs_x1)?;
+        ctx.constrain_equal(lhs.cell(), rhs.cell())?;
 
         Ok(res)
     }
 
-    /// Pad the row with empty cells.
-    fn pad(
+    /// Loads the generator, the start of the double-then-add ladder,
+    /// constrained to its constant coordinates.
+    fn load_generator(
         &self,
-        region: &mut Region<F>,
-        config: &Self::Config,
-        offset: &mut usize,
-    ) -> Result<(), Error> {
-        region.assign_advice(|| "pad", config.a, *offset, || Value::known(F::ZERO))?;
-        region.assign_advice(|| "pad", config.b, *offset, || Value::known(F::ZERO))?;
-        region.assign_advice(|| "pad", config.a, *offset + 1, || Value::known(F::ZERO))?;
-        region.assign_advice(|| "pad", config.b, *offset + 1, || Value::known(F::ZERO))?;
-        region.assign_advice(|| "pad", config.a, *offset + 2, || Value::known(F::ZERO))?;
-        region.assign_advice(|| "pad", config.b, *offset + 2, || Value::known(F::ZERO))?;
-        *offset += 3;
-        Ok(())
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let gen = C::generator();
+        let (x, y) = coordinates(&gen)?;
+        let res = self.load_private_point(ctx, config, Value::known(gen))?;
+        ctx.constrain_constant(res.x.cell(), x)?;
+        ctx.constrain_constant(res.y.cell(), y)?;
+        Ok(res)
+    }
+
+    /// Returns res - 2^n * generator, the correction of the scalar muls over
+    /// n bits, whose accumulators start from 2^n * generator.
+    fn subtract_offset_generator(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        res: &AssignedECPoint<C, F>,
+        n: usize,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        self.assign_offset_correction(ctx, config, res, n, Value::known(F::ONE))
+    }
+
+    /// `subtract_offset_generator` for a claimed condition of the correction,
+    /// which is pinned to the constant 1: otherwise a prover could skip the
+    /// correction and return s * p + 2^n * generator.
+    pub(crate) fn assign_offset_correction(
+        &self,
+        ctx: &mut RegionCtx<F>,
+        config: &ECConfig<C, F>,
+        res: &AssignedECPoint<C, F>,
+        n: usize,
+        condition: Value<F>,
+    ) -> Result<AssignedECPoint<C, F>, NativeEccError> {
+        let (offset_generator, x, y) = neg_generator_times_2_to::<C, C::Base>(n)?;
+        let offset_generator_assigned = self.assign_point(
+            ctx,
+            config,
+            Value::known(offset_generator),
+            "point mul",
+            ("offset x", "offset y"),
+        )?;
+        let bit = self.load_private_field(ctx, config, condition)?;
+        ctx.constrain_constant(bit.cell(), F::ONE)?;
+        let res = self.conditional_point_add(ctx, config, res, &offset_generator_assigned, &bit)?;
+        // ensure the `subtract 2^n * generator` cells are fixed constants
+        ctx.constrain_constant(offset_generator_assigned.x.cell(), x)?;
+        ctx.constrain_constant(offset_generator_assigned.y.cell(), y)?;
+
+        Ok(res)
     }
 }
@@ -2,32 +2,51 @@ use std::ops::Mul;
 
 use ark_std::test_rng;
 use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Chip;
 use halo2_proofs::circuit::Layouter;
 use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
 use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::ff::PrimeField;
 use halo2_proofs::halo2curves::group::Curve;
 use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::keygen_vk;
 use halo2_proofs::plonk::Circuit;
 use halo2_proofs::plonk::ConstraintSystem;
 use halo2_proofs::plonk::Error;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2curves::bn256::Bn256;
 use halo2curves::grumpkin::Fq;
 use halo2curves::grumpkin::Fr;
 use halo2curves::grumpkin::G1Affine;
 use halo2curves::grumpkin::G1;
+use proptest::prelude::*;
 
 use crate::chip::ECChip;
 use crate::config::ECConfig;
+use crate::config::EC_ADD;
+use crate::config::EC_DOUBLE;
+use crate::cost::Op;
 use crate::ec_gates::NativeECOps;
+use crate::region_ctx::RegionCtx;
+use crate::testing::failed_constraints;
+use crate::testing::run_gadget;
+use crate::testing::strategies::distinct_points;
+use crate::testing::strategies::exceptional_add_operands;
+use crate::testing::strategies::points;
+use crate::util::field_decompose;
 use crate::ArithOps;
+use crate::AssignedProjectivePoint;
+use crate::NativeEccError;
 
 #[derive(Default, Debug, Clone, Copy)]
 struct ECTestCircuit {
-    s: Fr,
-    p1: G1Affine,
-    p2: G1Affine,
-    p3: G1Affine, // p1 + p2
-    p4: G1Affine, // 2p1
-    p5: G1Affine, // p1 * s
+    s: Value<Fr>,
+    p1: Value<G1Affine>,
+    p2: Value<G1Affine>,
+    p3: Value<G1Affine>, // p1 + p2
+    p4: Value<G1Affine>, // 2p1
+    p5: Value<G1Affine>, // p1 * s
 }
 
 impl Circuit<Fq> for ECTestCircuit {
@@ -51,126 +70,69 @@ impl Circuit<Fq> for ECTestCircuit {
 
         layouter.assign_region(
             || "test ec circuit",
-            |mut region| {
-                let mut offset = 0;
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
                 // unit test: `load private unchecked`, then `enforce is on curve`
                 let _p1 = {
-                    let p1 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p1,
-                        &mut offset,
-                    )?;
-                    ec_chip.enforce_on_curve(&mut region, &config, &p1, &mut offset)?;
+                    let p1 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p1)?;
+                    ec_chip.enforce_on_curve(&mut ctx, &config, &p1)?;
                     p1
                 };
                 // unit test: load private
-                let _p2 =
-                    ec_chip.load_private_point(&mut region, &config, &self.p2, &mut offset)?;
-                let p3 = ec_chip.load_private_point(&mut region, &config, &self.p3, &mut offset)?;
-                let p4 = ec_chip.load_private_point(&mut region, &config, &self.p4, &mut offset)?;
-                let p5 = ec_chip.load_private_point(&mut region, &config, &self.p5, &mut offset)?;
+                let _p2 = ec_chip.load_private_point(&mut ctx, &config, self.p2)?;
+                let p3 = ec_chip.load_private_point(&mut ctx, &config, self.p3)?;
+                let p4 = ec_chip.load_private_point(&mut ctx, &config, self.p4)?;
+                let p5 = ec_chip.load_private_point(&mut ctx, &config, self.p5)?;
 
                 // unit test: point addition with 1
                 {
-                    let p1 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p1,
-                        &mut offset,
-                    )?;
-                    let p2 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p2,
-                        &mut offset,
-                    )?;
-                    let bit = ec_chip.load_private_field(
-                        &mut region,
-                        &config,
-                        &Fq::from(1),
-                        &mut offset,
-                    )?;
-                    let p3_rec = ec_chip.conditional_point_add(
-                        &mut region,
-                        &config,
-                        &p1,
-                        &p2,
-                        &bit,
-                        &mut offset,
-                    )?;
-
-                    region.constrain_equal(p3.x.cell(), p3_rec.x.cell())?;
-                    region.constrain_equal(p3.y.cell(), p3_rec.y.cell())?;
+                    let p1 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p1)?;
+                    let p2 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p2)?;
+                    let bit =
+                        ec_chip.load_private_field(&mut ctx, &config, Value::known(Fq::from(1)))?;
+                    let p3_rec =
+                        ec_chip.conditional_point_add(&mut ctx, &config, &p1, &p2, &bit)?;
+
+                    ctx.constrain_equal(p3.x().cell(), p3_rec.x().cell())?;
+                    ctx.constrain_equal(p3.y().cell(), p3_rec.y().cell())?;
                 }
 
                 // unit test: point addition with 0
                 {
-                    let p1 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p1,
-                        &mut offset,
-                    )?;
-                    let p2 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p2,
-                        &mut offset,
-                    )?;
-                    let bit = ec_chip.load_private_field(
-                        &mut region,
-                        &config,
-                        &Fq::from(0),
-                        &mut offset,
-                    )?;
-                    let p3_rec = ec_chip.conditional_point_add(
-                        &mut region,
-                        &config,
-                        &p1,
-                        &p2,
-                        &bit,
-                        &mut offset,
-                    )?;
-
-                    region.constrain_equal(p1.x.cell(), p3_rec.x.cell())?;
-                    region.constrain_equal(p1.y.cell(), p3_rec.y.cell())?;
+                    let p1 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p1)?;
+                    let p2 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p2)?;
+                    let bit =
+                        ec_chip.load_private_field(&mut ctx, &config, Value::known(Fq::from(0)))?;
+                    let p3_rec =
+                        ec_chip.conditional_point_add(&mut ctx, &config, &p1, &p2, &bit)?;
+
+                    ctx.constrain_equal(p1.x().cell(), p3_rec.x().cell())?;
+                    ctx.constrain_equal(p1.y().cell(), p3_rec.y().cell())?;
                 }
 
                 // unit test: point doubling
                 {
-                    let p1 = ec_chip.load_private_point_unchecked(
-                        &mut region,
-                        &config,
-                        &self.p1,
-                        &mut offset,
-                    )?;
-                    let p4_rec = ec_chip.point_double(&mut region, &config, &p1, &mut offset)?;
-
-                    region.constrain_equal(p4.x.cell(), p4_rec.x.cell())?;
-                    region.constrain_equal(p4.y.cell(), p4_rec.y.cell())?;
+                    let p1 = ec_chip.load_private_point_unchecked(&mut ctx, &config, self.p1)?;
+                    let p4_rec = ec_chip.point_double(&mut ctx, &config, &p1)?;
+
+                    ctx.constrain_equal(p4.x().cell(), p4_rec.x().cell())?;
+                    ctx.constrain_equal(p4.y().cell(), p4_rec.y().cell())?;
                 }
 
                 // unit test: scalar decomposition
                 {
-                    let start = offset;
-                    let _scalar_cells =
-                        ec_chip.decompose_scalar(&mut region, &config, &self.s, &mut offset)?;
-                    println!("scalar decompose uses {} rows", offset - start);
+                    let _scalar_cells = ec_chip.decompose_scalar(&mut ctx, &config, self.s)?;
                 }
 
                 // unit test: curve mul
                 {
-                    let start = offset;
-                    let p5_rec =
-                        ec_chip.point_mul(&mut region, &config, &self.p1, &self.s, &mut offset)?;
-                    region.constrain_equal(p5.x.cell(), p5_rec.x.cell())?;
-                    region.constrain_equal(p5.y.cell(), p5_rec.y.cell())?;
-                    println!("curve mul uses {} rows", offset - start);
+                    let p5_rec = ec_chip.point_mul(&mut ctx, &config, self.p1, self.s)?;
+                    ctx.constrain_equal(p5.x().cell(), p5_rec.x().cell())?;
+                    ctx.constrain_equal(p5.y().cell(), p5_rec.y().cell())?;
                 }
 
-                // pad the last two rows
-                ec_chip.pad(&mut region, &config, &mut offset)?;
+                // pad the rows queried by the last gates
+                ec_chip.pad(&mut ctx, &config)?;
 
                 Ok(())
             },
@@ -194,12 +156,12 @@ fn test_ec_ops() {
 
     {
         let circuit = ECTestCircuit {
-            s,
-            p1,
-            p2,
-            p3,
-            p4,
-            p5,
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
         };
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
@@ -210,12 +172,12 @@ fn test_ec_ops() {
     {
         let p3 = (p1 + p1).to_affine();
         let circuit = ECTestCircuit {
-            s,
-            p1,
-            p2,
-            p3,
-            p4,
-            p5,
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
         };
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
@@ -226,15 +188,1368 @@ fn test_ec_ops() {
     {
         let p4 = (p1 + p2).to_affine();
         let circuit = ECTestCircuit {
-            s,
-            p1,
-            p2,
-            p3,
-            p4,
-            p5,
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
         };
 
         let prover = MockProver::run(k, &circuit, vec![]).unwrap();
         assert!(prover.verify().is_err());
     }
 }
+
+#[test]
+fn test_keygen_without_witnesses() {
+    let k = 14;
+
+    // keygen only sees unknown values
+    let params = ParamsKZG::<Bn256>::setup(k, test_rng());
+    let circuit = ECTestCircuit::default();
+    keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct PadTestCircuit {
+    p1: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for PadTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config.clone());
+
+        layouter.assign_region(
+            || "test pad",
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
+
+                // nothing to pad without an enabled selector
+                ec_chip.pad(&mut ctx, &config)?;
+                assert_eq!(ctx.offset(), 0);
+
+                // every selector queries the rows of the cswap gate, i.e., the
+                // on curve check at row 0 queries the rows 0..=4
+                let p1 = ec_chip.load_private_point(&mut ctx, &config, self.p1)?;
+                ec_chip.pad(&mut ctx, &config)?;
+                assert_eq!(ctx.offset(), 5);
+
+                // padding twice is a no-op
+                ec_chip.pad(&mut ctx, &config)?;
+                assert_eq!(ctx.offset(), 5);
+
+                // a trailing cswap assigns all the rows it queries
+                let bit = ec_chip.load_private_field(&mut ctx, &config, Value::known(Fq::ONE))?;
+                let offset = ctx.offset();
+                ec_chip.cswap(&mut ctx, &config, &bit, &p1, &p1)?;
+                ec_chip.pad(&mut ctx, &config)?;
+                assert_eq!(ctx.offset(), offset + 5);
+
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_pad() {
+    let k = 6;
+
+    let p1 = G1::random(test_rng()).to_affine();
+    let circuit = PadTestCircuit {
+        p1: Value::known(p1),
+    };
+    let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+    prover.assert_satisfied();
+}
+
+#[test]
+fn test_row_conflict() {
+    let p1 = Value::known(G1::random(test_rng()).to_affine());
+
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+
+        // 2 * p1 right below p1: the add would start on the row of the double
+        let p1 = chip.load_private_point_unchecked(ctx, config, p1)?;
+        let p2 = chip.point_double(ctx, config, &p1)?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        assert!(matches!(
+            chip.conditional_point_add(ctx, config, &p1, &p2, &bit),
+            Err(NativeEccError::RowConflict {
+                op: "conditional add",
+                offset: 0
+            })
+        ));
+        Ok(())
+    })
+    .unwrap();
+    prover.assert_satisfied();
+}
+
+#[test]
+fn test_generator_mul() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p = (G1::generator() * s).to_affine();
+
+    let run = |expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+            let config = chip.config();
+            let res = chip.generator_mul(ctx, config, Value::known(s))?;
+            let expected = chip.load_private_point(ctx, config, Value::known(expected))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(p).assert_satisfied();
+
+    // error case: not the generator mul
+    let wrong = (G1::generator() * (s + Fr::ONE)).to_affine();
+    assert!(run(wrong).verify().is_err());
+
+    // the fixed base add gate is not configured
+    let prover = run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+        let mut config = chip.config().clone();
+        config.gates.fixed_base_add = false;
+        assert!(matches!(
+            chip.generator_mul(ctx, &config, Value::known(s)),
+            Err(NativeEccError::GateDisabled("fixed base add"))
+        ));
+        Ok(())
+    });
+    prover.unwrap().assert_satisfied();
+}
+
+#[test]
+fn test_point_mul_from_bits() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let run = |expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(12, |chip, ctx| {
+            let config = chip.config();
+            // one decomposition for both muls
+            let bits = chip.decompose_scalar(ctx, config, Value::known(s))?;
+            let res1 = chip.point_mul_from_bits(ctx, config, Value::known(p1), &bits)?;
+            let res2 = chip.point_mul_from_bits(ctx, config, Value::known(p2), &bits)?;
+
+            let expected1 =
+                chip.load_private_point(ctx, config, Value::known((p1 * s).to_affine()))?;
+            let expected2 = chip.load_private_point(ctx, config, Value::known(expected))?;
+            for (res, expected) in [(res1, expected1), (res2, expected2)] {
+                ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+                ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run((p2 * s).to_affine()).assert_satisfied();
+
+    // error case: the second mul is not by the same scalar
+    assert!(run((p2 * (s + Fr::ONE)).to_affine()).verify().is_err());
+}
+
+#[test]
+fn test_point_mul_sized() {
+    let p = G1::random(test_rng()).to_affine();
+    let run = |s: Fr, num_bits: usize| {
+        run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+            let config = chip.config();
+            let res =
+                chip.point_mul_sized(ctx, config, Value::known(p), Value::known(s), num_bits)?;
+            let expected =
+                chip.load_private_point(ctx, config, Value::known((p * s).to_affine()))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+            Ok(())
+        })
+    };
+    let two_to = |n: u64| Fr::from(2).pow_vartime([n]);
+
+    for num_bits in [1, 64, 128, 200, 256] {
+        let s = two_to(num_bits as u64 - 1);
+        run(s, num_bits).unwrap().assert_satisfied();
+    }
+    run(two_to(63) + Fr::from(0xdead_beef), 64)
+        .unwrap()
+        .assert_satisfied();
+
+    // error case: the scalar does not fit the bits of its low limb
+    assert!(run(two_to(64), 64).unwrap().verify().is_err());
+    assert!(run(two_to(199) * Fr::from(2), 192)
+        .unwrap()
+        .verify()
+        .is_err());
+    // error case: the scalar does not fit its low limb
+    assert!(run(two_to(128), 128).is_err());
+
+    let res = run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+        let config = chip.config();
+        for num_bits in [0, 257] {
+            assert!(matches!(
+                chip.decompose_scalar_sized(ctx, config, Value::known(Fr::ONE), num_bits),
+                Err(NativeEccError::InvalidInputLength { .. })
+            ));
+        }
+        Ok(())
+    });
+    res.unwrap().assert_satisfied();
+}
+
+#[test]
+fn test_import_scalar_bits() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p = G1::random(&mut rng).to_affine();
+    let s_bits = crate::util::to_le_bits(&s);
+
+    let run = |bits: Vec<Fq>, expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+            let config = chip.config();
+            // the bits of a hash chip, one per row
+            let bits = bits
+                .iter()
+                .map(|b| chip.load_private_field(ctx, config, Value::known(*b)))
+                .collect::<Result<Vec<_>, _>>()?;
+            let start = ctx.offset();
+            let (bits, limbs) = chip.import_scalar_bits(ctx, config, &bits)?;
+            assert_eq!(
+                ctx.offset() - start,
+                crate::cost::rows_for_decompose_scalar(bits.len())
+            );
+            assert_eq!(limbs.len(), bits.len().div_ceil(128));
+
+            let res = chip.point_mul_from_bits(ctx, config, Value::known(p), &bits)?;
+            let expected = chip.load_private_point(ctx, config, Value::known(expected))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+            Ok(())
+        })
+        .unwrap()
+    };
+    let field_bits = |bits: &[bool]| bits.iter().map(|b| Fq::from(*b as u64)).collect::<Vec<_>>();
+
+    run(field_bits(&s_bits), (p * s).to_affine()).assert_satisfied();
+    // a 200 bits scalar, the last limb padded with 0 bits
+    let short = field_bits(&s_bits[..200]);
+    let s_short = s_bits[..200]
+        .iter()
+        .rev()
+        .fold(Fr::ZERO, |acc, b| acc.double() + Fr::from(*b as u64));
+    run(short, (p * s_short).to_affine()).assert_satisfied();
+
+    // error case: a bit that is not binary
+    let mut bits = field_bits(&s_bits);
+    bits[7] = Fq::from(2);
+    assert!(run(bits, (p * s).to_affine()).verify().is_err());
+}
+
+#[test]
+fn test_reduce_scalar_limbs() {
+    let two_to_128 = Fr::from_u128(1 << 64).square();
+    // the claimed reduction, and the claimed quotient and carry
+    let run = |low: u128, high: u128, e: Option<Fr>, quotient: Option<(u64, u64)>| {
+        run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+            let config = chip.config();
+            let limbs = chip.load_two_private_fields(
+                ctx,
+                config,
+                Value::known(Fq::from_u128(low)),
+                Value::known(Fq::from_u128(high)),
+            )?;
+            let start = ctx.offset();
+            let (bits, _) = match (e, quotient) {
+                (None, _) => chip.reduce_scalar_limbs::<Fr>(ctx, config, &limbs)?,
+                (Some(e), None) => {
+                    chip.assign_reduced_scalar_limbs::<Fr>(ctx, config, &limbs, Value::known(e))?
+                }
+                (Some(e), Some((k, c))) => chip.assign_reduced_scalar_limbs_with_quotient::<Fr>(
+                    ctx,
+                    config,
+                    &limbs,
+                    Value::known(e),
+                    Value::known((Fq::from(k), Fq::from(c))),
+                )?,
+            };
+            assert_eq!(ctx.offset() - start, crate::cost::REDUCE_SCALAR_ROWS);
+
+            // the bits of the claimed reduction, if any
+            let expected = e.unwrap_or(Fr::from_u128(low) + Fr::from_u128(high) * two_to_128);
+            let expected_bits = crate::util::to_le_bits(&expected);
+            for (bit, expected) in bits.iter().zip(expected_bits) {
+                bit.value()
+                    .assert_if_known(|bit| **bit == Fq::from(expected as u64));
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    // the largest digest, about 5 times the order, with a carry of 2 between
+    // the limbs, and digests below the order
+    run(u128::MAX, u128::MAX, None, None).assert_satisfied();
+    run(1 << 127, u128::MAX >> 1, None, None).assert_satisfied();
+    run(0xdead_beef, 0, None, None).assert_satisfied();
+    run(0, 0, None, None).assert_satisfied();
+
+    let e = Fr::from_u128(u128::MAX) + Fr::from_u128(u128::MAX) * two_to_128;
+    run(u128::MAX, u128::MAX, Some(e), Some((5, 2))).assert_satisfied();
+
+    // error case: another residue
+    assert!(run(u128::MAX, u128::MAX, Some(e + Fr::ONE), None)
+        .verify()
+        .is_err());
+
+    // error case: the residue of d + c * 2^129, with a forged carry c = 2
+    // balancing the limbs if the carry were subtracted from the high limb
+    let two_to_129 = two_to_128.double();
+    let forged = e + two_to_129 + two_to_129;
+    assert!(run(u128::MAX, u128::MAX, Some(forged), Some((5, 2)))
+        .verify()
+        .is_err());
+
+    // error case: the right residue and quotient, with a forged carry
+    assert!(run(u128::MAX, u128::MAX, Some(e), Some((5, 3)))
+        .verify()
+        .is_err());
+}
+
+#[test]
+fn test_load_points_trusted() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let run = |p3: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let start = ctx.offset();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p1))?;
+            let p2 = chip.point_double(ctx, config, &p1)?;
+            // p1 and p2 are already validated: no on curve rows
+            let trusted = chip.load_points_trusted(ctx, config, &[p1.value(), p2.value()])?;
+            assert_eq!(ctx.offset() - start, 4);
+            ctx.constrain_equal(trusted[0].x.cell(), p1.x.cell())?;
+            ctx.constrain_equal(trusted[0].y.cell(), p1.y.cell())?;
+            ctx.constrain_equal(trusted[1].x.cell(), p2.x.cell())?;
+            ctx.constrain_equal(trusted[1].y.cell(), p2.y.cell())?;
+
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            let p3_rec = chip.conditional_point_add(ctx, config, &trusted[0], &trusted[1], &bit)?;
+            let p3 = chip.load_private_point(ctx, config, Value::known(p3))?;
+            ctx.constrain_equal(p3.x.cell(), p3_rec.x.cell())?;
+            ctx.constrain_equal(p3.y.cell(), p3_rec.y.cell())?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run((p1 + p1 + p1).to_affine()).assert_satisfied();
+
+    // error case: not p1 + 2 * p1
+    assert!(run((p1 + p2).to_affine()).verify().is_err());
+}
+
+#[test]
+fn test_point_mul_const() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p = G1::random(&mut rng).to_affine();
+
+    let run = |expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+            let config = chip.config();
+            let start = ctx.offset();
+            let res = chip.point_mul_const(ctx, config, Value::known(p), s)?;
+            let ones = crate::util::to_le_bits(&s)
+                .into_iter()
+                .filter(|b| *b)
+                .count();
+            assert_eq!(
+                ctx.offset() - start,
+                crate::cost::rows_for_point_mul_const(256, ones)
+            );
+
+            let expected = chip.load_private_point(ctx, config, Value::known(expected))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+
+            // a false condition folds to p1, without any row
+            let offset = ctx.offset();
+            let folded = chip.conditional_point_add_const(ctx, config, &res, &expected, false)?;
+            assert_eq!(ctx.offset(), offset);
+            assert_eq!(folded.x.cell(), res.x.cell());
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(p.mul(s).to_affine()).assert_satisfied();
+
+    // error case: not p * s
+    assert!(run(p.mul(s + Fr::ONE).to_affine()).verify().is_err());
+}
+
+/// A result (x3, y3) whose residuals cancel in a gate summing a constraint
+/// linear in y3 and the on curve constraint, i.e., a root of
+/// -y3^2 + c * y3 + k = 0, with `coefficients(x3) = (c, k)`.
+fn cancelling_result(coefficients: impl Fn(Fq) -> (Fq, Fq)) -> (Fq, Fq) {
+    (1u64..)
+        .find_map(|i| {
+            let x3 = Fq::from(i);
+            let (c, k) = coefficients(x3);
+            let d = c.square() + Fq::from(4) * k;
+            Option::from(d.sqrt()).map(|sqrt_d: Fq| {
+                let y3 = (c + sqrt_d) * Fq::from(2).invert().unwrap();
+                (x3, y3)
+            })
+        })
+        .unwrap()
+}
+
+#[test]
+fn test_ec_add_cancellation() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+    let (x1, y1) = crate::util::coordinates(&p1).unwrap();
+    let (x2, y2) = crate::util::coordinates(&p2).unwrap();
+    let b = crate::constants::b::<Fq>();
+
+    // (x3, y3) is neither on the line nor on curve, but the two residuals cancel
+    let (x3, y3) = cancelling_result(|x3| {
+        (
+            x2 - x1,
+            (x3 - x1) * (y2 - y1) + (x2 - x1) * y1 + x3 * x3 * x3 + b,
+        )
+    });
+    let line = (x3 - x1) * (y2 - y1) + (x2 - x1) * (y3 + y1);
+    let on_curve = x3 * x3 * x3 - y3 * y3 + b;
+    assert_ne!(line, Fq::ZERO);
+    assert_eq!(line + on_curve, Fq::ZERO);
+
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        chip.load_private_point_unchecked(ctx, config, Value::known(p1))?;
+        chip.load_private_point_unchecked(ctx, config, Value::known(p2))?;
+        chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        let offset = ctx.offset();
+        chip.enable_ec_op(ctx, config, "conditional add", offset - 3, EC_ADD)?;
+        ctx.assign_advice(|| "x3", config.a, Value::known(x3))?;
+        ctx.assign_advice(|| "y3", config.b, Value::known(y3))?;
+        ctx.next();
+        Ok(())
+    })
+    .unwrap();
+    let failed = failed_constraints(&prover);
+    assert!(failed.contains(&"ec add: line".into()));
+    assert!(failed.contains(&"ec add: on curve".into()));
+}
+
+#[test]
+fn test_ec_double_cancellation() {
+    let p1 = G1::random(test_rng()).to_affine();
+    let (x1, y1) = crate::util::coordinates(&p1).unwrap();
+    let b = crate::constants::b::<Fq>();
+
+    // (x3, y3) is neither on the tangent nor on curve, but the two residuals cancel
+    let (x3, y3) = cancelling_result(|x3| {
+        (
+            Fq::from(2) * y1,
+            Fq::from(2) * y1 * y1 + Fq::from(3) * x1 * x1 * (x3 - x1) + x3 * x3 * x3 + b,
+        )
+    });
+    let tangent = Fq::from(2) * y1 * (y3 + y1) + Fq::from(3) * x1 * x1 * (x3 - x1);
+    let on_curve = x3 * x3 * x3 - y3 * y3 + b;
+    assert_ne!(tangent, Fq::ZERO);
+    assert_eq!(tangent + on_curve, Fq::ZERO);
+
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        chip.load_private_point_unchecked(ctx, config, Value::known(p1))?;
+        let offset = ctx.offset();
+        chip.enable_ec_op(ctx, config, "point double", offset - 1, EC_DOUBLE)?;
+        ctx.assign_advice(|| "x3", config.a, Value::known(x3))?;
+        ctx.assign_advice(|| "y3", config.b, Value::known(y3))?;
+        ctx.next();
+        Ok(())
+    })
+    .unwrap();
+    let failed = failed_constraints(&prover);
+    assert!(failed.contains(&"ec double: tangent".into()));
+    assert!(failed.contains(&"ec double: on curve".into()));
+}
+
+#[test]
+fn test_ec_add_condition_binary() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let run = |condition: Fq, p3: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point_unchecked(ctx, config, Value::known(p1))?;
+            let p2 = chip.load_private_point_unchecked(ctx, config, Value::known(p2))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(condition))?;
+            chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, Value::known(p3))?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(Fq::ONE, (p1 + p2).to_affine()).assert_satisfied();
+    run(Fq::ZERO, p1).assert_satisfied();
+
+    // error case: the condition is 2
+    let failures = run(Fq::from(2), p1).verify().unwrap_err();
+    assert!(failures
+        .iter()
+        .any(|failure| failure.to_string().contains("ec add: condition binary")));
+}
+
+#[test]
+fn test_conditional_point_sub() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let run = |condition: Fq, p3: Option<G1Affine>| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p1))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(p2))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(condition))?;
+            let start = ctx.offset();
+            match p3 {
+                Some(p3) => chip.assign_conditional_point_sub(
+                    ctx,
+                    config,
+                    &p1,
+                    &p2,
+                    &bit,
+                    Value::known(p3),
+                )?,
+                None => chip.conditional_point_sub(ctx, config, &p1, &p2, &bit)?,
+            };
+            assert_eq!(ctx.offset() - start, Op::ConditionalPointSub.rows());
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(Fq::ONE, None).assert_satisfied();
+    run(Fq::ZERO, None).assert_satisfied();
+    run(Fq::ONE, Some((p1 - p2).to_affine())).assert_satisfied();
+
+    // error case: the sum rather than the difference
+    let failed = failed_constraints(&run(Fq::ONE, Some((p1 + p2).to_affine())));
+    assert_eq!(failed, ["ec sub: line"]);
+    // error case: the condition is 2
+    assert!(run(Fq::from(2), Some(p1)).verify().is_err());
+}
+
+#[test]
+fn test_conditional_point_add_assigned() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    let run = |condition: Fq, sub: bool| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            // a condition computed away from the operands
+            let bit = chip.load_private_field(ctx, config, Value::known(condition))?;
+            let p1_assigned = chip.load_private_point(ctx, config, Value::known(p1))?;
+            let p2_assigned = chip.load_private_point(ctx, config, Value::known(p2))?;
+            let start = ctx.offset();
+            let (res, op) = if sub {
+                let res = chip.conditional_point_sub_assigned(
+                    ctx,
+                    config,
+                    &p1_assigned,
+                    &p2_assigned,
+                    &bit,
+                )?;
+                (res, Op::ConditionalPointSubAssigned)
+            } else {
+                let res = chip.conditional_point_add_assigned(
+                    ctx,
+                    config,
+                    &p1_assigned,
+                    &p2_assigned,
+                    &bit,
+                )?;
+                (res, Op::ConditionalPointAddAssigned)
+            };
+            assert_eq!(ctx.offset() - start, op.rows());
+
+            let expected = match (condition != Fq::ZERO, sub) {
+                (false, _) => p1,
+                (true, false) => (p1 + p2).to_affine(),
+                (true, true) => (p1 - p2).to_affine(),
+            };
+            res.value().assert_if_known(|res| *res == expected);
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    for sub in [false, true] {
+        run(Fq::ONE, sub).assert_satisfied();
+        run(Fq::ZERO, sub).assert_satisfied();
+        // error case: the condition is not a bit
+        assert!(run(Fq::from(2), sub).verify().is_err());
+    }
+}
+
+#[test]
+fn test_cswap() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let q = G1::random(&mut rng).to_affine();
+
+    let run = |condition: Fq, expected: (G1Affine, G1Affine)| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p = chip.load_private_point(ctx, config, Value::known(p))?;
+            let q = chip.load_private_point(ctx, config, Value::known(q))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(condition))?;
+            let (r, s) = chip.cswap(ctx, config, &bit, &p, &q)?;
+            for (res, expected) in [(r, expected.0), (s, expected.1)] {
+                let (x, y) = crate::util::coordinates(&expected).unwrap();
+                ctx.constrain_constant(res.x.cell(), x)?;
+                ctx.constrain_constant(res.y.cell(), y)?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(Fq::ZERO, (p, q)).assert_satisfied();
+    run(Fq::ONE, (q, p)).assert_satisfied();
+
+    // error case: not swapped for a 1 bit
+    assert!(run(Fq::ONE, (p, q)).verify().is_err());
+
+    // error case: the condition is 2, the outputs are 2q - p and 2p - q
+    let two_q_minus_p = (q + q - p).to_affine();
+    let two_p_minus_q = (p + p - q).to_affine();
+    assert_eq!(
+        failed_constraints(&run(Fq::from(2), (two_q_minus_p, two_p_minus_q))),
+        ["cswap: condition binary"]
+    );
+}
+
+#[test]
+fn test_pedersen_commit_batch() {
+    let mut rng = test_rng();
+    let h = G1::random(&mut rng).to_affine();
+    let openings: Vec<(Fr, Fr)> = (0..2)
+        .map(|_| (Fr::random(&mut rng), Fr::random(&mut rng)))
+        .collect();
+
+    let run = |h_expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(12, |chip, ctx| {
+            let config = chip.config();
+            let values: Vec<_> = openings
+                .iter()
+                .map(|(v, r)| (Value::known(*v), Value::known(*r)))
+                .collect();
+            let start = ctx.offset();
+            let commitments = chip.pedersen_commit_batch(ctx, config, h, &values)?;
+            assert_eq!(
+                ctx.offset() - start,
+                crate::cost::rows_for_pedersen_commit_batch(openings.len())
+            );
+            for (c, (v, r)) in commitments.iter().zip(openings.iter()) {
+                let expected = (G1::generator() * v + h_expected * r).to_affine();
+                let (x, y) = crate::util::coordinates(&expected).unwrap();
+                ctx.constrain_constant(c.x.cell(), x)?;
+                ctx.constrain_constant(c.y.cell(), y)?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(h).assert_satisfied();
+
+    // error case: committed with another second generator
+    assert!(run(G1::from(h).double().to_affine()).verify().is_err());
+}
+
+#[test]
+fn test_x_only_point_mul() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p = G1::random(&mut rng).to_affine();
+    let expected = p.mul(s).to_affine();
+
+    let run = |expected: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+            let config = chip.config();
+            let (x, y) = crate::util::coordinates(&expected).unwrap();
+            let res_x = chip.x_only_point_mul(ctx, config, Value::known(p), Value::known(s))?;
+            ctx.constrain_constant(res_x.cell(), x)?;
+            let res =
+                chip.x_only_point_mul_with_y(ctx, config, Value::known(p), Value::known(s))?;
+            ctx.constrain_constant(res.x.cell(), x)?;
+            ctx.constrain_constant(res.y.cell(), y)?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(expected).assert_satisfied();
+
+    // error case: the negation has the same x, but not the same y
+    assert!(run(-expected).verify().is_err());
+    // error case: wrong result
+    assert!(run((expected + p).to_affine()).verify().is_err());
+}
+
+#[test]
+fn test_point_mul_with_scalar() {
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p = G1::random(&mut rng).to_affine();
+    let (high, low) = field_decompose::<Fq, Fr>(&s);
+
+    let run = |limbs: [Fq; 2]| {
+        run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+            let config = chip.config();
+            let (res, scalar) =
+                chip.point_mul_with_scalar(ctx, config, Value::known(p), Value::known(s))?;
+            let expected =
+                chip.load_private_point(ctx, config, Value::known(p.mul(s).to_affine()))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+
+            // bind the mul to the committed limbs
+            for (cell, limb) in scalar.iter().zip(limbs) {
+                ctx.constrain_constant(cell.cell(), limb)?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run([low, high]).assert_satisfied();
+
+    // error case: the mul is not by the committed scalar
+    assert!(run([low + Fq::ONE, high]).verify().is_err());
+}
+
+#[test]
+fn test_identity() {
+    let p = G1::random(test_rng()).to_affine();
+    let identity = G1::identity().to_affine();
+
+    run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+
+        // O + P and 2 * O: the identity has no (x, y) representation
+        assert!(matches!(
+            chip.load_private_point(ctx, config, Value::known(identity)),
+            Err(NativeEccError::PointAtInfinity)
+        ));
+        assert!(matches!(
+            chip.load_private_point_unchecked(ctx, config, Value::known(identity)),
+            Err(NativeEccError::PointAtInfinity)
+        ));
+
+        // P + (-P) and P + P are not covered by the incomplete formula
+        for q in [-p, p] {
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(q))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            assert!(matches!(
+                chip.conditional_point_add(ctx, config, &p1, &p2, &bit),
+                Err(NativeEccError::IncompleteAddition)
+            ));
+        }
+
+        // but P + (-P) with a 0 bit returns P
+        let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let p2 = chip.load_private_point(ctx, config, Value::known(-p))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ZERO))?;
+        let res = chip.conditional_point_add(ctx, config, &p1, &p2, &bit)?;
+        ctx.constrain_equal(res.x.cell(), p1.x.cell())?;
+        ctx.constrain_equal(res.y.cell(), p1.y.cell())?;
+        Ok(())
+    })
+    .unwrap()
+    .assert_satisfied();
+}
+
+#[test]
+fn test_ec_add_degenerate_witness() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let q = G1::random(&mut rng).to_affine();
+
+    // the witness `conditional_point_add` refuses, assigned directly
+    let run = |p2: G1Affine, p3: G1Affine, sub: bool| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(p2))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            if sub {
+                chip.assign_conditional_point_sub(ctx, config, &p1, &p2, &bit, Value::known(p3))?;
+            } else {
+                chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, Value::known(p3))?;
+            }
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    // P + P: the line through P and P is any line, so any point on curve
+    let failed = failed_constraints(&run(p, q, false));
+    assert!(failed.contains(&"ec add: distinct x".into()));
+    // P + (-P): the vertical line through P, i.e., ±P
+    let failed = failed_constraints(&run(-p, p, false));
+    assert!(failed.contains(&"ec add: distinct x".into()));
+    // P - P and P - (-P)
+    let failed = failed_constraints(&run(p, p, true));
+    assert!(failed.contains(&"ec sub: distinct x".into()));
+    let failed = failed_constraints(&run(-p, q, true));
+    assert!(failed.contains(&"ec sub: distinct x".into()));
+
+    // but the 0 condition does not check the operands
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let p2 = chip.load_private_point(ctx, config, Value::known(-p))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ZERO))?;
+        chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, Value::known(p))?;
+        Ok(())
+    })
+    .unwrap();
+    prover.assert_satisfied();
+}
+
+#[test]
+fn test_window_table() {
+    let p = G1::random(test_rng()).to_affine();
+    let honest = |window_bits: usize| {
+        (1..1u64 << window_bits)
+            .map(|j| (p * Fr::from(j)).to_affine())
+            .collect::<Vec<_>>()
+    };
+
+    let run = |window_bits: usize, table: Vec<G1Affine>| {
+        run_gadget::<G1Affine, Fq, _>(7, |chip, ctx| {
+            let config = chip.config();
+            let p_assigned = chip.load_private_point(ctx, config, Value::known(p))?;
+            let start = ctx.offset();
+            let entries = chip.assign_window_table(
+                ctx,
+                config,
+                &p_assigned,
+                window_bits,
+                Value::known(table.clone()),
+            )?;
+            assert_eq!(
+                ctx.offset() - start,
+                crate::cost::rows_for_window_table(window_bits)
+            );
+            for (entry, expected) in entries.iter().zip(table.iter()) {
+                entry.value().assert_if_known(|entry| entry == expected);
+            }
+            Ok(())
+        })
+    };
+
+    for window_bits in [1, 2, 4] {
+        run(window_bits, honest(window_bits))
+            .unwrap()
+            .assert_satisfied();
+    }
+
+    // the op witnesses the honest table
+    run_gadget::<G1Affine, Fq, _>(7, |chip, ctx| {
+        let config = chip.config();
+        let p_assigned = chip.load_private_point(ctx, config, Value::known(p))?;
+        let entries = chip.load_window_table(ctx, config, &p_assigned, 3)?;
+        assert_eq!(entries.len(), 7);
+        Ok(())
+    })
+    .unwrap()
+    .assert_satisfied();
+
+    // error case: a wrong entry, even on curve
+    let mut table = honest(3);
+    table[4] = (p * Fr::from(7)).to_affine();
+    assert!(run(3, table).unwrap().verify().is_err());
+    let mut table = honest(3);
+    table[1] = (p * Fr::from(3)).to_affine();
+    assert!(run(3, table).unwrap().verify().is_err());
+
+    // error case: a table of the wrong size
+    assert!(run(3, honest(2)).is_err());
+}
+
+#[test]
+fn test_normalize() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let z = Fq::random(&mut rng);
+
+    let run = |x: Fq, y: Fq, z: Fq| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let [x, y] =
+                chip.load_two_private_fields(ctx, config, Value::known(x), Value::known(y))?;
+            let z = chip.load_private_field(ctx, config, Value::known(z))?;
+            let start = ctx.offset();
+            let res = chip.normalize(ctx, config, &AssignedProjectivePoint::new(x, y, z))?;
+            assert_eq!(ctx.offset() - start, crate::cost::NORMALIZE_ROWS);
+
+            let expected = chip.load_private_point(ctx, config, Value::known(p))?;
+            ctx.constrain_equal(res.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(res.y.cell(), expected.y.cell())?;
+            Ok(())
+        })
+    };
+
+    run(p.x * z, p.y * z, z).unwrap().assert_satisfied();
+    run(p.x, p.y, Fq::ONE).unwrap().assert_satisfied();
+
+    // error case: (X : Y : Z) is not on curve
+    let prover = run(p.x * z, (p.y + Fq::ONE) * z, z).unwrap();
+    assert!(prover.verify().is_err());
+    // error case: Z = 0, the identity
+    assert!(run(p.x, p.y, Fq::ZERO).is_err());
+}
+
+#[test]
+fn test_double_and_add_step() {
+    let mut rng = test_rng();
+    let g = G1::generator().to_affine();
+    let p = G1::random(&mut rng).to_affine();
+
+    // 8 * g + 5 * p, starting from g, with the bits 1, 0, 1 of 5
+    let run = |bits: [u64; 3], expected: G1Affine, copy_acc: bool| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let bits = bits
+                .iter()
+                .map(|b| chip.load_private_field(ctx, config, Value::known(Fq::from(*b))))
+                .collect::<Result<Vec<_>, _>>()?;
+            let base = chip.load_private_point(ctx, config, Value::known(p))?;
+            let mut acc = chip.load_private_point(ctx, config, Value::known(g))?;
+            if copy_acc {
+                // acc is no longer on the latest row
+                chip.load_private_field(ctx, config, Value::known(Fq::ZERO))?;
+            }
+            for bit in &bits {
+                let start = ctx.offset();
+                acc = chip.double_and_add_step(ctx, config, &acc, &base, bit)?;
+                assert_eq!(ctx.offset() - start, Op::DoubleAndAddStep.rows());
+            }
+            let expected = chip.load_private_point(ctx, config, Value::known(expected))?;
+            ctx.constrain_equal(acc.x.cell(), expected.x.cell())?;
+            ctx.constrain_equal(acc.y.cell(), expected.y.cell())?;
+            Ok(())
+        })
+    };
+
+    let expected = (g * Fr::from(8) + p * Fr::from(5)).to_affine();
+    run([1, 0, 1], expected, false).unwrap().assert_satisfied();
+
+    // error case: the bits of another scalar
+    assert!(run([1, 1, 1], expected, false).unwrap().verify().is_err());
+    // error case: a bit that is not binary
+    assert!(run([2, 0, 1], expected, false).unwrap().verify().is_err());
+    // error case: the accumulator is not on the latest row
+    assert!(run([1, 0, 1], expected, true).is_err());
+}
+
+#[test]
+fn test_verify_mul_witness() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let s = Fr::random(&mut rng);
+
+    run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+        let config = chip.config();
+        let res = chip.point_mul(ctx, config, Value::known(p), Value::known(s))?;
+        let p = chip.load_private_point(ctx, config, Value::known(p))?;
+        let start = ctx.offset();
+        chip.verify_mul_witness(ctx, config, &p, Value::known(s), &res)?;
+        assert_eq!(ctx.offset(), start);
+
+        // error case: the result of another scalar
+        let mismatch = chip.verify_mul_witness(ctx, config, &p, Value::known(s + Fr::ONE), &res);
+        assert!(matches!(
+            mismatch,
+            Err(NativeEccError::WitnessMismatch {
+                op: "point mul",
+                ..
+            })
+        ));
+        // unknown values are not checked
+        chip.verify_mul_witness(ctx, config, &p, Value::unknown(), &res)?;
+        Ok(())
+    })
+    .unwrap()
+    .assert_satisfied();
+}
+
+#[test]
+fn test_assert_equal_const() {
+    let mut rng = test_rng();
+    let g = G1::generator().to_affine();
+    let p = G1::random(&mut rng).to_affine();
+
+    let run = |p: G1Affine, q: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p = chip.load_private_point(ctx, config, Value::known(p))?;
+            let start = ctx.offset();
+            chip.assert_equal_const(ctx, config, &p, &q)?;
+            assert_eq!(ctx.offset(), start);
+            Ok(())
+        })
+    };
+
+    run(g, g).unwrap().assert_satisfied();
+    run(p, p).unwrap().assert_satisfied();
+
+    // error case: a witness other than the constant
+    assert!(run(p, g).unwrap().verify().is_err());
+    // error case: the negation of the constant
+    assert!(run(-g, g).unwrap().verify().is_err());
+    // error case: the identity has no coordinates
+    assert!(run(p, G1::identity().to_affine()).is_err());
+}
+
+#[test]
+fn test_decompose_scalar_canonical() {
+    let s = Fr::random(test_rng());
+    let prover = run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+        let config = chip.config();
+        let (bits, _) = chip.decompose_scalar_canonical(ctx, config, Value::known(s))?;
+        assert_eq!(bits.len(), 256);
+        Ok(())
+    })
+    .unwrap();
+    prover.assert_satisfied();
+
+    // the limbs of r - 1 and r, where r is the order of the scalar field
+    let (m_hi, m_lo) = field_decompose::<Fq, Fr>(&-Fr::ONE);
+    let run = |low: Fq, high: Fq| {
+        run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+            let config = chip.config();
+            let limbs =
+                chip.load_two_private_fields(ctx, config, Value::known(low), Value::known(high))?;
+            chip.enforce_canonical_limbs::<Fr>(ctx, config, &limbs)
+        })
+        .unwrap()
+    };
+    run(m_lo, m_hi).assert_satisfied();
+
+    // error case: s = r, whose bits would also decompose 0
+    assert!(run(m_lo + Fq::ONE, m_hi).verify().is_err());
+}
+
+#[test]
+fn test_decompose_field_limbs() {
+    let x = Fq::random(test_rng());
+    for x in [x, Fq::ZERO, -Fq::ONE] {
+        let prover = run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+            let config = chip.config();
+            let x_cell = chip.load_private_field(ctx, config, Value::known(x))?;
+            let [low, high] = chip.decompose_field_limbs(ctx, config, &x_cell)?;
+
+            let (expected_high, expected_low) = field_decompose::<Fq, Fq>(&x);
+            low.value().assert_if_known(|low| **low == expected_low);
+            high.value().assert_if_known(|high| **high == expected_high);
+            Ok(())
+        })
+        .unwrap();
+        prover.assert_satisfied();
+    }
+}
+
+#[test]
+fn test_ec_add_distinct_x() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let forged = G1::random(&mut rng).to_affine();
+
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let p2 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, Value::known(forged))?;
+        Ok(())
+    })
+    .unwrap();
+
+    // the line through p and p does not determine p + p: any point on curve
+    // passes the line, but not the distinct x check
+    assert!(failed_constraints(&prover).contains(&"ec add: distinct x".into()));
+}
+
+#[test]
+fn test_offset_correction_pinned() {
+    let res = G1::random(test_rng()).to_affine();
+
+    let run = |condition: Fq| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let res = chip.load_private_point(ctx, config, Value::known(res))?;
+            chip.assign_offset_correction(ctx, config, &res, 256, Value::known(condition))?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(Fq::ONE).assert_satisfied();
+
+    // error case: the prover skips the correction, and the scalar mul
+    // returns s * p + 2^256 * generator
+    assert!(run(Fq::ZERO).verify().is_err());
+}
+
+/// Asserts the `complete-addition` feature rejects a forged witness, which
+/// the line or the tangent alone accept.
+fn assert_rejected_if_complete(prover: MockProver<Fq>) {
+    if cfg!(feature = "complete-addition") {
+        assert!(prover.verify().is_err());
+    } else {
+        prover.assert_satisfied();
+    }
+}
+
+#[test]
+fn test_ec_add_forged_negation() {
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+
+    // -p1 and -p2 are on curve, and their negations on the line through p1 and p2
+    let run = |p3: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p1))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(p2))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, Value::known(p3))?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run((p1 + p2).to_affine()).assert_satisfied();
+    assert_rejected_if_complete(run(-p1));
+    assert_rejected_if_complete(run(-p2));
+}
+
+#[test]
+fn test_ec_double_forged_negation() {
+    let p = G1::random(test_rng()).to_affine();
+
+    // -p is on curve, and p on the tangent at p
+    let run = |p2: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            chip.assign_point_double(ctx, config, &p1, Value::known(p2))?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(G1::from(p).double().to_affine()).assert_satisfied();
+    assert_rejected_if_complete(run(-p));
+}
+
+#[test]
+fn test_point_mul_forged_negation() {
+    let mut rng = test_rng();
+    let acc = G1::random(&mut rng).to_affine();
+    let p = G1::random(&mut rng).to_affine();
+    let double = G1::from(acc).double();
+
+    // a step of the double-then-add loop of `point_mul`, which the prover
+    // could otherwise use to negate the accumulator
+    let run = |double: G1Affine, sum: G1Affine| {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p = chip.load_private_point(ctx, config, Value::known(p))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            let acc = chip.load_private_point(ctx, config, Value::known(acc))?;
+            chip.assign_double_and_add_step(
+                ctx,
+                config,
+                &acc,
+                &p,
+                &bit,
+                Value::known(double),
+                Value::known(sum),
+            )?;
+            Ok(())
+        })
+        .unwrap()
+    };
+
+    run(double.to_affine(), (double + p).to_affine()).assert_satisfied();
+    // the double is -acc
+    assert_rejected_if_complete(run(-acc, (-acc + p).to_affine()));
+    // the sum is -(2 * acc)
+    assert_rejected_if_complete(run(double.to_affine(), (-double).to_affine()));
+}
+
+#[cfg(feature = "audit")]
+#[test]
+fn test_audit() {
+    use crate::audit::ExceptionalCase;
+    use crate::audit::Precondition;
+
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let s = Fr::random(&mut rng);
+
+    run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+        let config = chip.config();
+
+        // the ladder of an honest mul does not hit any exceptional case
+        chip.point_mul(ctx, config, Value::known(p), Value::known(s))?;
+        assert!(chip.exceptional_cases().is_empty());
+
+        // p + p
+        let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let p2 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        let sum = Value::known((p + p).to_affine());
+        chip.assign_conditional_point_add(ctx, config, &p1, &p2, &bit, sum)?;
+        assert_eq!(
+            chip.exceptional_cases(),
+            vec![ExceptionalCase {
+                op: "conditional add",
+                offset: p1.offset,
+                violated: Precondition::DistinctX,
+            }]
+        );
+        Ok(())
+    })
+    .unwrap()
+    .assert_satisfied();
+}
+
+#[cfg(feature = "audit")]
+#[test]
+fn test_gate_activations() {
+    use std::collections::BTreeSet;
+
+    use crate::audit::UNTRACED;
+
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let q = G1::random(&mut rng).to_affine();
+
+    run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+        let p2 = chip.load_private_point(ctx, config, Value::known(q))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        chip.conditional_point_add(ctx, config, &p1, &p2, &bit)?;
+
+        let report = chip.gate_activations();
+        // the on curve checks of the loads, outside of any op
+        let on_curve = BTreeSet::from(["q3", "q_ec_enable"]);
+        assert_eq!(report[UNTRACED][&p1.offset], on_curve);
+        assert_eq!(report[UNTRACED][&p2.offset], on_curve);
+        // the add is selected on the row of its first operand
+        let add = &report["conditional add"];
+        assert!(add[&p1.offset].contains("q_ec_op: add"));
+        assert!(add.values().flatten().all(|s| *s != "q_ec_op: double"));
+        Ok(())
+    })
+    .unwrap()
+    .assert_satisfied();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(16))]
+
+    #[test]
+    fn prop_conditional_point_add(
+        (p, q) in distinct_points::<G1Affine>(),
+        b in any::<bool>(),
+    ) {
+        let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(q))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::from(b as u64)))?;
+            let res = chip.conditional_point_add(ctx, config, &p1, &p2, &bit)?;
+            let expected = if b { (p + q).to_affine() } else { p };
+            res.value().assert_if_known(|res| *res == expected);
+            Ok(())
+        })
+        .unwrap();
+        prop_assert!(prover.verify().is_ok());
+    }
+
+    #[test]
+    fn prop_exceptional_add((p, q) in exceptional_add_operands::<G1Affine>()) {
+        run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            let p2 = chip.load_private_point(ctx, config, Value::known(q))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            assert!(matches!(
+                chip.conditional_point_add(ctx, config, &p1, &p2, &bit),
+                Err(NativeEccError::IncompleteAddition)
+            ));
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn prop_point_double(p in points::<G1Affine>()) {
+        let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+            let config = chip.config();
+            let p1 = chip.load_private_point(ctx, config, Value::known(p))?;
+            let res = chip.point_double(ctx, config, &p1)?;
+            res.value().assert_if_known(|res| *res == (p + p).to_affine());
+            Ok(())
+        })
+        .unwrap();
+        prop_assert!(prover.verify().is_ok());
+    }
+}
@@ -1,11 +1,15 @@
-use std::marker::PhantomData;
+use core::marker::PhantomData;
 
-use halo2_proofs::arithmetic::Field;
 use halo2_proofs::circuit::AssignedCell;
-use halo2_proofs::halo2curves::CurveAffine;
+use halo2_proofs::circuit::Cell;
+use halo2_proofs::circuit::Value;
 
-use crate::util::leak;
+use crate::backend::CurveAffine;
+use crate::backend::Field;
 
+/// An ec point (x, y) assigned to the advice columns (a, b) of the chip.
+///
+/// The two coordinates are always stored in a same row of a region.
 #[derive(Debug, Clone)]
 pub struct AssignedECPoint<C, F>
 where
@@ -24,7 +28,7 @@ where
     C: CurveAffine<Base = F>,
     F: Field,
 {
-    pub fn new(x: AssignedCell<F, F>, y: AssignedCell<F, F>, offset: usize) -> Self {
+    pub(crate) fn new(x: AssignedCell<F, F>, y: AssignedCell<F, F>, offset: usize) -> Self {
         Self {
             x,
             y,
@@ -33,11 +37,104 @@ where
         }
     }
 
-    pub fn witness(&self) -> C {
-        C::from_xy(leak(&self.x.value()), leak(&self.y.value())).unwrap()
+    /// The cell of the x coordinate.
+    pub fn x(&self) -> &AssignedCell<F, F> {
+        &self.x
     }
 
+    /// The cell of the y coordinate.
+    pub fn y(&self) -> &AssignedCell<F, F> {
+        &self.y
+    }
+
+    /// The cells of the (x, y) coordinates, e.g., for copy constraints.
+    pub fn cells(&self) -> [Cell; 2] {
+        [self.x.cell(), self.y.cell()]
+    }
+
+    /// The (x, y) coordinates.
+    pub fn coordinates(&self) -> Value<(F, F)> {
+        self.x.value().zip(self.y.value()).map(|(x, y)| (*x, *y))
+    }
+
+    /// The point.
+    ///
+    /// Unknown if the coordinates are unknown, or if they are not on curve
+    /// (possible only for points loaded without the on curve check).
+    pub fn value(&self) -> Value<C> {
+        self.coordinates().and_then(|(x, y)| {
+            let p = C::from_xy(x, y);
+            if bool::from(p.is_some()) {
+                Value::known(p.unwrap())
+            } else {
+                Value::unknown()
+            }
+        })
+    }
+
+    /// The row of the region that holds the point.
     pub fn offset(&self) -> usize {
         self.offset
     }
+
+    /// Consumes the point, returning the (x, y) cells.
+    pub fn into_cells(self) -> (AssignedCell<F, F>, AssignedCell<F, F>) {
+        (self.x, self.y)
+    }
+}
+
+impl<C, F> From<AssignedECPoint<C, F>> for (AssignedCell<F, F>, AssignedCell<F, F>)
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    fn from(p: AssignedECPoint<C, F>) -> Self {
+        p.into_cells()
+    }
+}
+
+/// An ec point in projective coordinates (X : Y : Z), i.e., the affine point
+/// (X / Z, Y / Z), e.g., the result of an out-of-chip projective formula.
+///
+/// The chip has no gate over projective coordinates; the cells can be in any
+/// row or column, `normalize` copies them into its own rows.
+#[derive(Debug, Clone)]
+pub struct AssignedProjectivePoint<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    pub(crate) x: AssignedCell<F, F>,
+    pub(crate) y: AssignedCell<F, F>,
+    pub(crate) z: AssignedCell<F, F>,
+    _phantom: PhantomData<C>,
+}
+
+impl<C, F> AssignedProjectivePoint<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    pub fn new(x: AssignedCell<F, F>, y: AssignedCell<F, F>, z: AssignedCell<F, F>) -> Self {
+        Self {
+            x,
+            y,
+            z,
+            _phantom: PhantomData::default(),
+        }
+    }
+
+    /// The cells of the (X, Y, Z) coordinates.
+    pub fn cells(&self) -> [Cell; 3] {
+        [self.x.cell(), self.y.cell(), self.z.cell()]
+    }
+
+    /// The (X, Y, Z) coordinates.
+    pub fn coordinates(&self) -> Value<(F, F, F)> {
+        self.x
+            .value()
+            .zip(self.y.value())
+            .zip(self.z.value())
+            .map(|((x, y), z)| (*x, *y, *z))
+    }
 }
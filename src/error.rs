@@ -0,0 +1,125 @@
+use alloc::string::String;
+use core::fmt;
+
+use crate::backend::Halo2Error as Error;
+
+/// Errors returned by the native ec chip.
+#[derive(Debug)]
+pub enum NativeEccError {
+    /// An error raised by halo2 while assigning cells or enabling selectors.
+    Synthesis(Error),
+    /// The point is the identity, which has no affine (x, y) representation.
+    PointAtInfinity,
+    /// The operands of an add are p and ±p, which the incomplete addition
+    /// formula does not cover; the gate rejects such a witness as well.
+    IncompleteAddition,
+    /// The curve does not have the shape the gates are written for, y^2 = x^3 + b.
+    UnsupportedCurve,
+    /// A serialized value is not the canonical repr of a field element.
+    InvalidEncoding,
+    /// The witnessed (x, y) pair is not a point on the curve.
+    NotOnCurve,
+    /// An operand is not located at the row the gate expects it.
+    UnexpectedOffset {
+        op: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    /// An op received the wrong number of inputs.
+    InvalidInputLength { expected: usize, actual: usize },
+    /// The witnessed decomposition does not recompose to its input.
+    DecompositionMismatch,
+    /// A value is exposed as public input, but the chip has no instance column.
+    MissingInstanceColumn,
+    /// An op relies on a gate the chip is not configured with.
+    GateDisabled(&'static str),
+    /// The row of an op is already used by another op, e.g., an ec add and an
+    /// ec double cannot start on the same row.
+    RowConflict { op: &'static str, offset: usize },
+    /// The witness of the result of an op differs from its value computed
+    /// natively, see `verify_mul_witness`.
+    WitnessMismatch {
+        op: &'static str,
+        offset: usize,
+        expected: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for NativeEccError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Synthesis(e) => write!(f, "synthesis error: {}", e),
+            Self::PointAtInfinity => write!(f, "point at infinity has no affine coordinates"),
+            Self::IncompleteAddition => {
+                write!(f, "incomplete addition: the operands are p and ±p")
+            }
+            Self::UnsupportedCurve => {
+                write!(
+                    f,
+                    "unsupported curve: the gates are written for y^2 = x^3 + b"
+                )
+            }
+            Self::InvalidEncoding => write!(f, "invalid encoding of a field element"),
+            Self::NotOnCurve => write!(f, "point is not on curve"),
+            Self::UnexpectedOffset {
+                op,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: operand is located at row {}, expected row {}",
+                op, actual, expected
+            ),
+            Self::InvalidInputLength { expected, actual } => write!(
+                f,
+                "invalid input length: got {}, expected {}",
+                actual, expected
+            ),
+            Self::DecompositionMismatch => {
+                write!(f, "decomposition does not recompose to the input")
+            }
+            Self::MissingInstanceColumn => {
+                write!(f, "no instance column; configure the chip with one")
+            }
+            Self::GateDisabled(gate) => write!(f, "the {} gate is not configured", gate),
+            Self::RowConflict { op, offset } => {
+                write!(f, "{}: row {} is already used by another op", op, offset)
+            }
+            Self::WitnessMismatch {
+                op,
+                offset,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "{}: the result at row {} is {}, expected {}",
+                op, offset, actual, expected
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for NativeEccError {}
+
+impl From<Error> for NativeEccError {
+    fn from(e: Error) -> Self {
+        Self::Synthesis(e)
+    }
+}
+
+/// Errors raised by the chip surface as `Error::Synthesis` at the `synthesize` boundary;
+/// halo2 errors are passed through unchanged.
+impl From<NativeEccError> for Error {
+    fn from(e: NativeEccError) -> Self {
+        match e {
+            NativeEccError::Synthesis(e) => e,
+            _ => {
+                #[cfg(feature = "verbose")]
+                println!("native ecc error: {}", e);
+                Error::Synthesis
+            }
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Exchange of assigned values between chips.
+//!
+//! A chip that consumes the outputs of this chip, e.g., a hash or a byte
+//! decomposer, takes any `ExportCells` and copies its cells with copy
+//! constraints. The other way round, `ImportCells` rebuilds a value of this
+//! chip from the cells of another chip.
+//!
+//! ```ignore
+//! // hash the coordinates of a point
+//! let cells = p.export_cells();
+//! let digest = poseidon.hash(&mut layouter, &cells)?;
+//!
+//! // a point computed by another chip
+//! let q: AssignedECPoint<_, _> = ecc.import(&other_chip_cells)?;
+//! ecc.enforce_on_curve(&q)?;
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
+
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::region_ctx::RegionCtx;
+use crate::util::annotation;
+use crate::AssignedECPoint;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// A value whose cells can be handed to another chip.
+pub trait ExportCells<F: Field> {
+    /// The cells of the value, in a fixed order, e.g., x then y for a point.
+    fn export_cells(&self) -> Vec<AssignedCell<F, F>>;
+}
+
+/// A value that can be rebuilt from the cells of another chip.
+pub trait ImportCells<F: Field>: Sized {
+    /// The number of cells of the value.
+    const NUM_CELLS: usize;
+
+    /// Copies the cells into the current row, one per column, with copy
+    /// constraints, and moves to the next row.
+    ///
+    /// The cells are in the order of `ExportCells`. The value is not
+    /// validated, e.g., a point is not checked on curve.
+    fn import_cells(
+        ctx: &mut RegionCtx<F>,
+        columns: &[Column<Advice>],
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<Self, NativeEccError>;
+}
+
+impl<F: Field> ExportCells<F> for AssignedCell<F, F> {
+    fn export_cells(&self) -> Vec<AssignedCell<F, F>> {
+        vec![self.clone()]
+    }
+}
+
+impl<C, F> ExportCells<F> for AssignedECPoint<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    fn export_cells(&self) -> Vec<AssignedCell<F, F>> {
+        vec![self.x.clone(), self.y.clone()]
+    }
+}
+
+impl<F: Field, T: ExportCells<F>> ExportCells<F> for [T] {
+    fn export_cells(&self) -> Vec<AssignedCell<F, F>> {
+        self.iter().flat_map(ExportCells::export_cells).collect()
+    }
+}
+
+impl<F: Field> ImportCells<F> for AssignedCell<F, F> {
+    const NUM_CELLS: usize = 1;
+
+    fn import_cells(
+        ctx: &mut RegionCtx<F>,
+        columns: &[Column<Advice>],
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<Self, NativeEccError> {
+        check_lengths::<F, Self>(columns, cells)?;
+        let res = ctx.copy_advice(|| annotation("import", "field"), columns[0], &cells[0])?;
+        ctx.next();
+        Ok(res)
+    }
+}
+
+impl<C, F> ImportCells<F> for AssignedECPoint<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: Field,
+{
+    const NUM_CELLS: usize = 2;
+
+    fn import_cells(
+        ctx: &mut RegionCtx<F>,
+        columns: &[Column<Advice>],
+        cells: &[AssignedCell<F, F>],
+    ) -> Result<Self, NativeEccError> {
+        check_lengths::<F, Self>(columns, cells)?;
+        let x = ctx.copy_advice(|| annotation("import", "x"), columns[0], &cells[0])?;
+        let y = ctx.copy_advice(|| annotation("import", "y"), columns[1], &cells[1])?;
+        let res = AssignedECPoint::new(x, y, ctx.offset());
+        ctx.next();
+        Ok(res)
+    }
+}
+
+fn check_lengths<F: Field, T: ImportCells<F>>(
+    columns: &[Column<Advice>],
+    cells: &[AssignedCell<F, F>],
+) -> Result<(), NativeEccError> {
+    if cells.len() != T::NUM_CELLS {
+        return Err(NativeEccError::InvalidInputLength {
+            expected: T::NUM_CELLS,
+            actual: cells.len(),
+        });
+    }
+    if columns.len() < T::NUM_CELLS {
+        return Err(NativeEccError::InvalidInputLength {
+            expected: T::NUM_CELLS,
+            actual: columns.len(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use ark_std::test_rng;
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::exchange::ExportCells;
+use crate::AssignedECPoint;
+use crate::NativeEccError;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct ExchangeTestCircuit {
+    p: Value<G1Affine>,
+    p2: Value<G1Affine>, // 2p
+    // exports the coordinates of p in the (y, x) order
+    swap: bool,
+}
+
+impl Circuit<Fq> for ExchangeTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            swap: self.swap,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        // the outputs of another chip
+        let mut cells = ec_chip.assign_scoped(
+            &mut layouter,
+            || "exporter",
+            |ecc| Ok(ecc.load_private_point(self.p)?.export_cells()),
+        )?;
+        if self.swap {
+            cells.swap(0, 1);
+        }
+
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "importer",
+            |ecc| {
+                assert!(matches!(
+                    ecc.import::<AssignedECPoint<_, _>>(&cells[..1]),
+                    Err(NativeEccError::InvalidInputLength {
+                        expected: 2,
+                        actual: 1
+                    })
+                ));
+
+                let p2 = ecc.load_private_point(self.p2)?;
+                let p: AssignedECPoint<_, _> = ecc.import(&cells)?;
+                ecc.enforce_on_curve(&p)?;
+                let p2_rec = ecc.point_double(&p)?;
+                ecc.constrain_equal(&p2, &p2_rec)?;
+
+                // a single field cell
+                let x = ecc.import::<AssignedCell<_, _>>(&cells[..1])?;
+                ecc.ctx().constrain_equal(x.cell(), p.x().cell())?;
+                Ok(())
+            },
+        )?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_export_import() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let p2 = (p + p).to_affine();
+
+    {
+        let circuit = ExchangeTestCircuit {
+            p: Value::known(p),
+            p2: Value::known(p2),
+            swap: false,
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: the cells are imported in the wrong order
+    {
+        let circuit = ExchangeTestCircuit {
+            p: Value::known(p),
+            p2: Value::known(p2),
+            swap: true,
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,199 @@
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Value;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::AssignedECPoint;
+use crate::ECChip;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// High level ec instructions, in the style of `halo2_gadgets::ecc::EccInstructions`.
+///
+/// Unlike `NativeECOps`, the instructions take a layouter rather than a region
+/// and an offset. Each instruction is laid out in its own region, and its
+/// operands are copied into that region via copy constraints.
+pub trait EccInstructions<C: CurveAffine>: Chip<C::Base> + Clone + Debug {
+    /// A point on the curve.
+    type Point: Clone + Debug;
+    /// A scalar of the curve.
+    type Scalar: Clone + Debug;
+
+    /// Witnesses a point and constrains it is on curve.
+    fn witness_point(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        value: Value<C>,
+    ) -> Result<Self::Point, NativeEccError>;
+
+    /// Witnesses a scalar.
+    fn witness_scalar(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        value: Value<C::ScalarExt>,
+    ) -> Result<Self::Scalar, NativeEccError>;
+
+    /// Constrains a == b.
+    fn constrain_equal(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        a: &Self::Point,
+        b: &Self::Point,
+    ) -> Result<(), NativeEccError>;
+
+    /// Returns a + b.
+    ///
+    /// Incomplete: a and b must satisfy a != b and a != -b.
+    fn add(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        a: &Self::Point,
+        b: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError>;
+
+    /// Returns a + a.
+    fn double(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        a: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError>;
+
+    /// Returns scalar * base.
+    fn mul(
+        &self,
+        layouter: &mut impl Layouter<C::Base>,
+        scalar: &Self::Scalar,
+        base: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError>;
+}
+
+impl<C, F> EccInstructions<C> for ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    C::ScalarExt: PrimeField<Repr = [u8; 32]>,
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    type Point = AssignedECPoint<C, F>;
+    /// The little endian bits of the scalar.
+    type Scalar = Vec<AssignedCell<F, F>>;
+
+    fn witness_point(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        value: Value<C>,
+    ) -> Result<Self::Point, NativeEccError> {
+        let config = self.config();
+        let p = layouter.assign_region(
+            || "witness point",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let p = self.load_private_point(&mut ctx, config, value)?;
+                self.pad(&mut ctx, config)?;
+                Ok(p)
+            },
+        )?;
+        Ok(p)
+    }
+
+    fn witness_scalar(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        value: Value<C::ScalarExt>,
+    ) -> Result<Self::Scalar, NativeEccError> {
+        let config = self.config();
+        let bits = layouter.assign_region(
+            || "witness scalar",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let bits = self.decompose_scalar::<C::ScalarExt>(&mut ctx, config, value)?;
+                self.pad(&mut ctx, config)?;
+                Ok(bits)
+            },
+        )?;
+        Ok(bits)
+    }
+
+    fn constrain_equal(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: &Self::Point,
+        b: &Self::Point,
+    ) -> Result<(), NativeEccError> {
+        layouter.assign_region(
+            || "constrain equal",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                ctx.constrain_equal(a.x.cell(), b.x.cell())?;
+                ctx.constrain_equal(a.y.cell(), b.y.cell())
+            },
+        )?;
+        Ok(())
+    }
+
+    fn add(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: &Self::Point,
+        b: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError> {
+        let config = self.config();
+        let res = layouter.assign_region(
+            || "point add",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let a = self.copy_point(&mut ctx, config, a)?;
+                let b = self.copy_point(&mut ctx, config, b)?;
+                let res = self.conditional_point_add_const(&mut ctx, config, &a, &b, true)?;
+                self.pad(&mut ctx, config)?;
+                Ok(res)
+            },
+        )?;
+        Ok(res)
+    }
+
+    fn double(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        a: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError> {
+        let config = self.config();
+        let res = layouter.assign_region(
+            || "point double",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let a = self.copy_point(&mut ctx, config, a)?;
+                let res = self.point_double(&mut ctx, config, &a)?;
+                self.pad(&mut ctx, config)?;
+                Ok(res)
+            },
+        )?;
+        Ok(res)
+    }
+
+    fn mul(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        scalar: &Self::Scalar,
+        base: &Self::Point,
+    ) -> Result<Self::Point, NativeEccError> {
+        let config = self.config();
+        let res = layouter.assign_region(
+            || "point mul",
+            |region| {
+                let mut ctx = self.region_ctx(region);
+                let res = self.point_mul_with_bits(&mut ctx, config, base, scalar)?;
+                self.pad(&mut ctx, config)?;
+                Ok(res)
+            },
+        )?;
+        Ok(res)
+    }
+}
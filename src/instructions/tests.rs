@@ -0,0 +1,116 @@
+use std::ops::Mul;
+
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct InstructionsTestCircuit {
+    s: Value<Fr>,
+    p1: Value<G1Affine>,
+    p2: Value<G1Affine>,
+    p3: Value<G1Affine>, // p1 + p2
+    p4: Value<G1Affine>, // 2p1
+    p5: Value<G1Affine>, // p1 * s
+}
+
+impl Circuit<Fq> for InstructionsTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+
+        let p1 = ec_chip.witness_point(&mut layouter, self.p1)?;
+        let p2 = ec_chip.witness_point(&mut layouter, self.p2)?;
+        let p3 = ec_chip.witness_point(&mut layouter, self.p3)?;
+        let p4 = ec_chip.witness_point(&mut layouter, self.p4)?;
+        let p5 = ec_chip.witness_point(&mut layouter, self.p5)?;
+        let s = ec_chip.witness_scalar(&mut layouter, self.s)?;
+
+        // unit test: point addition
+        let p3_rec = EccInstructions::add(&ec_chip, &mut layouter, &p1, &p2)?;
+        ec_chip.constrain_equal(&mut layouter, &p3, &p3_rec)?;
+
+        // unit test: point doubling
+        let p4_rec = ec_chip.double(&mut layouter, &p1)?;
+        ec_chip.constrain_equal(&mut layouter, &p4, &p4_rec)?;
+
+        // unit test: curve mul
+        let p5_rec = EccInstructions::mul(&ec_chip, &mut layouter, &s, &p1)?;
+        ec_chip.constrain_equal(&mut layouter, &p5, &p5_rec)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_ecc_instructions() {
+    let k = 14;
+
+    let mut rng = test_rng();
+    let s = Fr::random(&mut rng);
+    let p1 = G1::random(&mut rng).to_affine();
+    let p2 = G1::random(&mut rng).to_affine();
+    let p3 = (p1 + p2).to_affine();
+    let p4 = (p1 + p1).to_affine();
+    let p5 = p1.mul(s).to_affine();
+
+    {
+        let circuit = InstructionsTestCircuit {
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: mul not equal
+    {
+        let p5 = p1.mul(s + Fr::ONE).to_affine();
+        let circuit = InstructionsTestCircuit {
+            s: Value::known(s),
+            p1: Value::known(p1),
+            p2: Value::known(p2),
+            p3: Value::known(p3),
+            p4: Value::known(p4),
+            p5: Value::known(p5),
+        };
+
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
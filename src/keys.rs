@@ -0,0 +1,210 @@
+//! Serialization of the proving and verifying keys of circuits of the chip,
+//! for KZG over BN254, so that a service does not redo the keygen on every
+//! start.
+//!
+//! The keys are written after a header: the magic `necc`, `LAYOUT_VERSION`
+//! and the layout hash of the circuit (see `ECConfig::layout_hash`), in little
+//! endian. Reading a key checks the header, so that a key cached by another
+//! release or for another layout is rejected instead of producing invalid
+//! proofs.
+//!
+//! ```ignore
+//! let hash = keys::circuit_layout_hash::<DlogCircuit>();
+//! let pk = keys::load_or_keygen_pk(Path::new("dlog.pk"), &params, &circuit, hash)?;
+//! ```
+//!
+//! A key generated without witnesses is valid for every witness only if the
+//! layout of the circuit does not depend on the witness; the ops of the chip
+//! lay out their rows from their parameters only, and
+//! `check_deterministic_layout` checks a whole circuit does, e.g., in its tests.
+
+use std::fs::File;
+use std::io;
+use std::io::BufReader;
+use std::io::BufWriter;
+use std::io::Read;
+use std::io::Write;
+use std::path::Path;
+
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::plonk::ProvingKey;
+use halo2_proofs::plonk::VerifyingKey;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2_proofs::SerdeFormat;
+
+use crate::curves::bn256::Bn256;
+use crate::curves::bn256::Fr;
+use crate::curves::bn256::G1Affine;
+use crate::curves::EmbeddedCurve;
+use crate::ECConfig;
+use crate::LAYOUT_VERSION;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// The magic of the header of the keys.
+pub const MAGIC: [u8; 4] = *b"necc";
+
+/// The keys are written with their points checked on read.
+const FORMAT: SerdeFormat = SerdeFormat::RawBytes;
+
+/// The layout hash of a circuit whose config is the config of the chip.
+pub fn circuit_layout_hash<ConcreteCircuit>() -> u64
+where
+    ConcreteCircuit: Circuit<Fr, Config = ECConfig<EmbeddedCurve, Fr>>,
+{
+    ConcreteCircuit::configure(&mut ConstraintSystem::default()).layout_hash()
+}
+
+/// Writes the header of a key of a circuit of the given layout hash.
+pub fn write_header<W: Write>(writer: &mut W, layout_hash: u64) -> io::Result<()> {
+    writer.write_all(&MAGIC)?;
+    writer.write_all(&LAYOUT_VERSION.to_le_bytes())?;
+    writer.write_all(&layout_hash.to_le_bytes())
+}
+
+/// Reads a header, checking it is the header of a key of the current
+/// `LAYOUT_VERSION` and of the given layout hash.
+pub fn read_header<R: Read>(reader: &mut R, layout_hash: u64) -> io::Result<()> {
+    let mut magic = [0u8; 4];
+    let mut version = [0u8; 4];
+    let mut hash = [0u8; 8];
+    reader.read_exact(&mut magic)?;
+    reader.read_exact(&mut version)?;
+    reader.read_exact(&mut hash)?;
+
+    if magic != MAGIC {
+        return Err(invalid_data("not a key of the native ec chip"));
+    }
+    let version = u32::from_le_bytes(version);
+    if version != LAYOUT_VERSION {
+        return Err(invalid_data(&format!(
+            "key of layout version {}, expected {}",
+            version, LAYOUT_VERSION
+        )));
+    }
+    let hash = u64::from_le_bytes(hash);
+    if hash != layout_hash {
+        return Err(invalid_data(&format!(
+            "key of layout {:#018x}, expected {:#018x}",
+            hash, layout_hash
+        )));
+    }
+    Ok(())
+}
+
+/// Writes a verifying key after its header.
+pub fn write_vk<W: Write>(
+    writer: &mut W,
+    vk: &VerifyingKey<G1Affine>,
+    layout_hash: u64,
+) -> io::Result<()> {
+    write_header(writer, layout_hash)?;
+    vk.write(writer, FORMAT)
+}
+
+/// Reads a verifying key written by `write_vk`.
+pub fn read_vk<R, ConcreteCircuit>(
+    reader: &mut R,
+    layout_hash: u64,
+) -> io::Result<VerifyingKey<G1Affine>>
+where
+    R: Read,
+    ConcreteCircuit: Circuit<Fr>,
+{
+    read_header(reader, layout_hash)?;
+    VerifyingKey::read::<_, ConcreteCircuit>(reader, FORMAT)
+}
+
+/// Writes a proving key, and its verifying key, after their header.
+pub fn write_pk<W: Write>(
+    writer: &mut W,
+    pk: &ProvingKey<G1Affine>,
+    layout_hash: u64,
+) -> io::Result<()> {
+    write_header(writer, layout_hash)?;
+    pk.write(writer, FORMAT)
+}
+
+/// Reads a proving key written by `write_pk`.
+pub fn read_pk<R, ConcreteCircuit>(
+    reader: &mut R,
+    layout_hash: u64,
+) -> io::Result<ProvingKey<G1Affine>>
+where
+    R: Read,
+    ConcreteCircuit: Circuit<Fr>,
+{
+    read_header(reader, layout_hash)?;
+    ProvingKey::read::<_, ConcreteCircuit>(reader, FORMAT)
+}
+
+/// Reads the proving key cached at `path`, or generates it and caches it
+/// there if the file is missing, unreadable or holds a key of another layout.
+///
+/// The header does not identify the params: a key is only valid for the
+/// params it was generated with, so cache the params along with it.
+pub fn load_or_keygen_pk<ConcreteCircuit>(
+    path: &Path,
+    params: &ParamsKZG<Bn256>,
+    circuit: &ConcreteCircuit,
+    layout_hash: u64,
+) -> io::Result<ProvingKey<G1Affine>>
+where
+    ConcreteCircuit: Circuit<Fr>,
+{
+    let cached = File::open(path)
+        .and_then(|file| read_pk::<_, ConcreteCircuit>(&mut BufReader::new(file), layout_hash));
+    if let Ok(pk) = cached {
+        return Ok(pk);
+    }
+
+    let circuit = circuit.without_witnesses();
+    let vk = keygen_vk(params, &circuit).map_err(keygen_error)?;
+    let pk = keygen_pk(params, vk, &circuit).map_err(keygen_error)?;
+    let mut writer = BufWriter::new(File::create(path)?);
+    write_pk(&mut writer, &pk, layout_hash)?;
+    writer.flush()?;
+    Ok(pk)
+}
+
+/// Checks the fixed columns, the selectors and the copy constraints of the
+/// circuit do not depend on the witness: the keys of each of the circuits, as
+/// synthesized with their witnesses, are the key of the circuit without
+/// witnesses, i.e., the key of `load_or_keygen_pk`.
+///
+/// Errors with `InvalidData` on the first circuit whose layout differs.
+pub fn check_deterministic_layout<ConcreteCircuit>(
+    params: &ParamsKZG<Bn256>,
+    circuits: &[ConcreteCircuit],
+) -> io::Result<()>
+where
+    ConcreteCircuit: Circuit<Fr>,
+{
+    let Some(first) = circuits.first() else {
+        return Ok(());
+    };
+    let expected = keygen_vk(params, &first.without_witnesses()).map_err(keygen_error)?;
+    for (i, circuit) in circuits.iter().enumerate() {
+        let vk = keygen_vk(params, circuit).map_err(keygen_error)?;
+        if vk.transcript_repr() != expected.transcript_repr() {
+            return Err(invalid_data(&format!(
+                "the layout of circuit {} depends on its witness",
+                i
+            )));
+        }
+    }
+    Ok(())
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+fn keygen_error(e: Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, format!("keygen: {:?}", e))
+}
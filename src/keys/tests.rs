@@ -0,0 +1,191 @@
+use std::io::ErrorKind;
+
+use ark_std::test_rng;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::halo2curves::ff::Field;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::keygen_pk;
+use halo2_proofs::plonk::keygen_vk;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2_proofs::poly::kzg::commitment::ParamsKZG;
+use halo2curves::bn256::Bn256;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::keys;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct DoubleTestCircuit {
+    p: Value<G1Affine>,
+}
+
+impl Circuit<Fq> for DoubleTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "double",
+            |ecc| {
+                let p = ecc.load_private_point(self.p)?;
+                ecc.point_double(&p)
+            },
+        )?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_key_round_trip() {
+    let k = 6;
+    let mut rng = test_rng();
+    let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+    let circuit = DoubleTestCircuit {
+        p: Value::known(G1::random(&mut rng).to_affine()),
+    };
+    let vk = keygen_vk(&params, &circuit.without_witnesses()).unwrap();
+    let pk = keygen_pk(&params, vk, &circuit.without_witnesses()).unwrap();
+    let vk = pk.get_vk();
+
+    let hash = keys::circuit_layout_hash::<DoubleTestCircuit>();
+    assert_eq!(
+        hash,
+        ECChip::<G1Affine, Fq>::configure(&mut ConstraintSystem::default()).layout_hash()
+    );
+
+    let mut vk_bytes = vec![];
+    keys::write_vk(&mut vk_bytes, vk, hash).unwrap();
+    assert_eq!(vk_bytes[..4], keys::MAGIC);
+    let vk_read = keys::read_vk::<_, DoubleTestCircuit>(&mut &vk_bytes[..], hash).unwrap();
+    assert_eq!(vk_read.transcript_repr(), vk.transcript_repr());
+
+    let mut pk_bytes = vec![];
+    keys::write_pk(&mut pk_bytes, &pk, hash).unwrap();
+    let pk_read = keys::read_pk::<_, DoubleTestCircuit>(&mut &pk_bytes[..], hash).unwrap();
+    assert_eq!(pk_read.get_vk().transcript_repr(), vk.transcript_repr());
+
+    // error case: a key of another layout
+    let err = keys::read_vk::<_, DoubleTestCircuit>(&mut &vk_bytes[..], hash ^ 1).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    // error case: a key of another layout version
+    let mut old = vk_bytes.clone();
+    old[4] ^= 1;
+    let err = keys::read_vk::<_, DoubleTestCircuit>(&mut &old[..], hash).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+
+    // error case: not a key
+    let err = keys::read_vk::<_, DoubleTestCircuit>(&mut &[0u8; 16][..], hash).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_load_or_keygen_pk() {
+    let k = 6;
+    let mut rng = test_rng();
+    let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+    let circuit = DoubleTestCircuit::default();
+    let hash = keys::circuit_layout_hash::<DoubleTestCircuit>();
+
+    let path = std::env::temp_dir().join(format!("necc-{}.pk", std::process::id()));
+    // a stale key of another layout is replaced
+    std::fs::write(&path, b"stale").unwrap();
+    let pk = keys::load_or_keygen_pk(&path, &params, &circuit, hash).unwrap();
+    let cached = keys::load_or_keygen_pk(&path, &params, &circuit, hash).unwrap();
+    assert_eq!(
+        cached.get_vk().transcript_repr(),
+        pk.get_vk().transcript_repr()
+    );
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[derive(Default, Debug, Clone, Copy)]
+struct MulTestCircuit {
+    p: Value<G1Affine>,
+    s: Value<Fr>,
+    // doubles the result if s is 1, a layout depending on the witness
+    branch_on_witness: bool,
+}
+
+impl Circuit<Fq> for MulTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self {
+            branch_on_witness: self.branch_on_witness,
+            ..Self::default()
+        }
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(config);
+        ec_chip.assign_scoped(
+            &mut layouter,
+            || "mul",
+            |ecc| {
+                let res = ecc.point_mul(self.p, self.s)?;
+                let mut double = false;
+                self.s
+                    .map(|s| double = self.branch_on_witness && s == Fr::ONE);
+                if double {
+                    ecc.point_double(&res)?;
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+}
+
+#[test]
+fn test_check_deterministic_layout() {
+    let k = 11;
+    let mut rng = test_rng();
+    let params = ParamsKZG::<Bn256>::setup(k, &mut rng);
+    let p = Value::known(G1::random(&mut rng).to_affine());
+
+    let circuits = |branch_on_witness: bool| {
+        [Fr::ONE, Fr::random(&mut test_rng())].map(|s| MulTestCircuit {
+            p,
+            s: Value::known(s),
+            branch_on_witness,
+        })
+    };
+    keys::check_deterministic_layout(&params, &circuits(false)).unwrap();
+
+    // error case: an extra double for one of the witnesses
+    let err = keys::check_deterministic_layout(&params, &circuits(true)).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidData);
+}
@@ -1,12 +1,72 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+#[cfg(all(feature = "pse", feature = "zcash"))]
+compile_error!("features `pse` and `zcash` are mutually exclusive");
+#[cfg(not(any(feature = "pse", feature = "zcash")))]
+compile_error!("one of the features `pse` or `zcash` must be enabled");
+
+#[cfg(feature = "zcash")]
+extern crate zcash_halo2_proofs as halo2_proofs;
+
 mod arith_gates;
+#[cfg(feature = "audit")]
+pub mod audit;
+mod backend;
+pub mod binding;
+pub mod blinding;
+pub mod calldata;
+#[cfg(feature = "pse")]
+pub mod challenges;
 mod chip;
+pub mod commitment;
 mod config;
+mod config_builder;
+pub mod constants;
+mod context;
+pub mod cost;
+mod curves;
 mod ec_gates;
 mod ec_structs;
+mod error;
+mod exchange;
+mod instructions;
+#[cfg(all(feature = "pse", feature = "std"))]
+pub mod keys;
+pub mod prelude;
+mod region_ctx;
+mod sub_chip;
+mod tables;
+pub mod test_vectors;
+#[cfg(all(feature = "pse", any(test, feature = "testing")))]
+pub mod testing;
 mod util;
+pub mod witness;
+#[cfg(feature = "pse")]
+pub mod wnaf;
 
 pub use arith_gates::ArithOps;
+pub use backend::Halo2Error;
 pub use chip::ECChip;
+pub use config::ChipStats;
 pub use config::ECConfig;
+pub use config::EnabledGates;
+pub use config::LAYOUT_VERSION;
+pub use config_builder::ECConfigBuilder;
+pub use context::Accumulator;
+pub use context::ScopedEcc;
 pub use ec_gates::NativeECOps;
 pub use ec_structs::AssignedECPoint;
+pub use ec_structs::AssignedProjectivePoint;
+pub use error::NativeEccError;
+pub use exchange::ExportCells;
+pub use exchange::ImportCells;
+pub use instructions::EccInstructions;
+pub use region_ctx::RegionCtx;
+pub use sub_chip::SharedColumns;
+pub use sub_chip::SubChip;
+pub use util::field_decompose;
+pub use util::field_decompose_u128;
+pub use util::field_limbs_u128;
+pub use witness::EcWitnessBundle;
@@ -0,0 +1,57 @@
+//! The public surface of the crate.
+//!
+//! ```ignore
+//! use halo2_native_ecc::prelude::*;
+//! ```
+
+use crate::curves::EmbeddedBase;
+use crate::curves::EmbeddedCurve;
+
+#[cfg(feature = "audit")]
+pub use crate::audit::ExceptionalCase;
+#[cfg(feature = "audit")]
+pub use crate::audit::GateReport;
+pub use crate::Accumulator;
+pub use crate::ArithOps;
+pub use crate::AssignedECPoint;
+pub use crate::ChipStats;
+pub use crate::ECChip;
+pub use crate::ECConfig;
+pub use crate::ECConfigBuilder;
+pub use crate::EcWitnessBundle;
+pub use crate::EccInstructions;
+pub use crate::EnabledGates;
+pub use crate::ExportCells;
+pub use crate::Halo2Error;
+pub use crate::ImportCells;
+pub use crate::NativeECOps;
+pub use crate::NativeEccError;
+pub use crate::RegionCtx;
+pub use crate::ScopedEcc;
+pub use crate::SharedColumns;
+pub use crate::SubChip;
+pub use crate::LAYOUT_VERSION;
+
+#[cfg(feature = "pse")]
+/// The chip over Grumpkin, whose base field is the scalar field of BN254.
+pub type GrumpkinChip = ECChip<EmbeddedCurve, EmbeddedBase>;
+
+#[cfg(feature = "pse")]
+/// The config of `GrumpkinChip`.
+pub type GrumpkinConfig = ECConfig<EmbeddedCurve, EmbeddedBase>;
+
+#[cfg(feature = "pse")]
+/// A Grumpkin point assigned by `GrumpkinChip`.
+pub type AssignedGrumpkinPoint = AssignedECPoint<EmbeddedCurve, EmbeddedBase>;
+
+#[cfg(feature = "zcash")]
+/// The chip over Pallas, whose base field is the scalar field of Vesta.
+pub type PallasChip = ECChip<EmbeddedCurve, EmbeddedBase>;
+
+#[cfg(feature = "zcash")]
+/// The config of `PallasChip`.
+pub type PallasConfig = ECConfig<EmbeddedCurve, EmbeddedBase>;
+
+#[cfg(feature = "zcash")]
+/// A Pallas point assigned by `PallasChip`.
+pub type AssignedPallasPoint = AssignedECPoint<EmbeddedCurve, EmbeddedBase>;
@@ -0,0 +1,224 @@
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Cell;
+use halo2_proofs::circuit::Region;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::Fixed;
+use halo2_proofs::plonk::Instance;
+use halo2_proofs::plonk::Selector;
+
+#[cfg(feature = "audit")]
+use crate::audit::GateLog;
+#[cfg(feature = "audit")]
+use crate::audit::UNTRACED;
+use crate::backend::Field;
+use crate::backend::Halo2Error as Error;
+
+/// A region along with the offset of its next free row.
+///
+/// Cells are always assigned at the current offset; ops move to the next row
+/// with `next` (or `advance`) once a row is filled.
+#[derive(Debug)]
+pub struct RegionCtx<'r, F: Field> {
+    region: Region<'r, F>,
+    offset: usize,
+    // the last row with an enabled selector, if any
+    last_selector_offset: Option<usize>,
+    // the values of the fixed columns used as selectors, by (column index, row)
+    fixed_selectors: BTreeMap<(usize, usize), F>,
+    // with the `audit` feature: the log of the enabled selectors, if any,
+    // and the innermost op running
+    #[cfg(feature = "audit")]
+    gate_log: Option<GateLog<F>>,
+    #[cfg(feature = "audit")]
+    op: Option<&'static str>,
+}
+
+impl<'r, F: Field> RegionCtx<'r, F> {
+    /// Wraps a region, starting from the given offset.
+    pub fn new(region: Region<'r, F>, offset: usize) -> Self {
+        Self {
+            region,
+            offset,
+            last_selector_offset: None,
+            fixed_selectors: BTreeMap::new(),
+            #[cfg(feature = "audit")]
+            gate_log: None,
+            #[cfg(feature = "audit")]
+            op: None,
+        }
+    }
+
+    /// Records the selectors enabled from now on into the log.
+    #[cfg(feature = "audit")]
+    pub(crate) fn record_gates(&mut self, log: &GateLog<F>) {
+        self.gate_log = Some(log.clone());
+    }
+
+    /// The current offset, i.e., the next free row of the region.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// The last row with an enabled selector, if any.
+    pub fn last_selector_offset(&self) -> Option<usize> {
+        self.last_selector_offset
+    }
+
+    /// Moves to the next row.
+    pub fn next(&mut self) {
+        self.offset += 1
+    }
+
+    /// Moves n rows forward.
+    pub fn advance(&mut self, n: usize) {
+        self.offset += n
+    }
+
+    /// Runs an op from the current offset.
+    ///
+    /// With the `tracing` feature, the op runs in a debug span recording its
+    /// name, its first row and the rows it uses, e.g., to profile a circuit
+    /// with a subscriber reporting the spans on close. With the `audit` feature,
+    /// the selectors enabled by the op are recorded under its name.
+    pub(crate) fn traced<T>(&mut self, op: &'static str, f: impl FnOnce(&mut Self) -> T) -> T {
+        #[cfg(feature = "audit")]
+        let outer = self.op.replace(op);
+        #[cfg(feature = "tracing")]
+        let res = {
+            let start = self.offset;
+            let span = tracing::debug_span!("ec op", op, start, rows = tracing::field::Empty);
+            let _entered = span.enter();
+            let res = f(self);
+            span.record("rows", self.offset - start);
+            res
+        };
+        #[cfg(not(feature = "tracing"))]
+        let res = {
+            let _ = op;
+            f(self)
+        };
+        #[cfg(feature = "audit")]
+        {
+            self.op = outer;
+        }
+        res
+    }
+
+    /// The underlying region.
+    pub fn region(&mut self) -> &mut Region<'r, F> {
+        &mut self.region
+    }
+
+    /// Assigns an advice cell at the current offset.
+    pub fn assign_advice<A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        value: Value<F>,
+    ) -> Result<AssignedCell<F, F>, Error>
+    where
+        A: Fn() -> AR,
+        AR: Into<String>,
+    {
+        self.region
+            .assign_advice(annotation, column, self.offset, || value)
+    }
+
+    /// Copies an assigned cell to the current offset, with a copy constraint.
+    pub fn copy_advice<A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Advice>,
+        cell: &AssignedCell<F, F>,
+    ) -> Result<AssignedCell<F, F>, Error>
+    where
+        A: Fn() -> AR,
+        AR: Into<String>,
+    {
+        cell.copy_advice(annotation, &mut self.region, column, self.offset)
+    }
+
+    /// Copies a cell of an instance column to an advice cell at the current
+    /// offset, with a copy constraint.
+    pub fn assign_advice_from_instance<A, AR>(
+        &mut self,
+        annotation: A,
+        instance: Column<Instance>,
+        row: usize,
+        column: Column<Advice>,
+    ) -> Result<AssignedCell<F, F>, Error>
+    where
+        A: Fn() -> AR,
+        AR: Into<String>,
+    {
+        self.region
+            .assign_advice_from_instance(annotation, instance, row, column, self.offset)
+    }
+
+    /// Enables a selector at the given offset.
+    pub fn enable_selector(&mut self, selector: &Selector, offset: usize) -> Result<(), Error> {
+        self.last_selector_offset = self.last_selector_offset.max(Some(offset));
+        #[cfg(feature = "audit")]
+        if let Some(log) = &self.gate_log {
+            log.record_selector(self.op.unwrap_or(UNTRACED), offset, selector);
+        }
+        selector.enable(&mut self.region, offset)
+    }
+
+    /// Assigns a fixed column used as a selector at the given offset, e.g.,
+    /// the op flag of the ec add and ec double.
+    pub fn enable_fixed_selector(
+        &mut self,
+        annotation: &'static str,
+        column: Column<Fixed>,
+        offset: usize,
+        value: F,
+    ) -> Result<(), Error> {
+        self.last_selector_offset = self.last_selector_offset.max(Some(offset));
+        self.fixed_selectors.insert((column.index(), offset), value);
+        #[cfg(feature = "audit")]
+        if let Some(log) = &self.gate_log {
+            log.record_fixed(self.op.unwrap_or(UNTRACED), offset, column.index(), value);
+        }
+        self.region
+            .assign_fixed(|| annotation, column, offset, || Value::known(value))?;
+        Ok(())
+    }
+
+    /// Assigns a fixed cell at the given offset.
+    pub fn assign_fixed<A, AR>(
+        &mut self,
+        annotation: A,
+        column: Column<Fixed>,
+        offset: usize,
+        value: F,
+    ) -> Result<AssignedCell<F, F>, Error>
+    where
+        A: Fn() -> AR,
+        AR: Into<String>,
+    {
+        self.region
+            .assign_fixed(annotation, column, offset, || Value::known(value))
+    }
+
+    /// The value of a fixed column used as a selector, if assigned through
+    /// `enable_fixed_selector`.
+    pub fn fixed_selector(&self, column: Column<Fixed>, offset: usize) -> Option<F> {
+        self.fixed_selectors.get(&(column.index(), offset)).copied()
+    }
+
+    /// Constrains two cells are equal.
+    pub fn constrain_equal(&mut self, left: Cell, right: Cell) -> Result<(), Error> {
+        self.region.constrain_equal(left, right)
+    }
+
+    /// Constrains a cell to a constant.
+    pub fn constrain_constant(&mut self, cell: Cell, constant: F) -> Result<(), Error> {
+        self.region.constrain_constant(cell, constant)
+    }
+}
@@ -0,0 +1,56 @@
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Fixed;
+use halo2_proofs::plonk::Instance;
+
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::PrimeField;
+use crate::ECChip;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// A chip configured over resources shared with the other chips of a circuit.
+///
+/// Circuit frameworks allocate the shared resources once, e.g., the advice and
+/// fixed columns, then configure and construct each chip the same way.
+pub trait SubChip<F: Field>: Chip<F> + Sized {
+    /// The resources the chip is configured over.
+    type SharedResources;
+
+    /// Configures the chip over the shared resources.
+    fn configure(meta: &mut ConstraintSystem<F>, shared: Self::SharedResources) -> Self::Config;
+
+    /// Constructs the chip from its config.
+    fn construct(config: Self::Config) -> Self;
+}
+
+/// The columns `ECChip` is configured over.
+///
+/// The advice columns are in the first phase; the chip uses no challenge.
+#[derive(Clone, Copy, Debug)]
+pub struct SharedColumns {
+    pub advice: [Column<Advice>; 2],
+    pub constant: Column<Fixed>,
+    pub instance: Option<Column<Instance>>,
+}
+
+impl<C, F> SubChip<F> for ECChip<C, F>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField,
+{
+    type SharedResources = SharedColumns;
+
+    fn configure(meta: &mut ConstraintSystem<F>, shared: Self::SharedResources) -> Self::Config {
+        let [a, b] = shared.advice;
+        ECChip::configure_with_columns(meta, a, b, shared.constant, shared.instance)
+    }
+
+    fn construct(config: Self::Config) -> Self {
+        ECChip::construct(config)
+    }
+}
@@ -0,0 +1,86 @@
+use ark_std::test_rng;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::instructions::EccInstructions;
+use crate::sub_chip::SharedColumns;
+use crate::sub_chip::SubChip;
+
+#[derive(Default, Debug, Clone, Copy)]
+struct SubChipTestCircuit {
+    p1: Value<G1Affine>,
+    p2: Value<G1Affine>, // 2p1
+}
+
+impl Circuit<Fq> for SubChipTestCircuit {
+    type Config = ECConfig<G1Affine, Fq>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let shared = SharedColumns {
+            advice: [meta.advice_column(), meta.advice_column()],
+            constant: meta.fixed_column(),
+            instance: None,
+        };
+        <ECChip<G1Affine, Fq> as SubChip<Fq>>::configure(meta, shared)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = <ECChip<G1Affine, Fq> as SubChip<Fq>>::construct(config);
+
+        let p1 = ec_chip.witness_point(&mut layouter, self.p1)?;
+        let p2 = ec_chip.witness_point(&mut layouter, self.p2)?;
+        let p2_rec = ec_chip.double(&mut layouter, &p1)?;
+        ec_chip.constrain_equal(&mut layouter, &p2, &p2_rec)?;
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_sub_chip() {
+    let k = 6;
+
+    let mut rng = test_rng();
+    let p1 = G1::random(&mut rng);
+    let p2 = p1.double();
+
+    {
+        let circuit = SubChipTestCircuit {
+            p1: Value::known(p1.to_affine()),
+            p2: Value::known(p2.to_affine()),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        prover.assert_satisfied();
+    }
+
+    // error case: p2 != 2p1
+    {
+        let circuit = SubChipTestCircuit {
+            p1: Value::known(p1.to_affine()),
+            p2: Value::known((p2 + p1).to_affine()),
+        };
+        let prover = MockProver::run(k, &circuit, vec![]).unwrap();
+        assert!(prover.verify().is_err());
+    }
+}
@@ -0,0 +1,165 @@
+use alloc::vec::Vec;
+
+use crate::backend::Curve;
+use crate::backend::CurveAffine;
+use crate::backend::Group;
+use crate::backend::PrimeCurveAffine;
+use crate::error::NativeEccError;
+use crate::util::batch_normalize;
+use crate::util::coordinates;
+
+/// Number of doublings applied to the accumulator of `point_mul`, i.e.,
+/// the accumulator is initialized with the generator and ends up
+/// carrying an extra `2^256 * generator` term.
+pub(crate) const SCALAR_MUL_BITS: usize = 256;
+
+/// The correction point `-2^256 * generator` of the curve, and its coordinates.
+///
+/// `point_mul` starts its double-then-add loop from the generator
+/// (we do not have a cell representation for the infinity point),
+/// so this point is added at the end to cancel the offset.
+pub(crate) fn neg_generator_times_2_to_256<C, F>() -> Result<(C, F, F), NativeEccError>
+where
+    C: CurveAffine<Base = F>,
+{
+    neg_generator_times_2_to(SCALAR_MUL_BITS)
+}
+
+/// The correction point `-2^n * generator`, of a double-then-add loop over
+/// `n` bits, and its coordinates.
+///
+/// The point is computed once per curve and `n`, see `cached`.
+pub(crate) fn neg_generator_times_2_to<C, F>(n: usize) -> Result<(C, F, F), NativeEccError>
+where
+    C: CurveAffine<Base = F>,
+{
+    let p = cached(n, || {
+        let mut acc = C::generator().to_curve();
+        for _ in 0..n {
+            acc = acc.double();
+        }
+        (-acc).to_affine()
+    });
+    let (x, y) = coordinates(&p)?;
+    Ok((p, x, y))
+}
+
+/// The multiples `2^i * base` for `i in 0..n`, read by the fixed base add
+/// gate of `generator_mul` and `fixed_base_mul`, normalized with a single
+/// batch inversion.
+///
+/// The multiples of the generator are computed once per curve and `n`, see
+/// `cached`.
+pub(crate) fn fixed_base_multiples<C: CurveAffine>(base: &C, n: usize) -> Vec<C> {
+    let multiples = || {
+        let mut acc = base.to_curve();
+        let mut res = Vec::with_capacity(n);
+        for _ in 0..n {
+            res.push(acc);
+            acc = acc.double();
+        }
+        batch_normalize(&res)
+    };
+    if *base == C::generator() {
+        cached(n, multiples)
+    } else {
+        multiples()
+    }
+}
+
+/// The value of `compute` for the type T and `n`, computed on the first call
+/// and cloned afterwards. The values are keyed by their type, e.g., the
+/// correction point of a curve, so each curve has tables of its own.
+///
+/// Without `std`, the value is computed on each call.
+#[cfg(feature = "std")]
+fn cached<T>(n: usize, compute: impl FnOnce() -> T) -> T
+where
+    T: Clone + Send + Sync + 'static,
+{
+    use std::any::Any;
+    use std::any::TypeId;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use std::sync::OnceLock;
+    use std::sync::PoisonError;
+
+    type Cache = Mutex<HashMap<(TypeId, usize), Box<dyn Any + Send + Sync>>>;
+    static CACHE: OnceLock<Cache> = OnceLock::new();
+
+    let cache = CACHE.get_or_init(Default::default);
+    let key = (TypeId::of::<T>(), n);
+    let hit = cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .get(&key)
+        .and_then(|value| value.downcast_ref::<T>())
+        .cloned();
+    if let Some(value) = hit {
+        return value;
+    }
+
+    // computed without the lock; a concurrent miss computes the same value
+    let value = compute();
+    cache
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(key, Box::new(value.clone()));
+    value
+}
+
+#[cfg(not(feature = "std"))]
+fn cached<T>(_n: usize, compute: impl FnOnce() -> T) -> T {
+    compute()
+}
+
+#[cfg(all(test, feature = "pse"))]
+mod test {
+    use halo2_proofs::arithmetic::Field;
+    use halo2_proofs::halo2curves::group::Curve;
+    use halo2_proofs::halo2curves::group::Group;
+    use halo2_proofs::halo2curves::CurveAffine;
+    use halo2curves::grumpkin::Fq;
+    use halo2curves::grumpkin::Fr;
+    use halo2curves::grumpkin::G1Affine;
+    use halo2curves::grumpkin::G1;
+
+    use super::fixed_base_multiples;
+    use super::neg_generator_times_2_to;
+    use super::neg_generator_times_2_to_256;
+    use crate::constants::correction_point;
+
+    #[test]
+    fn test_grumpkin_correction_point() {
+        // the value that used to be hardcoded for Grumpkin
+        let (x, y) = correction_point::<Fq>().unwrap();
+
+        let (p, px, py) = neg_generator_times_2_to_256::<G1Affine, Fq>().unwrap();
+        assert_eq!(px, x);
+        assert_eq!(py, y);
+        assert_eq!(p, G1Affine::from_xy(x, y).unwrap());
+    }
+
+    #[test]
+    fn test_cached_tables() {
+        let g = G1::generator();
+        let h = (g * Fr::from(7)).to_affine();
+
+        // the first call fills the cache, the second reads it
+        for _ in 0..2 {
+            let (p, _, _) = neg_generator_times_2_to::<G1Affine, Fq>(64).unwrap();
+            assert_eq!(p, (-g * Fr::from(2).pow([64])).to_affine());
+
+            for base in [g.to_affine(), h] {
+                let multiples = fixed_base_multiples(&base, 8);
+                assert_eq!(multiples.len(), 8);
+                for (i, multiple) in multiples.iter().enumerate() {
+                    assert_eq!(*multiple, (base * Fr::from(1u64 << i)).to_affine());
+                }
+            }
+        }
+
+        // the cache is keyed by n too
+        assert_eq!(fixed_base_multiples(&g.to_affine(), 4).len(), 4);
+    }
+}
@@ -0,0 +1,191 @@
+//! Known-answer vectors of Grumpkin, to catch regressions of the curve
+//! constants and of the formulas of the gates.
+//!
+//! The vectors are computed independently of halo2curves and of the chip,
+//! with the textbook affine formulas over the integers. The coordinates and
+//! scalars are in decimal, as in `constants`.
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// A point, by its coordinates in decimal.
+#[derive(Clone, Copy, Debug)]
+pub struct PointVector {
+    pub x: &'static str,
+    pub y: &'static str,
+}
+
+impl PointVector {
+    /// The point; `None` if the coordinates do not fit in the base field or
+    /// are not on curve.
+    pub fn point<C: CurveAffine>(&self) -> Option<C>
+    where
+        C::Base: PrimeField,
+    {
+        let x = C::Base::from_str_vartime(self.x)?;
+        let y = C::Base::from_str_vartime(self.y)?;
+        Option::from(C::from_xy(x, y))
+    }
+}
+
+/// p + q.
+#[derive(Clone, Copy, Debug)]
+pub struct AddVector {
+    pub p: PointVector,
+    pub q: PointVector,
+    pub sum: PointVector,
+}
+
+/// 2 * p.
+#[derive(Clone, Copy, Debug)]
+pub struct DoubleVector {
+    pub p: PointVector,
+    pub double: PointVector,
+}
+
+/// s * p, for a scalar s in decimal.
+#[derive(Clone, Copy, Debug)]
+pub struct MulVector {
+    pub p: PointVector,
+    pub scalar: &'static str,
+    pub product: PointVector,
+}
+
+impl MulVector {
+    /// The scalar; `None` if it does not fit in the scalar field.
+    pub fn scalar<S: PrimeField>(&self) -> Option<S> {
+        S::from_str_vartime(self.scalar)
+    }
+}
+
+/// Additions p + q, for p != ±q.
+pub const ADD: &[AddVector] = &[
+    AddVector {
+        p: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+        q: PointVector {
+            x: "3078034153852398078128400807926804309327113743808504829582559963737223069694",
+            y: "12696890884641142049456609402511852099066095483298083855939691685001536962732",
+        },
+        sum: PointVector {
+            x: "18660890509582237958343981571981920822503400000196279471655180441138020044621",
+            y: "8902249110305491597038405103722863701255802573786510474664632793109847672620",
+        },
+    },
+    AddVector {
+        p: PointVector {
+            x: "12229279139087521908560794489267966517139449915173592433539394009359081620359",
+            y: "12096995292699515952722386974733884667125946823386040531322131902193094989869",
+        },
+        q: PointVector {
+            x: "6502298228793251914218452601347199200336821300374732886528232462753193470018",
+            y: "9407677376110273038006540221648729284102344671467345386528008239979586131147",
+        },
+        sum: PointVector {
+            x: "1988391795606846601479006934661846879888483061709603532752854109979634068437",
+            y: "17214484238118553757321334917973127463763154890600851766768106011087651167145",
+        },
+    },
+    AddVector {
+        p: PointVector {
+            x: "19765890682282814101644954065011050485475652384873853876112165209066185153655",
+            y: "11969979396425931635527825852054799130886601847069084999381463088439895241549",
+        },
+        q: PointVector {
+            x: "18392278067141051091597412259727162417010772468255915864715835856094026163873",
+            y: "16626915134689401974639831728929086967608039043887260204783088903956262569576",
+        },
+        sum: PointVector {
+            x: "15574124999183536814073360940679322199250641291369771350165202942139443126209",
+            y: "4372593017293868108463892685242999437646058110526699929550065336582346978876",
+        },
+    },
+];
+
+/// Doublings 2 * p.
+pub const DOUBLE: &[DoubleVector] = &[
+    DoubleVector {
+        p: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+        double: PointVector {
+            x: "3078034153852398078128400807926804309327113743808504829582559963737223069694",
+            y: "12696890884641142049456609402511852099066095483298083855939691685001536962732",
+        },
+    },
+    DoubleVector {
+        p: PointVector {
+            x: "3472012310425041633149259403815581056906690499552519301278299341829103561988",
+            y: "6804536111651694196420845025858303718850611160827614585600689824976083696363",
+        },
+        double: PointVector {
+            x: "2167838613858828790245736741355903852862759888593064635373076181925443078052",
+            y: "2301529599840868059446400854536238795956257757203494151868248065539356856035",
+        },
+    },
+];
+
+/// Scalar muls s * p, including the scalars 1, r - 1 and 2^128.
+pub const MUL: &[MulVector] = &[
+    MulVector {
+        p: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+        scalar: "1",
+        product: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+    },
+    MulVector {
+        p: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+        scalar: "21888242871839275222246405745257275088696311157297823662689037894645226208582",
+        product: PointVector {
+            x: "1",
+            y: "21888242871839275204614721864072299718383108512864252727949815652902133356757",
+        },
+    },
+    MulVector {
+        p: PointVector {
+            x: "12229279139087521908560794489267966517139449915173592433539394009359081620359",
+            y: "12096995292699515952722386974733884667125946823386040531322131902193094989869",
+        },
+        scalar: "2721993162925788897766085007677819525346429835302545939241072675233116581396",
+        product: PointVector {
+            x: "607034124019045715416598128368474532863088404202590273066800142889646353483",
+            y: "4614854112443255877579805455722983722429410198840296934482104536348372010874",
+        },
+    },
+    MulVector {
+        p: PointVector {
+            x: "18822980439876392669038541278116828468587694184253529133325397684418636073483",
+            y: "12056525309659684604763366424328462120152680986562895118773658907365498485574",
+        },
+        scalar: "18472045061556944722539328576859687912738555687952185421925497291327482317815",
+        product: PointVector {
+            x: "20792115067917344905710623095800530335461226732256601136400824141196595116763",
+            y: "21648074046454997007769039848647519184069520816247843090216215490165557225592",
+        },
+    },
+    MulVector {
+        p: PointVector {
+            x: "1",
+            y: "17631683881184975370165255887551781615748388533673675138860",
+        },
+        scalar: "340282366920938463463374607431768211456",
+        product: PointVector {
+            x: "15476314808301153468744958803190714559192263484857164741420741964129202671253",
+            y: "17053803688492601797819630176258266953429536685886388354044970427053987680053",
+        },
+    },
+];
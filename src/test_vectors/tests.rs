@@ -0,0 +1,97 @@
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::CurveAffine;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+
+use crate::calldata;
+use crate::test_vectors::PointVector;
+use crate::test_vectors::ADD;
+use crate::test_vectors::DOUBLE;
+use crate::test_vectors::MUL;
+use crate::testing::run_gadget;
+use crate::ArithOps;
+use crate::AssignedECPoint;
+use crate::NativeECOps;
+use crate::NativeEccError;
+use crate::RegionCtx;
+
+fn point(v: &PointVector) -> G1Affine {
+    v.point().unwrap()
+}
+
+/// Constrains p to the coordinates of the vector.
+fn constrain_vector(
+    ctx: &mut RegionCtx<Fq>,
+    p: &AssignedECPoint<G1Affine, Fq>,
+    v: &PointVector,
+) -> Result<(), NativeEccError> {
+    let v = point(v).coordinates().unwrap();
+    ctx.constrain_constant(p.x().cell(), *v.x())?;
+    ctx.constrain_constant(p.y().cell(), *v.y())?;
+    Ok(())
+}
+
+#[test]
+fn test_vectors_halo2curves() {
+    for v in ADD {
+        assert_eq!((point(&v.p) + point(&v.q)).to_affine(), point(&v.sum));
+    }
+    for v in DOUBLE {
+        assert_eq!((point(&v.p) + point(&v.p)).to_affine(), point(&v.double));
+    }
+    for v in MUL {
+        let s = v.scalar::<Fr>().unwrap();
+        assert_eq!((point(&v.p) * s).to_affine(), point(&v.product));
+    }
+}
+
+#[test]
+fn test_vectors_chip() {
+    let prover = run_gadget::<G1Affine, Fq, _>(13, |chip, ctx| {
+        let config = chip.config();
+        for v in ADD {
+            let p = chip.load_private_point(ctx, config, Value::known(point(&v.p)))?;
+            let q = chip.load_private_point(ctx, config, Value::known(point(&v.q)))?;
+            let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+            let sum = chip.conditional_point_add(ctx, config, &p, &q, &bit)?;
+            constrain_vector(ctx, &sum, &v.sum)?;
+        }
+        for v in DOUBLE {
+            let p = chip.load_private_point(ctx, config, Value::known(point(&v.p)))?;
+            let double = chip.point_double(ctx, config, &p)?;
+            constrain_vector(ctx, &double, &v.double)?;
+        }
+        for v in MUL {
+            let s = Value::known(v.scalar::<Fr>().unwrap());
+            let product = chip.point_mul(ctx, config, Value::known(point(&v.p)), s)?;
+            constrain_vector(ctx, &product, &v.product)?;
+        }
+        Ok(())
+    })
+    .unwrap();
+    prover.assert_satisfied();
+
+    // error case: the sum of the first add vector is the double of the first double vector
+    let prover = run_gadget::<G1Affine, Fq, _>(6, |chip, ctx| {
+        let config = chip.config();
+        let v = &ADD[0];
+        let p = chip.load_private_point(ctx, config, Value::known(point(&v.p)))?;
+        let q = chip.load_private_point(ctx, config, Value::known(point(&v.q)))?;
+        let bit = chip.load_private_field(ctx, config, Value::known(Fq::ONE))?;
+        let sum = chip.conditional_point_add(ctx, config, &p, &q, &bit)?;
+        constrain_vector(ctx, &sum, &DOUBLE[0].double)?;
+        Ok(())
+    })
+    .unwrap();
+    assert!(prover.verify().is_err());
+}
+
+#[test]
+fn test_vectors_calldata() {
+    let points: Vec<G1Affine> = ADD.iter().map(|v| point(&v.sum)).collect();
+    let data = calldata::encode_points(&points).unwrap();
+    assert_eq!(calldata::decode_points::<G1Affine>(&data).unwrap(), points);
+}
@@ -0,0 +1,169 @@
+//! MockProver scaffolding, to unit test compositions of the chip without
+//! writing a `Circuit`.
+//!
+//! ```ignore
+//! let prover = run_gadget::<G1Affine, Fq, _>(10, |chip, ctx| {
+//!     let p = chip.load_private_point(ctx, chip.config(), p)?;
+//!     chip.point_double(ctx, chip.config(), &p)?;
+//!     Ok(())
+//! })?;
+//! prover.assert_satisfied();
+//! ```
+
+use alloc::string::String;
+use alloc::string::ToString;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+
+use halo2_proofs::circuit::Chip;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::dev::VerifyFailure;
+use halo2_proofs::halo2curves::ff::FromUniformBytes;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+
+use crate::backend::CurveAffine;
+use crate::backend::Halo2Error as Error;
+use crate::backend::PrimeField;
+use crate::region_ctx::RegionCtx;
+use crate::ECChip;
+use crate::ECConfig;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod strategies;
+
+/// A circuit assigning a gadget in a single region, then padding the region.
+///
+/// The gadget is run as is by `without_witnesses`; it is meant for the
+/// MockProver, not for key generation.
+pub struct GadgetCircuit<'g, C, F, G>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+    G: Fn(&ECChip<C, F>, &mut RegionCtx<F>) -> Result<(), NativeEccError>,
+{
+    gadget: &'g G,
+    _phantom: PhantomData<C>,
+}
+
+impl<'g, C, F, G> GadgetCircuit<'g, C, F, G>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+    G: Fn(&ECChip<C, F>, &mut RegionCtx<F>) -> Result<(), NativeEccError>,
+{
+    pub fn new(gadget: &'g G) -> Self {
+        Self {
+            gadget,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<'g, C, F, G> Circuit<F> for GadgetCircuit<'g, C, F, G>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]>,
+    G: Fn(&ECChip<C, F>, &mut RegionCtx<F>) -> Result<(), NativeEccError>,
+{
+    type Config = ECConfig<C, F>;
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::new(self.gadget)
+    }
+
+    fn configure(meta: &mut ConstraintSystem<F>) -> Self::Config {
+        ECChip::configure(meta)
+    }
+
+    fn synthesize(
+        &self,
+        config: Self::Config,
+        mut layouter: impl Layouter<F>,
+    ) -> Result<(), Error> {
+        let chip = ECChip::construct(config);
+
+        layouter.assign_region(
+            || "gadget",
+            |region| {
+                let mut ctx = chip.region_ctx(region);
+                (self.gadget)(&chip, &mut ctx)?;
+                chip.pad(&mut ctx, chip.config())?;
+                Ok(())
+            },
+        )
+    }
+}
+
+/// The names of the constraints the MockProver reports as not satisfied, e.g.,
+/// "ec add: line", without duplicates; see `ECConfig::constraint_names`.
+///
+/// ```ignore
+/// let prover = run_gadget::<G1Affine, Fq, _>(10, gadget)?;
+/// assert_eq!(failed_constraints(&prover), ["ec add: on curve"]);
+/// ```
+pub fn failed_constraints<F>(prover: &MockProver<F>) -> Vec<String>
+where
+    F: PrimeField + FromUniformBytes<64> + Ord,
+{
+    let mut names = vec![];
+    for failure in prover.verify().err().unwrap_or_default() {
+        if let VerifyFailure::ConstraintNotSatisfied { constraint, .. } = failure {
+            // displayed as "Constraint <index> ('<name>') in gate <index> ('<gate>')"
+            let constraint = constraint.to_string();
+            let name = constraint
+                .split_once("('")
+                .and_then(|(_, rest)| rest.split_once("')"))
+                .map(|(name, _)| name.to_string());
+            if let Some(name) = name.filter(|name| !names.contains(name)) {
+                names.push(name);
+            }
+        }
+    }
+    names
+}
+
+/// Runs the MockProver over a gadget, with `2^k` rows and no public input.
+pub fn run_gadget<C, F, G>(k: u32, gadget: G) -> Result<MockProver<F>, Error>
+where
+    C: CurveAffine<Base = F>,
+    F: PrimeField<Repr = [u8; 32]> + FromUniformBytes<64> + Ord,
+    G: Fn(&ECChip<C, F>, &mut RegionCtx<F>) -> Result<(), NativeEccError>,
+{
+    MockProver::run(k, &GadgetCircuit::new(&gadget), vec![])
+}
+
+/// Renders the layout of a circuit to a png, with the name of each region,
+/// with the `dev-graph` feature.
+///
+/// Assign each op in its own region, e.g., with `ECChip::assign_scoped`, to
+/// label the rows it uses.
+#[cfg(feature = "dev-graph")]
+pub fn render_layout<F, ConcreteCircuit>(
+    k: u32,
+    circuit: &ConcreteCircuit,
+    title: &str,
+    path: &std::path::Path,
+) -> Result<(), alloc::boxed::Box<dyn std::error::Error>>
+where
+    F: PrimeField,
+    ConcreteCircuit: Circuit<F>,
+{
+    use plotters::prelude::*;
+
+    let root = BitMapBackend::new(path, (1024, 2048)).into_drawing_area();
+    root.fill(&WHITE)?;
+    let root = root.titled(title, ("sans-serif", 40))?;
+    halo2_proofs::dev::CircuitLayout::default()
+        .show_labels(true)
+        .mark_equality_cells(true)
+        .render(k, circuit, &root)?;
+    root.present()?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+//! proptest strategies for the inputs of the chip, with the `test-utils`
+//! feature.
+//!
+//! The strategies are generic over the curve; the points are multiples of the
+//! generator by uniform scalars, so they are never the identity.
+//!
+//! ```ignore
+//! proptest! {
+//!     #[test]
+//!     fn double_then_add((p, q) in distinct_points::<G1Affine>()) {
+//!         // ...
+//!     }
+//! }
+//! ```
+
+use proptest::prelude::*;
+
+use crate::backend::Curve;
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+
+/// Uniform scalars, including 0.
+pub fn scalars<S: PrimeField>() -> impl Strategy<Value = S> {
+    any::<(u128, u128)>().prop_map(|(hi, lo)| {
+        // hi * 2^128 + lo, reduced
+        S::from_u128(hi) * S::from_u128(1 << 64).square() + S::from_u128(lo)
+    })
+}
+
+/// Non zero scalars.
+pub fn nonzero_scalars<S: PrimeField>() -> impl Strategy<Value = S> {
+    scalars::<S>().prop_filter("zero scalar", |s| !bool::from(s.is_zero()))
+}
+
+/// Points on curve, other than the identity.
+pub fn points<C: CurveAffine>() -> impl Strategy<Value = C>
+where
+    C::ScalarExt: PrimeField,
+{
+    nonzero_scalars::<C::ScalarExt>().prop_map(|s| (C::generator() * s).to_affine())
+}
+
+/// Pairs (p, q) with q != ±p, i.e., the operands the incomplete addition covers.
+pub fn distinct_points<C: CurveAffine>() -> impl Strategy<Value = (C, C)>
+where
+    C::ScalarExt: PrimeField,
+{
+    (points::<C>(), points::<C>()).prop_filter("p = ±q", |(p, q)| p != q && *p != -*q)
+}
+
+/// Pairs (p, ±p), i.e., the exceptional cases of the incomplete addition.
+pub fn exceptional_add_operands<C: CurveAffine>() -> impl Strategy<Value = (C, C)>
+where
+    C::ScalarExt: PrimeField,
+{
+    (points::<C>(), any::<bool>()).prop_map(|(p, neg)| (p, if neg { -p } else { p }))
+}
+
+/// Little endian bits of a scalar of `n` bits.
+pub fn bits(n: usize) -> impl Strategy<Value = Vec<bool>> {
+    proptest::collection::vec(any::<bool>(), n)
+}
@@ -1,109 +1,356 @@
-use std::u128;
+#[cfg(feature = "annotate")]
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
 
+use halo2_proofs::circuit::AssignedCell;
 use halo2_proofs::circuit::Value;
-use halo2_proofs::halo2curves::ff::PrimeField;
-use halo2curves::CurveAffine;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
 
-pub(crate) fn leak<T: Copy + Default>(a: &Value<&T>) -> T {
-    let mut t = T::default();
-    a.map(|x| t = *x);
-    t
+use crate::backend::Curve;
+use crate::backend::CurveAffine;
+use crate::backend::Field;
+use crate::backend::Group;
+use crate::backend::PrimeCurveAffine;
+use crate::backend::PrimeField;
+use crate::error::NativeEccError;
+
+/// Annotation of an operand of an op, e.g., "x3 of conditional add".
+///
+/// Empty unless the `annotate` feature is enabled.
+pub(crate) fn annotation(op: &'static str, operand: &'static str) -> String {
+    #[cfg(feature = "annotate")]
+    {
+        format!("{} of {}", operand, op)
+    }
+    #[cfg(not(feature = "annotate"))]
+    {
+        let _ = (op, operand);
+        String::new()
+    }
+}
+
+/// Affine coordinates (x, y) of a point.
+/// Returns an error for the identity point.
+pub(crate) fn coordinates<C: CurveAffine>(p: &C) -> Result<(C::Base, C::Base), NativeEccError> {
+    let coordinates = p.coordinates();
+    if bool::from(coordinates.is_none()) {
+        return Err(NativeEccError::PointAtInfinity);
+    }
+    let coordinates = coordinates.unwrap();
+    Ok((*coordinates.x(), *coordinates.y()))
+}
+
+/// p * s, computed natively.
+pub(crate) fn point_mul_native<C: CurveAffine>(p: C, s: C::ScalarExt) -> C {
+    (p.to_curve() * s).to_affine()
 }
 
-/// Split a scalar field elements into high and low and
-/// store the high and low in base field.
-pub(crate) fn field_decompose_u128<S>(e: &S) -> (u128, u128)
+/// Affine coordinates (x, y) of a point value.
+/// Returns an error if the point is known to be the identity.
+#[allow(clippy::type_complexity)]
+pub(crate) fn coordinates_value<C: CurveAffine>(
+    p: Value<C>,
+) -> Result<Value<(C::Base, C::Base)>, NativeEccError> {
+    let mut res = Ok(());
+    let xy = p.map(|p| match coordinates(&p) {
+        Ok(xy) => xy,
+        Err(e) => {
+            res = Err(e);
+            (C::Base::ZERO, C::Base::ZERO)
+        }
+    });
+    res.map(|_| xy)
+}
+
+/// The values of assigned bits.
+pub(crate) fn bits_value<F: Field>(bits: &[AssignedCell<F, F>]) -> Value<Vec<bool>> {
+    bits.iter()
+        .map(|b| b.value().map(|b| *b != F::ZERO))
+        .collect()
+}
+
+/// Witness of the double-then-add ladder of `point_mul`, from `start` and
+/// for the little endian bits of the scalar: the double and the result of
+/// the conditional add of each step, from the most significant bit.
+///
+/// The steps are computed in projective coordinates and normalized with a
+/// single batch inversion, instead of one inversion per step.
+pub(crate) fn double_then_add_witness<C: CurveAffine>(start: C, p: C, bits: &[bool]) -> Vec<C> {
+    let mut acc = start.to_curve();
+    let mut steps = Vec::with_capacity(2 * bits.len());
+    for b in bits.iter().rev() {
+        acc = acc.double();
+        steps.push(acc);
+        if *b {
+            acc += p;
+        }
+        steps.push(acc);
+    }
+    batch_normalize(&steps)
+}
+
+/// Witness of the fixed base ladder of `generator_mul`, from `start` and for
+/// the bits of the scalar, least significant first: the result of the
+/// conditional add of `bases[i]` for the i-th bit.
+///
+/// As for `double_then_add_witness`, the steps share a single batch inversion.
+pub(crate) fn fixed_base_witness<C: CurveAffine>(start: C, bases: &[C], bits: &[bool]) -> Vec<C> {
+    let mut acc = start.to_curve();
+    let mut steps = Vec::with_capacity(bits.len());
+    for (b, base) in bits.iter().zip(bases) {
+        if *b {
+            acc += *base;
+        }
+        steps.push(acc);
+    }
+    batch_normalize(&steps)
+}
+
+/// Witness of the x-only ladder of `x_only_point_mul`, from the accumulators
+/// (p, 2p) and for the little endian bits of the scalar below its top bit:
+/// the x coordinates of the accumulators (r0, r1) after each step, from the
+/// most significant bit.
+///
+/// Errors if an accumulator is the identity, which has no x coordinate.
+pub(crate) fn x_only_ladder_witness<C: CurveAffine>(
+    p: C,
+    bits: &[bool],
+) -> Result<Vec<C::Base>, NativeEccError> {
+    let mut r0 = p.to_curve();
+    let mut r1 = r0.double();
+    let mut steps = Vec::with_capacity(2 * bits.len());
+    for b in bits.iter().rev() {
+        (r0, r1) = if *b {
+            (r0 + r1, r1.double())
+        } else {
+            (r0.double(), r0 + r1)
+        };
+        steps.extend([r0, r1]);
+    }
+    batch_normalize::<C>(&steps)
+        .iter()
+        .map(|p| coordinates(p).map(|(x, _)| x))
+        .collect()
+}
+
+/// The window table `[1 * p, 2 * p, ..., (2^window_bits - 1) * p]` of a
+/// variable base p, for a windowed mul; the entry 0 * p is left out, as the
+/// identity has no (x, y) representation.
+pub(crate) fn window_table<C: CurveAffine>(p: C, window_bits: usize) -> Vec<C> {
+    let size = (1 << window_bits) - 1;
+    let mut acc = p.to_curve();
+    let mut entries = Vec::with_capacity(size);
+    for _ in 0..size {
+        entries.push(acc);
+        acc += p;
+    }
+    batch_normalize(&entries)
+}
+
+/// Converts projective points to affine with Montgomery's batch inversion.
+///
+/// With the `parallel` feature, the points are split into a chunk per thread
+/// of rayon, each with a batch inversion of its own.
+pub(crate) fn batch_normalize<C: CurveAffine>(points: &[C::CurveExt]) -> Vec<C> {
+    let mut res = vec![C::identity(); points.len()];
+    #[cfg(feature = "parallel")]
+    {
+        let chunk = points.len().div_ceil(rayon::current_num_threads()).max(1);
+        points
+            .par_chunks(chunk)
+            .zip(res.par_chunks_mut(chunk))
+            .for_each(|(points, res)| C::CurveExt::batch_normalize(points, res));
+    }
+    #[cfg(not(feature = "parallel"))]
+    C::CurveExt::batch_normalize(points, &mut res);
+    res
+}
+
+/// The 32 bytes compressed encoding of a point: its x coordinate in little
+/// endian, with the parity of y in the top bit.
+pub(crate) fn compress<C>(p: &C) -> Result<[u8; 32], NativeEccError>
 where
-    S: PrimeField<Repr = [u8; 32]>,
+    C: CurveAffine,
+    C::Base: PrimeField<Repr = [u8; 32]>,
 {
+    let (x, y) = coordinates(p)?;
+    let mut res = x.to_repr();
+    res[31] |= (y.to_repr()[0] & 1) << 7;
+    Ok(res)
+}
+
+/// The y coordinate of parity `odd` of the point of x coordinate x on
+/// y^2 = x^3 + b, if any.
+pub(crate) fn decompress_y<F>(x: F, b: F, odd: bool) -> Option<F>
+where
+    F: PrimeField<Repr = [u8; 32]>,
+{
+    let y = Option::<F>::from((x.square() * x + b).sqrt())?;
+    Some(if (y.to_repr()[0] & 1 == 1) == odd {
+        y
+    } else {
+        -y
+    })
+}
+
+/// The 128 bits limbs of a field element, least significant first.
+///
+/// The bytes of the repr are read in little endian order, as the reprs of the
+/// fields of halo2curves and pasta; a repr of any length is split into 16
+/// bytes limbs, the last one zero-padded.
+pub fn field_limbs_u128<S: PrimeField>(e: &S) -> Vec<u128> {
+    e.to_repr().as_ref().chunks(16).map(le_limb).collect()
+}
+
+/// Splits a field element of at most 256 bits into its (high, low) 128 bits
+/// limbs, e = high * 2^128 + low; see `field_limbs_u128` for the endianness.
+///
+/// The split is unchecked; `NativeECOps::decompose_field_limbs` constrains it
+/// in-circuit. Panics if the field has more than 256 bits.
+pub fn field_decompose_u128<S: PrimeField>(e: &S) -> (u128, u128) {
+    assert!(S::NUM_BITS <= 256, "a field of more than 256 bits");
     let repr = e.to_repr();
-    let high = u128::from_le_bytes(repr[16..].try_into().unwrap());
-    let low = u128::from_le_bytes(repr[..16].try_into().unwrap());
+    let mut limbs = repr.as_ref().chunks(16).map(le_limb);
+    let low = limbs.next().unwrap_or_default();
+    let high = limbs.next().unwrap_or_default();
     (high, low)
 }
 
-/// Split a scalar field elements into high and low and
-/// store the high and low in base field.
-#[allow(dead_code)]
-pub(crate) fn field_decompose<F, S>(e: &S) -> (F, F)
+/// `field_decompose_u128`, with the (high, low) limbs as elements of F.
+pub fn field_decompose<F, S>(e: &S) -> (F, F)
 where
     F: PrimeField,
+    S: PrimeField,
+{
+    let (high, low) = field_decompose_u128(e);
+    (F::from_u128(high), F::from_u128(low))
+}
+
+/// A limb of at most 16 little endian bytes.
+#[inline]
+fn le_limb(bytes: &[u8]) -> u128 {
+    let mut limb = [0u8; 16];
+    limb[..bytes.len()].copy_from_slice(bytes);
+    u128::from_le_bytes(limb)
+}
+
+/// The (high, low) 128 bits limbs of s + c * r, where r is the order of the
+/// field of s and c is the smallest multiple for which the sum has its 256th
+/// bit set, i.e., the scalar of the same multiples with a fixed top bit.
+///
+/// None if the sum jumps over 2^256, which requires r > 2^255.
+pub(crate) fn scalar_with_top_bit<S>(s: &S) -> Option<(u128, u128)>
+where
     S: PrimeField<Repr = [u8; 32]>,
 {
-    let repr = e.to_repr();
-    let high = F::from_u128(u128::from_le_bytes(repr[16..].try_into().unwrap()));
-    let low = F::from_u128(u128::from_le_bytes(repr[..16].try_into().unwrap()));
-    (high, low)
+    // r = (r - 1) + 1
+    let (m_hi, m_lo) = field_decompose_u128(&-S::ONE);
+    let (r_lo, carry) = m_lo.overflowing_add(1);
+    let r_hi = m_hi + u128::from(carry);
+
+    let (mut high, mut low) = field_decompose_u128(s);
+    while high >> 127 == 0 {
+        let (sum_lo, carry) = low.overflowing_add(r_lo);
+        high = high.checked_add(r_hi)?.checked_add(u128::from(carry))?;
+        low = sum_lo;
+    }
+    Some((high, low))
 }
 
 #[allow(dead_code)]
 pub(crate) fn to_le_bits<F: PrimeField<Repr = [u8; 32]>>(e: &F) -> Vec<bool> {
-    let mut res = vec![];
-    let repr = e.to_repr();
-    for e in repr.iter() {
-        res.extend_from_slice(byte_to_le_bits(e).as_slice())
-    }
-    res
+    le_bits(e).collect()
 }
 
-#[inline]
-fn byte_to_le_bits(b: &u8) -> Vec<bool> {
-    let mut t = *b;
-    let mut res = vec![];
-    for _ in 0..8 {
-        res.push(t & 1 == 1);
-        t >>= 1;
-    }
-    res
+/// The little endian bits of the repr of a field element, without allocation.
+pub(crate) fn le_bits<F: PrimeField<Repr = [u8; 32]>>(
+    e: &F,
+) -> impl DoubleEndedIterator<Item = bool> {
+    e.to_repr().into_iter().flat_map(byte_le_bits)
+}
+
+/// The little endian bits of a u128, without allocation.
+pub(crate) fn u128_le_bits(a: u128) -> impl DoubleEndedIterator<Item = bool> {
+    a.to_le_bytes().into_iter().flat_map(byte_le_bits)
 }
 
 #[inline]
-pub(crate) fn decompose_u128(a: &u128) -> Vec<u64> {
-    a.to_le_bytes()
-        .iter()
-        .flat_map(|x| {
-            byte_to_le_bits(x)
-                .iter()
-                .map(|&x| x as u64)
-                .collect::<Vec<_>>()
+fn byte_le_bits(b: u8) -> impl DoubleEndedIterator<Item = bool> {
+    (0..8).map(move |i| (b >> i) & 1 == 1)
+}
+
+/// The `len` little endian digits of the width-w NAF of k: each digit is 0
+/// or odd in (-2^(w-1), 2^(w-1)), and any w consecutive digits have at most
+/// one nonzero digit.
+///
+/// `len` must be at least 129 for the NAF of any u128 to fit.
+#[cfg(feature = "pse")]
+pub(crate) fn wnaf(k: u128, w: u32, len: usize) -> Vec<i64> {
+    let window = 1i64 << w;
+    let mut k = k;
+    (0..len)
+        .map(|_| {
+            let mut carry = false;
+            let d = if k & 1 == 1 {
+                let d = (k % window as u128) as i64;
+                let d = if d >= window / 2 { d - window } else { d };
+                // k - d clears the low w bits of k
+                if d < 0 {
+                    (k, carry) = k.overflowing_add(d.unsigned_abs().into());
+                } else {
+                    k -= d as u128;
+                }
+                d
+            } else {
+                0
+            };
+            k = (k >> 1) | (u128::from(carry) << 127);
+            d
         })
         .collect()
 }
 
 #[inline]
-// hardcoded value for `-2^256 * generator` for Grumpkin curve
-pub(crate) fn neg_generator_times_2_to_256<C, F>() -> (C, F, F)
-where
-    F: PrimeField<Repr = [u8; 32]>,
-    C: CurveAffine<Base = F>,
-{
-    let x = F::from_str_vartime(
-        "18292374296067206172215749431916515128228165256807037435601971767767562625877",
-    )
-    .unwrap();
-    let y = F::from_str_vartime(
-        "8411761026004062292626067694055242675827541323706122037355419552115320964415",
-    )
-    .unwrap();
-    (C::from_xy(x, y).unwrap(), x, y)
-}
-
-#[cfg(test)]
+pub(crate) fn decompose_u128(a: &u128) -> Vec<u64> {
+    u128_le_bits(*a).map(u64::from).collect()
+}
+
+#[cfg(all(test, feature = "pse"))]
 mod test {
+    use halo2_proofs::arithmetic::CurveAffine;
     use halo2_proofs::arithmetic::Field;
+    use halo2_proofs::halo2curves::ff::PrimeField;
+    use halo2_proofs::halo2curves::group::Curve;
+    use halo2_proofs::halo2curves::group::Group;
     use halo2curves::grumpkin::Fq;
     use halo2curves::grumpkin::Fr;
+    use halo2curves::grumpkin::G1Affine;
+    use halo2curves::grumpkin::G1;
 
-    use crate::util::byte_to_le_bits;
+    use crate::util::byte_le_bits;
+    use crate::util::compress;
+    use crate::util::decompress_y;
+    use crate::util::double_then_add_witness;
+    use crate::util::fixed_base_witness;
+    use crate::util::scalar_with_top_bit;
     use crate::util::to_le_bits;
+    use crate::util::window_table;
+    use crate::util::wnaf;
+    use crate::util::x_only_ladder_witness;
 
     use super::decompose_u128;
     use super::field_decompose;
+    use super::field_decompose_u128;
+    use super::field_limbs_u128;
+    use super::u128_le_bits;
 
     #[test]
     fn test_to_bites() {
         assert_eq!(
-            byte_to_le_bits(&4),
+            byte_le_bits(4).collect::<Vec<_>>(),
             vec![false, false, true, false, false, false, false, false]
         );
 
@@ -138,16 +385,142 @@ mod test {
     fn test_field_decom() {
         let mut rng = ark_std::test_rng();
         let a = Fr::random(&mut rng);
-        let (_high, _low) = field_decompose::<Fq, Fr>(&a);
+        let (high, low) = field_decompose::<Fr, Fr>(&a);
+        let two_to_128 = Fr::from_u128(1 << 64).square();
+        assert_eq!(high * two_to_128 + low, a);
 
-        // println!("{:?}", a);
-        // println!("{:?}", high);
-        // println!("{:?}", low);
+        let (high, low) = field_decompose_u128(&a);
+        assert_eq!(field_limbs_u128(&a), vec![low, high]);
+        assert_eq!(field_decompose_u128(&Fr::from_u128(7)), (0, 7));
+        assert_eq!(field_decompose_u128(&-Fr::ONE).0 >> 120, 0x30);
 
         let a = u128::from_le_bytes([1; 16]);
-        let _bits = decompose_u128(&a);
+        let bits = decompose_u128(&a);
+        for (i, bit) in bits.iter().enumerate() {
+            assert_eq!(*bit, u64::from(i % 8 == 0), "{}-th bit failed", i);
+        }
         // println!("{0:x?}", a);
         // println!("{:?}", bits);
         // panic!()
     }
+
+    #[test]
+    fn test_ladder_witness() {
+        let mut rng = ark_std::test_rng();
+        let start = G1::random(&mut rng);
+        let p = G1::random(&mut rng);
+        let bits = [true, false, true, true];
+
+        let mut acc = start;
+        let mut expected = vec![];
+        for b in bits.into_iter().rev() {
+            acc = acc.double();
+            expected.push(acc.to_affine());
+            if b {
+                acc += p;
+            }
+            expected.push(acc.to_affine());
+        }
+        assert_eq!(
+            double_then_add_witness(start.to_affine(), p.to_affine(), &bits),
+            expected
+        );
+
+        let bases = [p, p.double(), start, p + start].map(|b| b.to_affine());
+        let mut acc = start;
+        let mut expected = vec![];
+        for (b, base) in bits.iter().zip(bases) {
+            if *b {
+                acc += base;
+            }
+            expected.push(acc.to_affine());
+        }
+        assert_eq!(
+            fixed_base_witness(start.to_affine(), &bases, &bits),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_x_only_ladder_witness() {
+        let mut rng = ark_std::test_rng();
+        let p = G1::random(&mut rng);
+        let bits = [true, false, true, true];
+
+        // the scalar 0b11101, with the top bit
+        let mut expected = vec![];
+        let mut k = 1u64;
+        for b in bits.into_iter().rev() {
+            k = 2 * k + u64::from(b);
+            for m in [k, k + 1] {
+                let (x, _) = crate::util::coordinates(&(p * Fr::from(m)).to_affine()).unwrap();
+                expected.push(x);
+            }
+        }
+        assert_eq!(
+            x_only_ladder_witness(p.to_affine(), &bits).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_window_table() {
+        let p = G1::random(ark_std::test_rng());
+        let table = window_table(p.to_affine(), 3);
+        assert_eq!(table.len(), 7);
+        for (j, entry) in table.iter().enumerate() {
+            assert_eq!(*entry, (p * Fr::from(j as u64 + 1)).to_affine());
+        }
+    }
+
+    #[test]
+    fn test_scalar_with_top_bit() {
+        let two_to_128 = Fr::from_u128(1 << 64).square();
+        for s in [Fr::ZERO, Fr::ONE, -Fr::ONE, Fr::random(ark_std::test_rng())] {
+            let (high, low) = scalar_with_top_bit(&s).unwrap();
+            assert_eq!(high >> 127, 1);
+            assert_eq!(Fr::from_u128(high) * two_to_128 + Fr::from_u128(low), s);
+        }
+    }
+
+    #[test]
+    fn test_compress() {
+        let mut rng = ark_std::test_rng();
+        for _ in 0..4 {
+            let p = G1::random(&mut rng).to_affine();
+            let (x, y) = crate::util::coordinates(&p).unwrap();
+            let bytes = compress(&p).unwrap();
+            let odd = bytes[31] >> 7 == 1;
+            assert_eq!(odd, y.to_repr()[0] & 1 == 1);
+
+            let mut x_bytes = bytes;
+            x_bytes[31] &= 0x7f;
+            assert_eq!(Fq::from_repr(x_bytes).unwrap(), x);
+            assert_eq!(decompress_y(x, G1Affine::b(), odd), Some(y));
+            assert_eq!(decompress_y(x, G1Affine::b(), !odd), Some(-y));
+        }
+    }
+
+    #[test]
+    fn test_wnaf() {
+        let two = Fq::from(2);
+        for k in [0, 1, 7, 8, 0xdead_beef, u128::MAX, u128::MAX - 3] {
+            let digits = wnaf(k, 4, 129);
+            let sum = digits.iter().rev().fold(Fq::ZERO, |acc, d| {
+                let d = if *d < 0 {
+                    -Fq::from(d.unsigned_abs())
+                } else {
+                    Fq::from(*d as u64)
+                };
+                acc * two + d
+            });
+            assert_eq!(sum, Fq::from_u128(k));
+            for (i, d) in digits.iter().enumerate() {
+                assert!(*d == 0 || (d % 2 != 0 && d.abs() < 8));
+                if *d != 0 {
+                    assert!(digits[i + 1..].iter().take(3).all(|d| *d == 0));
+                }
+            }
+        }
+    }
 }
@@ -0,0 +1,123 @@
+//! A serializable bundle of witness values, so that the witness can be
+//! generated by another process than the one synthesizing the circuit.
+//!
+//! Points and scalars are stored as the little endian reprs of their
+//! coordinates, and are validated when decoded. With the `serde` feature,
+//! the bundle implements `Serialize` and `Deserialize`.
+//!
+//! ```ignore
+//! // witness service
+//! let mut bundle = EcWitnessBundle::default();
+//! bundle.push_point(&p)?;
+//! bundle.push_scalar(&s);
+//! let json = serde_json::to_string(&bundle)?;
+//!
+//! // prover
+//! let bundle: EcWitnessBundle = serde_json::from_str(&json)?;
+//! let circuit = MulCircuit { p: bundle.point_values()?[0], s: bundle.scalar_values()?[0] };
+//! ```
+
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::Value;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::util::coordinates;
+use crate::AssignedECPoint;
+use crate::NativeEccError;
+
+#[cfg(all(test, feature = "pse"))]
+mod tests;
+
+/// Points, scalars and bits of a witness.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EcWitnessBundle {
+    /// The (x, y) coordinates of the points.
+    pub points: Vec<([u8; 32], [u8; 32])>,
+    /// The scalars.
+    pub scalars: Vec<[u8; 32]>,
+    /// Bits, e.g., the conditions of the adds or a decomposed scalar.
+    pub bits: Vec<bool>,
+}
+
+impl EcWitnessBundle {
+    /// Adds a point; the identity has no (x, y) coordinates.
+    pub fn push_point<C>(&mut self, p: &C) -> Result<(), NativeEccError>
+    where
+        C: CurveAffine,
+        C::Base: PrimeField<Repr = [u8; 32]>,
+    {
+        let (x, y) = coordinates(p)?;
+        self.points.push((x.to_repr(), y.to_repr()));
+        Ok(())
+    }
+
+    /// Adds the value of an assigned point, e.g., to dump the witness of a run.
+    ///
+    /// Does nothing if the value is unknown, e.g., during key generation.
+    pub fn push_assigned_point<C, F>(
+        &mut self,
+        p: &AssignedECPoint<C, F>,
+    ) -> Result<(), NativeEccError>
+    where
+        C: CurveAffine<Base = F>,
+        F: PrimeField<Repr = [u8; 32]>,
+    {
+        let mut res = Ok(());
+        p.value().map(|p| res = self.push_point(&p));
+        res
+    }
+
+    /// Adds a scalar.
+    pub fn push_scalar<S: PrimeField<Repr = [u8; 32]>>(&mut self, s: &S) {
+        self.scalars.push(s.to_repr());
+    }
+
+    /// Adds bits.
+    pub fn push_bits(&mut self, bits: impl IntoIterator<Item = bool>) {
+        self.bits.extend(bits);
+    }
+
+    /// The points, checked to be on curve.
+    pub fn points<C>(&self) -> Result<Vec<C>, NativeEccError>
+    where
+        C: CurveAffine,
+        C::Base: PrimeField<Repr = [u8; 32]>,
+    {
+        self.points
+            .iter()
+            .map(|(x, y)| {
+                let x = decode::<C::Base>(x)?;
+                let y = decode::<C::Base>(y)?;
+                Option::from(C::from_xy(x, y)).ok_or(NativeEccError::NotOnCurve)
+            })
+            .collect()
+    }
+
+    /// The scalars, checked to be canonical.
+    pub fn scalars<S: PrimeField<Repr = [u8; 32]>>(&self) -> Result<Vec<S>, NativeEccError> {
+        self.scalars.iter().map(decode::<S>).collect()
+    }
+
+    /// The points as known values, e.g., for the fields of a circuit.
+    pub fn point_values<C>(&self) -> Result<Vec<Value<C>>, NativeEccError>
+    where
+        C: CurveAffine,
+        C::Base: PrimeField<Repr = [u8; 32]>,
+    {
+        Ok(self.points()?.into_iter().map(Value::known).collect())
+    }
+
+    /// The scalars as known values, e.g., for the fields of a circuit.
+    pub fn scalar_values<S: PrimeField<Repr = [u8; 32]>>(
+        &self,
+    ) -> Result<Vec<Value<S>>, NativeEccError> {
+        Ok(self.scalars()?.into_iter().map(Value::known).collect())
+    }
+}
+
+fn decode<F: PrimeField<Repr = [u8; 32]>>(repr: &[u8; 32]) -> Result<F, NativeEccError> {
+    Option::from(F::from_repr(*repr)).ok_or(NativeEccError::InvalidEncoding)
+}
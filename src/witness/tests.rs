@@ -0,0 +1,82 @@
+use ark_std::test_rng;
+use halo2_proofs::arithmetic::Field;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::halo2curves::group::Curve;
+use halo2_proofs::halo2curves::group::Group;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::Fr;
+use halo2curves::grumpkin::G1Affine;
+use halo2curves::grumpkin::G1;
+
+use crate::testing::run_gadget;
+use crate::witness::EcWitnessBundle;
+use crate::NativeECOps;
+use crate::NativeEccError;
+
+#[test]
+fn test_bundle_round_trip() {
+    let mut rng = test_rng();
+    let p = G1::random(&mut rng).to_affine();
+    let s = Fr::random(&mut rng);
+
+    let mut bundle = EcWitnessBundle::default();
+    bundle.push_point(&p).unwrap();
+    bundle.push_scalar(&s);
+    bundle.push_bits([true, false]);
+    assert_eq!(bundle.points::<G1Affine>().unwrap(), [p]);
+    assert_eq!(bundle.scalars::<Fr>().unwrap(), [s]);
+
+    // the witness of a run, dumped from the assigned result
+    let dumped = std::cell::RefCell::new(EcWitnessBundle::default());
+    run_gadget::<G1Affine, Fq, _>(11, |chip, ctx| {
+        let config = chip.config();
+        let p = bundle.point_values::<G1Affine>()?[0];
+        let s = bundle.scalar_values::<Fr>()?[0];
+        let res = chip.point_mul(ctx, config, p, s)?;
+        let mut dumped = dumped.borrow_mut();
+        *dumped = EcWitnessBundle::default();
+        dumped.push_assigned_point(&res)
+    })
+    .unwrap()
+    .assert_satisfied();
+    assert_eq!(
+        dumped.into_inner().points::<G1Affine>().unwrap(),
+        [(p * s).to_affine()]
+    );
+
+    // error cases: the identity, a point not on curve, a non canonical scalar
+    assert!(matches!(
+        bundle.push_point(&G1Affine::from(G1::identity())),
+        Err(NativeEccError::PointAtInfinity)
+    ));
+    let mut invalid = bundle.clone();
+    invalid.points[0].1 = (-Fq::ONE).to_repr();
+    assert!(matches!(
+        invalid.points::<G1Affine>(),
+        Err(NativeEccError::NotOnCurve)
+    ));
+    invalid.scalars[0] = [0xff; 32];
+    assert!(matches!(
+        invalid.scalars::<Fr>(),
+        Err(NativeEccError::InvalidEncoding)
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_bundle_serde() {
+    let mut rng = test_rng();
+    let mut bundle = EcWitnessBundle::default();
+    bundle
+        .push_point(&G1::random(&mut rng).to_affine())
+        .unwrap();
+    bundle.push_scalar(&Fr::random(&mut rng));
+    bundle.push_bits([true]);
+
+    let json = serde_json::to_string(&bundle).unwrap();
+    assert_eq!(
+        serde_json::from_str::<EcWitnessBundle>(&json).unwrap(),
+        bundle
+    );
+}
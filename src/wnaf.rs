@@ -0,0 +1,244 @@
+//! Validation of the signed-digit recoding of a scalar limb, the width-4
+//! NAF, so that a signed-digit scalar mul can trust its digits.
+//!
+//! The prover claims the digits, from the most significant one, next to a
+//! running sum, acc = 2 * acc_prev + d. The recoding is valid if
+//! - each digit is in the table of `WNAF_DIGIT_TABLE`, i.e., 0 or odd in (-8, 8)
+//! - of any 4 consecutive digits, at most one is nonzero
+//! - the running sum ends at the limb
+//!
+//! ```ignore
+//! // configure
+//! let ecc = ECChip::<G1Affine, Fq>::configure(meta);
+//! let wnaf = WnafConfig::configure(meta, &ecc);
+//!
+//! // synthesize
+//! wnaf.load_table(&mut layouter)?;
+//! let digits = wnaf.recode(&mut layouter, &limb)?;
+//! ```
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use halo2_proofs::circuit::AssignedCell;
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::plonk::Advice;
+use halo2_proofs::plonk::Column;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Expression;
+use halo2_proofs::plonk::Selector;
+use halo2_proofs::plonk::TableColumn;
+use halo2_proofs::poly::Rotation;
+
+use crate::backend::CurveAffine;
+use crate::backend::PrimeField;
+use crate::region_ctx::RegionCtx;
+use crate::util::annotation;
+use crate::util::field_decompose_u128;
+use crate::util::wnaf;
+use crate::ECConfig;
+use crate::NativeEccError;
+
+#[cfg(test)]
+mod tests;
+
+/// The width of the NAF.
+pub const WNAF_WINDOW: u32 = 4;
+
+/// The number of digits of the NAF of a 128 bits limb.
+pub const WNAF_DIGITS: usize = 129;
+
+/// The digits of the NAF: 0 and the odd integers in (-2^(w-1), 2^(w-1)).
+pub const WNAF_DIGIT_TABLE: [i64; 9] = [0, 1, -1, 3, -3, 5, -5, 7, -7];
+
+/// The columns, the selectors and the digit table of the recoding check.
+///
+/// The digits and the running sum are assigned to the advice columns of the
+/// chip; the check has a gate and a lookup of its own.
+#[derive(Clone, Copy, Debug)]
+pub struct WnafConfig {
+    digit: Column<Advice>,
+    acc: Column<Advice>,
+    // the digit rows: the table lookup and the non-adjacency
+    q_digit: Selector,
+    // the running sum, from the second digit
+    q_acc: Selector,
+    table: TableColumn,
+}
+
+impl WnafConfig {
+    /// Configures the recoding check over the advice columns of the chip.
+    pub fn configure<C, F>(meta: &mut ConstraintSystem<F>, ecc: &ECConfig<C, F>) -> Self
+    where
+        C: CurveAffine<Base = F>,
+        F: PrimeField,
+    {
+        let (digit, acc) = (ecc.a(), ecc.b());
+        let q_digit = meta.complex_selector();
+        let q_acc = meta.selector();
+        let table = meta.lookup_table_column();
+
+        meta.lookup("wnaf digit", |meta| {
+            // a disabled row looks up 0, which is a digit
+            let q_digit = meta.query_selector(q_digit);
+            let d = meta.query_advice(digit, Rotation::cur());
+            vec![(q_digit * d, table)]
+        });
+
+        meta.create_gate("wnaf", |meta| {
+            let q_digit = meta.query_selector(q_digit);
+            let q_acc = meta.query_selector(q_acc);
+            let d = meta.query_advice(digit, Rotation::cur());
+            let acc = meta.query_advice(acc, Rotation::cur());
+            let acc_prev = meta.query_advice(acc, Rotation::prev());
+            let two = Expression::Constant(F::from(2));
+
+            let mut constraints = vec![("running sum", q_acc * (acc - two * acc_prev - d.clone()))];
+            // the digits of the next w - 1 rows are 0 if d is not
+            let names = ["non-adjacent 1", "non-adjacent 2", "non-adjacent 3"];
+            for (i, name) in names.into_iter().enumerate() {
+                let next = meta.query_advice(digit, Rotation(i as i32 + 1));
+                constraints.push((name, q_digit.clone() * d.clone() * next));
+            }
+            constraints
+        });
+
+        Self {
+            digit,
+            acc,
+            q_digit,
+            q_acc,
+            table,
+        }
+    }
+
+    /// Loads the digit table; once per circuit.
+    pub fn load_table<F: PrimeField>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+    ) -> Result<(), NativeEccError> {
+        layouter.assign_table(
+            || "wnaf digits",
+            |mut table| {
+                for (i, d) in WNAF_DIGIT_TABLE.iter().enumerate() {
+                    table.assign_cell(
+                        || "wnaf digit",
+                        self.table,
+                        i,
+                        || Value::known(digit_to_field::<F>(*d)),
+                    )?;
+                }
+                Ok(())
+            },
+        )?;
+        Ok(())
+    }
+
+    /// The NAF digits of a limb of at most 128 bits, in little endian, checked
+    /// against the limb. The prover computes the digits.
+    pub fn recode<F>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        limb: &AssignedCell<F, F>,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        F: PrimeField<Repr = [u8; 32]>,
+    {
+        let digits = limb
+            .value()
+            .map(|f| wnaf(field_decompose_u128(f).1, WNAF_WINDOW, WNAF_DIGITS));
+        self.assign_recoding(layouter, limb, digits)
+    }
+
+    /// Checks the claimed `WNAF_DIGITS` little endian digits are the NAF of
+    /// a limb, and returns their cells, in little endian.
+    ///
+    /// Takes `WNAF_DIGITS + 3` rows: a row per digit, from the most
+    /// significant one, and 3 rows of zero digits closing the non-adjacency
+    /// check of the last digits.
+    pub fn assign_recoding<F>(
+        &self,
+        layouter: &mut impl Layouter<F>,
+        limb: &AssignedCell<F, F>,
+        digits: Value<Vec<i64>>,
+    ) -> Result<Vec<AssignedCell<F, F>>, NativeEccError>
+    where
+        F: PrimeField,
+    {
+        let mut res = Ok(());
+        let digits = digits.map(|digits| {
+            if digits.len() != WNAF_DIGITS {
+                res = Err(NativeEccError::InvalidInputLength {
+                    expected: WNAF_DIGITS,
+                    actual: digits.len(),
+                });
+            }
+            digits
+        });
+        res?;
+
+        let cells = layouter.assign_region(
+            || "wnaf recoding",
+            |region| {
+                let mut ctx = RegionCtx::new(region, 0);
+
+                //  index  |  a  |  b   | q_digit | q_acc
+                //  -------|-----|------|---------|------
+                //  offset | d_n | acc  |    1    |
+                //         | ... | acc  |    1    |  1
+                //         | d_0 | limb |    1    |  1
+                //         |  0  |      |         |
+                //         |  0  |      |         |
+                //         |  0  |      |         |
+                let mut acc: Option<AssignedCell<F, F>> = None;
+                let mut cells = Vec::with_capacity(WNAF_DIGITS);
+                for i in (0..WNAF_DIGITS).rev() {
+                    let d = digits.as_ref().map(|digits| digit_to_field::<F>(digits[i]));
+                    ctx.enable_selector(&self.q_digit, ctx.offset())?;
+                    let d = ctx.assign_advice(|| annotation("wnaf", "d"), self.digit, d)?;
+                    let value = match &acc {
+                        None => d.value().copied(),
+                        Some(acc) => acc.value().map(|acc| acc.double()) + d.value().copied(),
+                    };
+                    let next = ctx.assign_advice(|| annotation("wnaf", "acc"), self.acc, value)?;
+                    if acc.is_none() {
+                        // the first running sum is the first digit
+                        ctx.constrain_equal(next.cell(), d.cell())?;
+                    } else {
+                        ctx.enable_selector(&self.q_acc, ctx.offset())?;
+                    }
+                    ctx.next();
+                    acc = Some(next);
+                    cells.push(d);
+                }
+                if let Some(acc) = acc {
+                    ctx.constrain_equal(acc.cell(), limb.cell())?;
+                }
+
+                for _ in 1..WNAF_WINDOW {
+                    let zero = ctx.assign_advice(
+                        || annotation("wnaf", "zero"),
+                        self.digit,
+                        Value::known(F::ZERO),
+                    )?;
+                    ctx.constrain_constant(zero.cell(), F::ZERO)?;
+                    ctx.next();
+                }
+
+                cells.reverse();
+                Ok(cells)
+            },
+        )?;
+        Ok(cells)
+    }
+}
+
+fn digit_to_field<F: PrimeField>(d: i64) -> F {
+    let abs = F::from(d.unsigned_abs());
+    if d < 0 {
+        -abs
+    } else {
+        abs
+    }
+}
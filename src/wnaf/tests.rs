@@ -0,0 +1,93 @@
+use halo2_proofs::circuit::Layouter;
+use halo2_proofs::circuit::SimpleFloorPlanner;
+use halo2_proofs::circuit::Value;
+use halo2_proofs::dev::MockProver;
+use halo2_proofs::halo2curves::ff::PrimeField;
+use halo2_proofs::plonk::Circuit;
+use halo2_proofs::plonk::ConstraintSystem;
+use halo2_proofs::plonk::Error;
+use halo2curves::grumpkin::Fq;
+use halo2curves::grumpkin::G1Affine;
+
+use crate::chip::ECChip;
+use crate::config::ECConfig;
+use crate::util::wnaf;
+use crate::wnaf::WnafConfig;
+use crate::wnaf::WNAF_DIGITS;
+use crate::wnaf::WNAF_WINDOW;
+
+#[derive(Default, Debug, Clone)]
+struct WnafTestCircuit {
+    limb: Value<Fq>,
+    // the claimed digits, or the NAF of the limb if None
+    digits: Option<Value<Vec<i64>>>,
+}
+
+impl Circuit<Fq> for WnafTestCircuit {
+    type Config = (ECConfig<G1Affine, Fq>, WnafConfig);
+    type FloorPlanner = SimpleFloorPlanner;
+
+    fn without_witnesses(&self) -> Self {
+        Self::default()
+    }
+
+    fn configure(meta: &mut ConstraintSystem<Fq>) -> Self::Config {
+        let ecc = ECChip::configure(meta);
+        let wnaf = WnafConfig::configure(meta, &ecc);
+        (ecc, wnaf)
+    }
+
+    fn synthesize(
+        &self,
+        (ecc, wnaf): Self::Config,
+        mut layouter: impl Layouter<Fq>,
+    ) -> Result<(), Error> {
+        let ec_chip = ECChip::construct(ecc);
+        wnaf.load_table(&mut layouter)?;
+
+        let limb = ec_chip.assign_scoped(
+            &mut layouter,
+            || "limb",
+            |ecc| ecc.load_private_field(self.limb),
+        )?;
+        let digits = match &self.digits {
+            None => wnaf.recode(&mut layouter, &limb)?,
+            Some(digits) => wnaf.assign_recoding(&mut layouter, &limb, digits.clone())?,
+        };
+        assert_eq!(digits.len(), WNAF_DIGITS);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn test_wnaf_recoding() {
+    let k = 9;
+    let run = |limb: u128, digits: Option<Vec<i64>>| {
+        let circuit = WnafTestCircuit {
+            limb: Value::known(Fq::from_u128(limb)),
+            digits: digits.map(Value::known),
+        };
+        MockProver::run(k, &circuit, vec![]).unwrap()
+    };
+
+    for limb in [0, 1, 0xdead_beef, u128::MAX] {
+        run(limb, None).assert_satisfied();
+    }
+
+    // error case: the binary digits sum to the limb, but are adjacent
+    let limb = 0b11;
+    let mut binary = vec![0; WNAF_DIGITS];
+    binary[0] = 1;
+    binary[1] = 1;
+    assert!(run(limb, Some(binary)).verify().is_err());
+
+    // error case: an even digit
+    let mut even = vec![0; WNAF_DIGITS];
+    even[0] = 2;
+    assert!(run(2, Some(even)).verify().is_err());
+
+    // error case: the NAF of another limb
+    let other = wnaf(5, WNAF_WINDOW, WNAF_DIGITS);
+    assert!(run(7, Some(other)).verify().is_err());
+}